@@ -0,0 +1,106 @@
+//! Decoders for program accounts and instruction return data.
+
+use crate::{
+    Badge, Challenge, ChallengeTracker, CrankConfig, Dispute, DisputeConfig, FeeTracker,
+    ModerationRecord, ReputationConfig, Season, SeasonConfig, SponsorRecord, Team, TokenState,
+    Tournament, Trophy, TrophyCollection, UserProfile, VoteCommitment, VoteStake,
+};
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator};
+
+/// Decode any program account, checking its discriminator.
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> anchor_lang::Result<T> {
+    let mut data = data;
+    T::try_deserialize(&mut data)
+}
+
+pub fn decode_challenge(data: &[u8]) -> anchor_lang::Result<Challenge> {
+    decode(data)
+}
+
+pub fn decode_fee_tracker(data: &[u8]) -> anchor_lang::Result<FeeTracker> {
+    decode(data)
+}
+
+pub fn decode_challenge_tracker(data: &[u8]) -> anchor_lang::Result<ChallengeTracker> {
+    decode(data)
+}
+
+pub fn decode_crank_config(data: &[u8]) -> anchor_lang::Result<CrankConfig> {
+    decode(data)
+}
+
+pub fn decode_dispute_config(data: &[u8]) -> anchor_lang::Result<DisputeConfig> {
+    decode(data)
+}
+
+pub fn decode_dispute(data: &[u8]) -> anchor_lang::Result<Dispute> {
+    decode(data)
+}
+
+pub fn decode_moderation_record(data: &[u8]) -> anchor_lang::Result<ModerationRecord> {
+    decode(data)
+}
+
+pub fn decode_badge(data: &[u8]) -> anchor_lang::Result<Badge> {
+    decode(data)
+}
+
+pub fn decode_reputation_config(data: &[u8]) -> anchor_lang::Result<ReputationConfig> {
+    decode(data)
+}
+
+pub fn decode_season_config(data: &[u8]) -> anchor_lang::Result<SeasonConfig> {
+    decode(data)
+}
+
+pub fn decode_season(data: &[u8]) -> anchor_lang::Result<Season> {
+    decode(data)
+}
+
+pub fn decode_team(data: &[u8]) -> anchor_lang::Result<Team> {
+    decode(data)
+}
+
+pub fn decode_tournament(data: &[u8]) -> anchor_lang::Result<Tournament> {
+    decode(data)
+}
+
+pub fn decode_sponsor_record(data: &[u8]) -> anchor_lang::Result<SponsorRecord> {
+    decode(data)
+}
+
+pub fn decode_trophy(data: &[u8]) -> anchor_lang::Result<Trophy> {
+    decode(data)
+}
+
+pub fn decode_trophy_collection(data: &[u8]) -> anchor_lang::Result<TrophyCollection> {
+    decode(data)
+}
+
+pub fn decode_user_profile(data: &[u8]) -> anchor_lang::Result<UserProfile> {
+    decode(data)
+}
+
+pub fn decode_vote_commitment(data: &[u8]) -> anchor_lang::Result<VoteCommitment> {
+    decode(data)
+}
+
+pub fn decode_vote_stake(data: &[u8]) -> anchor_lang::Result<VoteStake> {
+    decode(data)
+}
+
+pub fn decode_token_state(data: &[u8]) -> anchor_lang::Result<TokenState> {
+    decode(data)
+}
+
+/// Whether raw account data belongs to an account of type `T`, e.g. to filter
+/// the results of `getProgramAccounts`.
+pub fn is_account<T: Discriminator>(data: &[u8]) -> bool {
+    data.starts_with(&T::DISCRIMINATOR)
+}
+
+/// Decode the return data of a view instruction such as `audit_challenge`
+/// or `preview_finalization`, as found in a simulated transaction.
+pub fn decode_return_data<T: AnchorDeserialize>(data: &[u8]) -> std::io::Result<T> {
+    T::try_from_slice(data)
+}
@@ -0,0 +1,174 @@
+//! Mapping of program error numbers back to `ErrorCode` and `TokenError`.
+//!
+//! Both enums are numbered from 6000, so the same number means different
+//! things in each. Challenge instructions only raise `ErrorCode`; token
+//! instructions only raise `TokenError`.
+
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::solana_program::instruction::InstructionError;
+pub use coinpetitive::errors::{ErrorCode, TokenError};
+
+/// Every `ErrorCode` variant, in declaration (and therefore number) order.
+pub const ERROR_CODES: &[ErrorCode] = &[
+    ErrorCode::ChallengeNotActive,
+    ErrorCode::ChallengeStillActive,
+    ErrorCode::InvalidCreator,
+    ErrorCode::InvalidSubmissionId,
+    ErrorCode::VotingPeriodActive,
+    ErrorCode::InvalidVoteCount,
+    ErrorCode::InvalidWinner,
+    ErrorCode::InvalidTokenMint,
+    ErrorCode::MaxParticipantsReached,
+    ErrorCode::AlreadyParticipated,
+    ErrorCode::AlreadyVoted,
+    ErrorCode::SubmissionNotFound,
+    ErrorCode::InvalidTreasury,
+    ErrorCode::InvalidTokenProgram,
+    ErrorCode::InvalidVotingTreasury,
+    ErrorCode::Unauthorized,
+    ErrorCode::MaxVotersReached,
+    ErrorCode::NoSubmissions,
+    ErrorCode::NoVotes,
+    ErrorCode::VoterDidNotVoteForWinner,
+    ErrorCode::NoRewardToDistribute,
+    ErrorCode::InvalidTokenAccount,
+    ErrorCode::MathOverflow,
+    ErrorCode::NoWinnerDeclared,
+    ErrorCode::ArithmeticOverflow,
+    ErrorCode::InsufficientFunds,
+    ErrorCode::ChallengeFrozen,
+    ErrorCode::LedgerMatchesBalances,
+    ErrorCode::ChallengeHasSubmissions,
+    ErrorCode::ChallengeNotCancelled,
+    ErrorCode::NothingToRefund,
+    ErrorCode::RefundsPending,
+    ErrorCode::VoterRewardsPending,
+    ErrorCode::InvalidSchedule,
+    ErrorCode::SubmissionsClosed,
+    ErrorCode::SubmissionPeriodActive,
+    ErrorCode::VotingClosed,
+    ErrorCode::MaxSubmissionsReached,
+    ErrorCode::QuorumMet,
+    ErrorCode::InvalidCrankBounty,
+    ErrorCode::InvalidRunnerUp,
+    ErrorCode::InvalidPlatformTreasury,
+    ErrorCode::InvalidQuorum,
+    ErrorCode::QuorumNotMet,
+    ErrorCode::CommitRevealDisabled,
+    ErrorCode::CommitRevealRequired,
+    ErrorCode::RevealClosed,
+    ErrorCode::InvalidReveal,
+    ErrorCode::WrongVotingMode,
+    ErrorCode::InvalidVoteWeight,
+    ErrorCode::VoterAlreadyRewarded,
+    ErrorCode::StakesLocked,
+    ErrorCode::InvalidJudgePanel,
+    ErrorCode::JudgingDisabled,
+    ErrorCode::NotAJudge,
+    ErrorCode::InvalidScore,
+    ErrorCode::NoScores,
+    ErrorCode::JudgeFeesPending,
+    ErrorCode::ResultsNotProposed,
+    ErrorCode::ResultsAlreadyProposed,
+    ErrorCode::DisputeWindowClosed,
+    ErrorCode::DisputeWindowOpen,
+    ErrorCode::DisputesOpen,
+    ErrorCode::SubmissionDisqualified,
+    ErrorCode::InvalidDisputeConfig,
+    ErrorCode::NotAParticipant,
+    ErrorCode::SponsorshipClosed,
+    ErrorCode::InvalidSponsorship,
+    ErrorCode::SponsorUriTooLong,
+    ErrorCode::MaxSponsorsReached,
+    ErrorCode::PrizeLocked,
+    ErrorCode::InvalidPrize,
+    ErrorCode::InvalidPrizeAccount,
+    ErrorCode::CptPrizeOnly,
+    ErrorCode::InvalidTrophyRank,
+    ErrorCode::InvalidTrophyMetadata,
+    ErrorCode::NotEligibleForBadge,
+    ErrorCode::InsufficientReputation,
+    ErrorCode::InvalidProfile,
+    ErrorCode::InvalidSeason,
+    ErrorCode::InvalidSeasonConfig,
+    ErrorCode::SeasonNotOver,
+    ErrorCode::SeasonAlreadySettled,
+    ErrorCode::InvalidTournament,
+    ErrorCode::InvalidTournamentConfig,
+    ErrorCode::InvalidTournamentRound,
+    ErrorCode::TournamentRoundNotOpen,
+    ErrorCode::NotQualifiedForRound,
+    ErrorCode::TournamentFinished,
+    ErrorCode::InvalidTeam,
+    ErrorCode::InvalidTeamConfig,
+    ErrorCode::NotTeamMember,
+    ErrorCode::TeamFeeAlreadyPaid,
+    ErrorCode::TeamNotEntered,
+    ErrorCode::TeamAlreadySubmitted,
+    ErrorCode::NothingToSplit,
+    ErrorCode::InvalidDuel,
+    ErrorCode::DuelLocked,
+    ErrorCode::DuelClosed,
+    ErrorCode::NotDuelOpponent,
+    ErrorCode::DuelStakesOnly,
+    ErrorCode::DuelVideoLimit,
+    ErrorCode::ChallengeCancelled,
+    ErrorCode::ChallengeNotSettled,
+    ErrorCode::DisputeResolutionOpen,
+    ErrorCode::TournamentRoundCancel,
+    ErrorCode::TeamCanStillEnter,
+    ErrorCode::TeamFeesPending,
+];
+
+/// Every `TokenError` variant, in declaration (and therefore number) order.
+pub const TOKEN_ERRORS: &[TokenError] = &[
+    TokenError::InvalidTokenOwner,
+    TokenError::ExceedsInitialSupplyCap,
+    TokenError::ExceedsMaxSupplyCap,
+    TokenError::InvalidMintIncrement,
+    TokenError::MintingTooFrequent,
+    TokenError::NoMintConditionsMet,
+    TokenError::ExceedsDailySellLimit,
+    TokenError::ArithmeticOverflow,
+    TokenError::Unauthorized,
+    TokenError::InvalidBatchSize,
+    TokenError::WalletAlreadyTracked,
+    TokenError::TooManyRequests,
+    TokenError::InvalidBlockhash,
+    TokenError::NotTokenOwner,
+    TokenError::InvalidTokenMint,
+    TokenError::InsufficientTokenBalance,
+    TokenError::NotAuthorized,
+    TokenError::OwnerMismatch,
+];
+
+fn index(code: u32) -> Option<usize> {
+    code.checked_sub(ERROR_CODE_OFFSET).map(|i| i as usize)
+}
+
+/// `ErrorCode` for a custom program error number.
+pub fn error_code(code: u32) -> Option<ErrorCode> {
+    index(code).and_then(|i| ERROR_CODES.get(i)).copied()
+}
+
+/// `TokenError` for a custom program error number.
+pub fn token_error(code: u32) -> Option<TokenError> {
+    index(code).and_then(|i| TOKEN_ERRORS.get(i)).copied()
+}
+
+/// Custom error number carried by a failed instruction, if any.
+pub fn custom_code(err: &InstructionError) -> Option<u32> {
+    match err {
+        InstructionError::Custom(code) => Some(*code),
+        _ => None,
+    }
+}
+
+/// Error number from the logs of a failed or simulated transaction, which
+/// Anchor prints as `Error Code: <Name>. Error Number: <n>.`
+pub fn code_from_logs<S: AsRef<str>>(logs: &[S]) -> Option<u32> {
+    logs.iter().find_map(|line| {
+        let (_, rest) = line.as_ref().split_once("Error Number: ")?;
+        rest.split('.').next()?.trim().parse().ok()
+    })
+}
@@ -0,0 +1,179 @@
+//! Decoders for the events the program emits into transaction logs.
+//!
+//! Anchor's `emit!` writes each event as a `Program data: <base64>` line,
+//! where the payload is the event's discriminator followed by its borsh
+//! encoding. Only lines written while the Coinpetitive program itself is
+//! executing are decoded, so events logged by other programs in the same
+//! transaction are ignored.
+
+use crate::events::*;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::Engine;
+
+/// Every event the program emits.
+pub enum Event {
+    ChallengeCreated(ChallengeCreated),
+    PrizeFunded(PrizeFunded),
+    ParticipantJoined(ParticipantJoined),
+    ChallengeSponsored(ChallengeSponsored),
+    FeeTrackerUpdated(FeeTrackerUpdated),
+    VideoSubmitted(VideoSubmitted),
+    VoteCommitted(VoteCommitted),
+    VoteCast(VoteCast),
+    SubmissionScored(SubmissionScored),
+    ResultsProposed(ResultsProposed),
+    DisputeRaised(DisputeRaised),
+    DisputeResolved(DisputeResolved),
+    DisputeExpired(DisputeExpired),
+    SubmissionDisqualified(SubmissionDisqualified),
+    ChallengeFinalized(ChallengeFinalized),
+    PayoutIssued(PayoutIssued),
+    ChallengeAuditDiscrepancy(ChallengeAuditDiscrepancy),
+    ChallengeFrozenChanged(ChallengeFrozenChanged),
+    SubmissionsClosed(SubmissionsClosed),
+    CrankBountyChanged(CrankBountyChanged),
+    CrankBountyPaid(CrankBountyPaid),
+    DisputeConfigChanged(DisputeConfigChanged),
+    ReputationConfigChanged(ReputationConfigChanged),
+    ReputationChanged(ReputationChanged),
+    SeasonCreated(SeasonCreated),
+    SeasonPointsAwarded(SeasonPointsAwarded),
+    SeasonPoolFunded(SeasonPoolFunded),
+    SeasonPrizePaid(SeasonPrizePaid),
+    SeasonSettled(SeasonSettled),
+    TournamentCreated(TournamentCreated),
+    TournamentRoundAdded(TournamentRoundAdded),
+    TournamentAdvanced(TournamentAdvanced),
+    TournamentFinished(TournamentFinished),
+    TeamCreated(TeamCreated),
+    TeamFeePaid(TeamFeePaid),
+    TeamFeeRefunded(TeamFeeRefunded),
+    TeamSharePaid(TeamSharePaid),
+    DuelOpened(DuelOpened),
+    DuelAccepted(DuelAccepted),
+    ChallengeCancelled(ChallengeCancelled),
+    RefundIssued(RefundIssued),
+    SponsorRefunded(SponsorRefunded),
+    StakeWithdrawn(StakeWithdrawn),
+    BadgeMinted(BadgeMinted),
+    UserProfileCreated(UserProfileCreated),
+    WalletMilestoneReached(WalletMilestoneReached),
+    TrophyCollectionCreated(TrophyCollectionCreated),
+    TrophyMinted(TrophyMinted),
+    ChallengeClosed(ChallengeClosed),
+}
+
+fn decode_as<T: AnchorDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    let body = data.strip_prefix(&T::DISCRIMINATOR[..])?;
+    T::try_from_slice(body).ok()
+}
+
+/// Decode one event payload (discriminator included). Unknown discriminators
+/// and malformed bodies return `None`.
+pub fn decode_event(data: &[u8]) -> Option<Event> {
+    macro_rules! try_events {
+        ($($event:ident),* $(,)?) => {
+            $(
+                if data.starts_with(&$event::DISCRIMINATOR) {
+                    return decode_as::<$event>(data).map(Event::$event);
+                }
+            )*
+        };
+    }
+    try_events!(
+        ChallengeCreated,
+        PrizeFunded,
+        ParticipantJoined,
+        ChallengeSponsored,
+        FeeTrackerUpdated,
+        VideoSubmitted,
+        VoteCommitted,
+        VoteCast,
+        SubmissionScored,
+        ResultsProposed,
+        DisputeRaised,
+        DisputeResolved,
+        DisputeExpired,
+        SubmissionDisqualified,
+        ChallengeFinalized,
+        PayoutIssued,
+        ChallengeAuditDiscrepancy,
+        ChallengeFrozenChanged,
+        SubmissionsClosed,
+        CrankBountyChanged,
+        CrankBountyPaid,
+        DisputeConfigChanged,
+        ReputationConfigChanged,
+        ReputationChanged,
+        SeasonCreated,
+        SeasonPointsAwarded,
+        SeasonPoolFunded,
+        SeasonPrizePaid,
+        SeasonSettled,
+        TournamentCreated,
+        TournamentRoundAdded,
+        TournamentAdvanced,
+        TournamentFinished,
+        TeamCreated,
+        TeamFeePaid,
+        TeamFeeRefunded,
+        TeamSharePaid,
+        DuelOpened,
+        DuelAccepted,
+        ChallengeCancelled,
+        RefundIssued,
+        SponsorRefunded,
+        StakeWithdrawn,
+        BadgeMinted,
+        UserProfileCreated,
+        WalletMilestoneReached,
+        TrophyCollectionCreated,
+        TrophyMinted,
+        ChallengeClosed,
+    );
+    None
+}
+
+/// Decode every Coinpetitive event in a transaction's log messages, in the
+/// order they were emitted.
+pub fn events_from_logs<S: AsRef<str>>(logs: &[S]) -> Vec<Event> {
+    let program_id = crate::ID.to_string();
+    // Programs currently executing, innermost last
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        let line = line.as_ref();
+        if let Some(rest) = line.strip_prefix("Program data: ") {
+            if stack.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            let Ok(data) = base64::engine::general_purpose::STANDARD.decode(rest.trim()) else {
+                continue;
+            };
+            events.extend(decode_event(&data));
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            let (Some(program), Some(verb)) = (words.next(), words.next()) else {
+                continue;
+            };
+            match verb {
+                "invoke" => stack.push(program),
+                "success" | "failed" => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    events
+}
+
+/// Encode an event the way `emit!` logs it. Used to build test fixtures.
+pub fn event_log_line<T: anchor_lang::Event>(event: &T) -> String {
+    format!(
+        "Program data: {}",
+        base64::engine::general_purpose::STANDARD.encode(event.data())
+    )
+}
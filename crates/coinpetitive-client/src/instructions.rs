@@ -0,0 +1,1198 @@
+//! Typed builders for every program instruction.
+//!
+//! Builders take the wallets and the challenge involved and derive every PDA
+//! and token account themselves. Token accounts are Token-2022 ATAs of `mint`,
+//! except prize accounts, which follow the challenge's [`Prize`].
+
+use crate::pda;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use coinpetitive::state::{Challenge, DisqualificationReason, PrizeKind};
+use coinpetitive::{accounts, instruction, CreateChallengeParams};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// What a challenge's winners are paid in, and where that is held.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Prize {
+    pub kind: PrizeKind,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+}
+
+impl Prize {
+    /// The default prize, paid in `mint` out of the challenge's CPT fees.
+    pub fn cpt(mint: &Pubkey) -> Self {
+        Self {
+            kind: PrizeKind::Cpt,
+            mint: *mint,
+            token_program: crate::token_2022_program_id(),
+        }
+    }
+
+    /// The prize recorded on `challenge`.
+    pub fn of(challenge: &Challenge) -> Self {
+        Self {
+            kind: challenge.prize_kind,
+            mint: challenge.prize_mint,
+            token_program: challenge.prize_token_program,
+        }
+    }
+
+    /// Where the challenge holds the prize: the treasury PDA for SOL, its
+    /// token account otherwise.
+    pub fn escrow(&self, challenge: &Pubkey) -> Pubkey {
+        self.account(&pda::treasury(challenge).0)
+    }
+
+    /// Where `wallet` is paid the prize: the wallet itself for SOL, its token
+    /// account otherwise.
+    pub fn account(&self, wallet: &Pubkey) -> Pubkey {
+        match self.kind {
+            PrizeKind::Sol => *wallet,
+            PrizeKind::Cpt | PrizeKind::Token => {
+                pda::token_account_in(wallet, &self.mint, &self.token_program)
+            }
+        }
+    }
+}
+
+/// `challenge` is a fresh keypair that must also sign the transaction.
+pub fn create_challenge(
+    user: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    program_account: &Pubkey,
+    params: CreateChallengeParams,
+) -> Instruction {
+    build(
+        accounts::CreateChallenge {
+            user: *user,
+            challenge: *challenge,
+            treasury: pda::treasury(challenge).0,
+            program_account: *program_account,
+            system_program: system_program::ID,
+            token_program: crate::token_2022_program_id(),
+            token_mint: *mint,
+            creator_token_account: pda::token_account(user, mint),
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            associated_token_program: crate::associated_token_program_id(),
+            voting_treasury: pda::voting_treasury(challenge).0,
+            voting_treasury_token_account: pda::voting_treasury_token_account(challenge, mint),
+            user_profile: pda::user_profile(user).0,
+            token_state: pda::token_state().0,
+            reputation_config: pda::reputation_config().0,
+        },
+        instruction::CreateChallenge { params },
+    )
+}
+
+/// `tournament` is required when the challenge is one of its rounds.
+pub fn pay_participation_fee(
+    participant: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    tournament: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::PayParticipationFee {
+            participant: *participant,
+            challenge: *challenge,
+            tournament,
+            treasury: pda::treasury(challenge).0,
+            token_program: crate::token_2022_program_id(),
+            participant_token_account: pda::token_account(participant, mint),
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            fee_tracker: pda::fee_tracker().0,
+            user_profile: pda::user_profile(participant).0,
+            token_state: pda::token_state().0,
+            system_program: system_program::ID,
+        },
+        instruction::PayParticipationFee {},
+    )
+}
+
+/// Register `members`, captain first, as a team for `challenge` with each
+/// member's share of the fee and payouts in basis points. The team's CPT
+/// account is created with it; sent by the captain.
+pub fn create_team(
+    captain: &Pubkey,
+    challenge: &Pubkey,
+    members: &[Pubkey],
+    shares_bps: &[u16],
+) -> Instruction {
+    let team = pda::team(challenge, captain).0;
+    build(
+        accounts::CreateTeam {
+            captain: *captain,
+            challenge: *challenge,
+            team,
+            team_profile: pda::user_profile(&team).0,
+            captain_profile: pda::user_profile(captain).0,
+            token_state: pda::token_state().0,
+            team_token_account: pda::token_account(&team, &crate::cpt_mint()),
+            cpt_mint: crate::cpt_mint(),
+            token_program: crate::token_2022_program_id(),
+            associated_token_program: crate::associated_token_program_id(),
+            system_program: system_program::ID,
+        },
+        instruction::CreateTeam {
+            members: members.to_vec(),
+            shares_bps: shares_bps.to_vec(),
+        },
+    )
+}
+
+/// Create `owner`'s token account for a token `prize` unless it exists;
+/// anyone can pay for it. A team needs one in a Token prize's mint to be
+/// paid, which `create_team` doesn't make.
+pub fn create_prize_account(payer: &Pubkey, owner: &Pubkey, prize: &Prize) -> Instruction {
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+        payer,
+        owner,
+        &prize.mint,
+        &prize.token_program,
+    )
+}
+
+/// Pay `payer`'s part of the team's participation fee, or with `all` (captain
+/// only) every part still owed.
+pub fn pay_team_fee(
+    payer: &Pubkey,
+    challenge: &Pubkey,
+    captain: &Pubkey,
+    mint: &Pubkey,
+    all: bool,
+) -> Instruction {
+    build(
+        accounts::PayTeamFee {
+            payer: *payer,
+            team: pda::team(challenge, captain).0,
+            challenge: *challenge,
+            treasury: pda::treasury(challenge).0,
+            token_program: crate::token_2022_program_id(),
+            payer_token_account: pda::token_account(payer, mint),
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            fee_tracker: pda::fee_tracker().0,
+            system_program: system_program::ID,
+        },
+        instruction::PayTeamFee { all },
+    )
+}
+
+/// Hand `member` back their part of the fee of a team that never entered;
+/// anyone can send it once the team can no longer enter.
+pub fn refund_team_fee(
+    payer: &Pubkey,
+    challenge: &Pubkey,
+    captain: &Pubkey,
+    mint: &Pubkey,
+    member: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::RefundTeamFee {
+            payer: *payer,
+            team: pda::team(challenge, captain).0,
+            challenge: *challenge,
+            token_program: crate::token_2022_program_id(),
+            treasury: pda::treasury(challenge).0,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            member_token_account: pda::token_account(member, mint),
+        },
+        instruction::RefundTeamFee { member: *member },
+    )
+}
+
+/// Pay out the team's balance in `currency` to `members`, in the team's
+/// member order, by their shares; anyone can send it once the challenge is
+/// over. Use [`Prize::cpt`] for refunded fees and [`Prize::of`] for a prize.
+pub fn split_team_reward(
+    authority: &Pubkey,
+    challenge: &Pubkey,
+    captain: &Pubkey,
+    members: &[Pubkey],
+    currency: &Prize,
+) -> Instruction {
+    let team = pda::team(challenge, captain).0;
+    let mut ix = build(
+        accounts::SplitTeamReward {
+            authority: *authority,
+            team,
+            challenge: *challenge,
+            team_token_account: currency.account(&team),
+            mint: currency.mint,
+            token_program: currency.token_program,
+        },
+        instruction::SplitTeamReward {},
+    );
+    ix.accounts.extend(
+        members
+            .iter()
+            .map(|member| AccountMeta::new(currency.account(member), false)),
+    );
+    ix
+}
+
+/// Turn `creator`'s fresh challenge into a duel staking `stake`, against
+/// `opponent` or, with `None`, whoever matches the stake by `accept_by`.
+pub fn open_duel(
+    creator: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    stake: u64,
+    opponent: Option<Pubkey>,
+    accept_by: i64,
+) -> Instruction {
+    build(
+        accounts::OpenDuel {
+            creator: *creator,
+            challenge: *challenge,
+            user_profile: pda::user_profile(creator).0,
+            token_program: crate::token_2022_program_id(),
+            creator_token_account: pda::token_account(creator, mint),
+            treasury: pda::treasury(challenge).0,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+        },
+        instruction::OpenDuel {
+            stake,
+            opponent,
+            accept_by,
+        },
+    )
+}
+
+/// Match a duel's stake as its opponent.
+pub fn accept_duel(opponent: &Pubkey, challenge: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptDuel {
+            opponent: *opponent,
+            challenge: *challenge,
+            user_profile: pda::user_profile(opponent).0,
+            token_state: pda::token_state().0,
+            token_program: crate::token_2022_program_id(),
+            opponent_token_account: pda::token_account(opponent, mint),
+            treasury: pda::treasury(challenge).0,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            system_program: system_program::ID,
+        },
+        instruction::AcceptDuel {},
+    )
+}
+
+/// Escrow a challenge's whole reward as `prize` instead of paying it out of
+/// CPT fees. Only before anyone has joined or sponsored it.
+pub fn fund_prize(creator: &Pubkey, challenge: &Pubkey, prize: &Prize) -> Instruction {
+    build(
+        accounts::FundPrize {
+            creator: *creator,
+            challenge: *challenge,
+            prize_mint: prize.mint,
+            prize_token_program: prize.token_program,
+            creator_prize_account: prize.account(creator),
+            treasury: pda::treasury(challenge).0,
+            prize_escrow: prize.escrow(challenge),
+            associated_token_program: crate::associated_token_program_id(),
+            system_program: system_program::ID,
+        },
+        instruction::FundPrize { kind: prize.kind },
+    )
+}
+
+/// Add `amount` CPT to a challenge's reward while it still takes entries.
+/// `metadata_uri` replaces the sponsor's branding when given.
+pub fn sponsor_challenge(
+    sponsor: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    metadata_uri: Option<String>,
+) -> Instruction {
+    build(
+        accounts::SponsorChallenge {
+            sponsor: *sponsor,
+            challenge: *challenge,
+            sponsor_record: pda::sponsor_record(challenge, sponsor).0,
+            token_program: crate::token_2022_program_id(),
+            sponsor_token_account: pda::token_account(sponsor, mint),
+            treasury: pda::treasury(challenge).0,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            system_program: system_program::ID,
+        },
+        instruction::SponsorChallenge {
+            amount,
+            metadata_uri,
+        },
+    )
+}
+
+/// `team` is the team `participant` captains, when submitting its video.
+pub fn submit_video(
+    participant: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    video_reference: &Pubkey,
+    video_url: String,
+    team: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::SubmitVideo {
+            participant: *participant,
+            challenge: *challenge,
+            team,
+            treasury: pda::treasury(challenge).0,
+            token_program: crate::token_2022_program_id(),
+            participant_token_account: pda::token_account(participant, mint),
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            video_reference: *video_reference,
+            user_profile: pda::user_profile(participant).0,
+        },
+        instruction::SubmitVideo { video_url },
+    )
+}
+
+/// `weight` must be 1 unless the challenge uses quadratic voting, where it
+/// costs `voting_fee * weight²`.
+pub fn vote_for_submission(
+    voter: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    submission_id: &Pubkey,
+    weight: u64,
+) -> Instruction {
+    build(
+        accounts::VoteForSubmission {
+            voter: *voter,
+            challenge: *challenge,
+            treasury: pda::treasury(challenge).0,
+            token_program: crate::token_2022_program_id(),
+            voter_token_account: pda::token_account(voter, mint),
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            submission_id: *submission_id,
+            voting_treasury: pda::voting_treasury(challenge).0,
+            voting_treasury_token_account: pda::voting_treasury_token_account(challenge, mint),
+            user_profile: pda::user_profile(voter).0,
+            token_state: pda::token_state().0,
+            system_program: system_program::ID,
+        },
+        instruction::VoteForSubmission { weight },
+    )
+}
+
+/// Vote in a stake-weighted challenge, paying the voting fee and locking
+/// `stake` as the vote's weight until `withdraw_stake`.
+pub fn stake_vote(
+    voter: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    submission_id: &Pubkey,
+    stake: u64,
+) -> Instruction {
+    build(
+        accounts::StakeVote {
+            voter: *voter,
+            challenge: *challenge,
+            vote_stake: pda::vote_stake(challenge, voter).0,
+            token_program: crate::token_2022_program_id(),
+            voter_token_account: pda::token_account(voter, mint),
+            submission_id: *submission_id,
+            voting_treasury: pda::voting_treasury(challenge).0,
+            voting_treasury_token_account: pda::voting_treasury_token_account(challenge, mint),
+            user_profile: pda::user_profile(voter).0,
+            token_state: pda::token_state().0,
+            system_program: system_program::ID,
+        },
+        instruction::StakeVote { stake },
+    )
+}
+
+/// Return everything `voter` staked once the challenge is settled; anyone can
+/// send it.
+pub fn withdraw_stake(
+    payer: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    voter: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::WithdrawStake {
+            payer: *payer,
+            challenge: *challenge,
+            vote_stake: pda::vote_stake(challenge, voter).0,
+            voter: *voter,
+            token_program: crate::token_2022_program_id(),
+            voting_treasury: pda::voting_treasury(challenge).0,
+            voting_treasury_token_account: pda::voting_treasury_token_account(challenge, mint),
+            voter_token_account: pda::token_account(voter, mint),
+        },
+        instruction::WithdrawStake {},
+    )
+}
+
+/// Commit to `VoteCommitment::hash(challenge, voter, submission_id, salt)` in
+/// a commit-reveal challenge, paying the voting fee.
+pub fn commit_vote(
+    voter: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    commitment: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::CommitVote {
+            voter: *voter,
+            challenge: *challenge,
+            vote_commitment: pda::vote_commitment(challenge, voter).0,
+            token_program: crate::token_2022_program_id(),
+            voter_token_account: pda::token_account(voter, mint),
+            voting_treasury: pda::voting_treasury(challenge).0,
+            voting_treasury_token_account: pda::voting_treasury_token_account(challenge, mint),
+            user_profile: pda::user_profile(voter).0,
+            token_state: pda::token_state().0,
+            system_program: system_program::ID,
+        },
+        instruction::CommitVote { commitment },
+    )
+}
+
+/// Open a commitment made with `commit_vote` during the reveal window.
+pub fn reveal_vote(
+    voter: &Pubkey,
+    challenge: &Pubkey,
+    submission_id: &Pubkey,
+    salt: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::RevealVote {
+            voter: *voter,
+            challenge: *challenge,
+            vote_commitment: pda::vote_commitment(challenge, voter).0,
+            submission_id: *submission_id,
+        },
+        instruction::RevealVote { salt },
+    )
+}
+
+/// Judges only; scores from 0 to `MAX_SCORE`, replacing an earlier score.
+pub fn score_submission(
+    judge: &Pubkey,
+    challenge: &Pubkey,
+    submission_id: &Pubkey,
+    score: u8,
+) -> Instruction {
+    build(
+        accounts::ScoreSubmission {
+            judge: *judge,
+            challenge: *challenge,
+            submission_id: *submission_id,
+        },
+        instruction::ScoreSubmission { score },
+    )
+}
+
+/// Pay `judge` their share of a finalized challenge's judge fees; anyone can
+/// send it.
+pub fn claim_judge_fee(
+    payer: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    judge: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ClaimJudgeFee {
+            payer: *payer,
+            challenge: *challenge,
+            token_program: crate::token_2022_program_id(),
+            treasury: pda::treasury(challenge).0,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            judge_token_account: pda::token_account(judge, mint),
+        },
+        instruction::ClaimJudgeFee { judge: *judge },
+    )
+}
+
+pub fn close_submissions(payer: &Pubkey, challenge: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseSubmissions {
+            payer: *payer,
+            challenge: *challenge,
+        },
+        instruction::CloseSubmissions {},
+    )
+}
+
+/// Admin only; creates the crank config on first use.
+pub fn set_crank_bounty(authority: &Pubkey, finalize_bounty_bps: u16) -> Instruction {
+    build(
+        accounts::SetCrankBounty {
+            authority: *authority,
+            challenge_tracker: pda::challenge_tracker().0,
+            crank_config: pda::crank_config().0,
+            system_program: system_program::ID,
+        },
+        instruction::SetCrankBounty {
+            finalize_bounty_bps,
+        },
+    )
+}
+
+/// Admin only; creates the dispute config on first use.
+pub fn set_dispute_config(authority: &Pubkey, bond: u64, window: i64) -> Instruction {
+    build(
+        accounts::SetDisputeConfig {
+            authority: *authority,
+            challenge_tracker: pda::challenge_tracker().0,
+            dispute_config: pda::dispute_config().0,
+            system_program: system_program::ID,
+        },
+        instruction::SetDisputeConfig { bond, window },
+    )
+}
+
+/// Require `min_creator_reputation` of anyone creating a challenge that
+/// rewards `large_reward` or more (admin only); zero turns it off.
+pub fn set_reputation_config(
+    authority: &Pubkey,
+    large_reward: u64,
+    min_creator_reputation: u64,
+) -> Instruction {
+    build(
+        accounts::SetReputationConfig {
+            authority: *authority,
+            challenge_tracker: pda::challenge_tracker().0,
+            reputation_config: pda::reputation_config().0,
+            system_program: system_program::ID,
+        },
+        instruction::SetReputationConfig {
+            large_reward,
+            min_creator_reputation,
+        },
+    )
+}
+
+/// Open season `season_id`, the one after the latest (admin only). It runs
+/// from `start_time` to `end_time`, collects `pool_share_bps` of each CPT
+/// platform fee and pays the top `payout_places` of its leaderboard.
+pub fn create_season(
+    authority: &Pubkey,
+    season_id: u64,
+    start_time: i64,
+    end_time: i64,
+    pool_share_bps: u16,
+    payout_places: u8,
+) -> Instruction {
+    build(
+        accounts::CreateSeason {
+            authority: *authority,
+            challenge_tracker: pda::challenge_tracker().0,
+            season_config: pda::season_config().0,
+            season: pda::season(season_id).0,
+            season_token_account: pda::season_token_account(season_id),
+            cpt_mint: crate::cpt_mint(),
+            token_program: crate::token_2022_program_id(),
+            associated_token_program: crate::associated_token_program_id(),
+            system_program: system_program::ID,
+        },
+        instruction::CreateSeason {
+            season_id,
+            start_time,
+            end_time,
+            pool_share_bps,
+            payout_places,
+        },
+    )
+}
+
+/// Pay out an ended season; anyone can send it. `placed` are the wallets
+/// [`Season::placed`] returns, best first, whose CPT accounts are paid.
+/// `platform_treasury_token_account` is only paid when nobody scored.
+///
+/// [`Season::placed`]: crate::Season::placed
+pub fn settle_season(
+    authority: &Pubkey,
+    season_id: u64,
+    placed: &[Pubkey],
+    platform_treasury_token_account: &Pubkey,
+) -> Instruction {
+    let mut ix = build(
+        accounts::SettleSeason {
+            authority: *authority,
+            season: pda::season(season_id).0,
+            season_token_account: pda::season_token_account(season_id),
+            challenge_tracker: pda::challenge_tracker().0,
+            platform_treasury_token_account: *platform_treasury_token_account,
+            token_program: crate::token_2022_program_id(),
+        },
+        instruction::SettleSeason {},
+    );
+    ix.accounts.extend(
+        placed
+            .iter()
+            .map(|wallet| AccountMeta::new(pda::token_account(wallet, &crate::cpt_mint()), false)),
+    );
+    ix
+}
+
+/// Start `creator`'s tournament number `tournament_id`, escrowing `prize`
+/// CPT from their account. It runs `round_count` rounds, the top
+/// `advance_count` submitters of each going through to the next; with
+/// `roll_fees` the rounds' leftover fees are added to the prize.
+pub fn create_tournament(
+    creator: &Pubkey,
+    tournament_id: u64,
+    round_count: u8,
+    advance_count: u8,
+    prize: u64,
+    roll_fees: bool,
+) -> Instruction {
+    let tournament = pda::tournament(creator, tournament_id).0;
+    build(
+        accounts::CreateTournament {
+            creator: *creator,
+            tournament,
+            tournament_token_account: pda::tournament_token_account(&tournament),
+            creator_token_account: pda::token_account(creator, &crate::cpt_mint()),
+            cpt_mint: crate::cpt_mint(),
+            token_program: crate::token_2022_program_id(),
+            associated_token_program: crate::associated_token_program_id(),
+            system_program: system_program::ID,
+        },
+        instruction::CreateTournament {
+            tournament_id,
+            round_count,
+            advance_count,
+            prize,
+            roll_fees,
+        },
+    )
+}
+
+/// Make `challenge`, a fresh one of the tournament's creator, its next round.
+pub fn add_tournament_round(
+    creator: &Pubkey,
+    tournament: &Pubkey,
+    challenge: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::AddTournamentRound {
+            creator: *creator,
+            tournament: *tournament,
+            challenge: *challenge,
+        },
+        instruction::AddTournamentRound {},
+    )
+}
+
+/// Qualify the top submitters of a settled `round` for the next one; anyone
+/// can send it.
+pub fn advance_tournament(authority: &Pubkey, tournament: &Pubkey, round: &Pubkey) -> Instruction {
+    build(
+        accounts::AdvanceTournament {
+            authority: *authority,
+            tournament: *tournament,
+            round: *round,
+        },
+        instruction::AdvanceTournament {},
+    )
+}
+
+/// Release the escrow to `recipient`: the final's winner once it settles, or
+/// the creator if the current round was cancelled. Anyone can send it.
+pub fn finish_tournament(
+    authority: &Pubkey,
+    tournament: &Pubkey,
+    round: &Pubkey,
+    recipient: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::FinishTournament {
+            authority: *authority,
+            tournament: *tournament,
+            round: *round,
+            tournament_token_account: pda::tournament_token_account(tournament),
+            recipient_token_account: pda::token_account(recipient, &crate::cpt_mint()),
+            token_program: crate::token_2022_program_id(),
+        },
+        instruction::FinishTournament {},
+    )
+}
+
+/// Open the dispute window once voting has ended; anyone can send it.
+pub fn propose_results(authority: &Pubkey, challenge: &Pubkey) -> Instruction {
+    build(
+        accounts::ProposeResults {
+            authority: *authority,
+            challenge: *challenge,
+            dispute_config: pda::dispute_config().0,
+        },
+        instruction::ProposeResults {},
+    )
+}
+
+/// Participants only; posts the challenge's dispute bond against a submission.
+pub fn raise_dispute(
+    disputer: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    submission_id: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::RaiseDispute {
+            disputer: *disputer,
+            challenge: *challenge,
+            dispute: pda::dispute(challenge, submission_id).0,
+            submission_id: *submission_id,
+            token_program: crate::token_2022_program_id(),
+            disputer_token_account: pda::token_account(disputer, mint),
+            treasury: pda::treasury(challenge).0,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            system_program: system_program::ID,
+        },
+        instruction::RaiseDispute {},
+    )
+}
+
+/// The admin or a judge; `upheld` disqualifies the submission and returns the
+/// bond, otherwise the bond is slashed.
+pub fn resolve_dispute(
+    resolver: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    submission_id: &Pubkey,
+    disputer: &Pubkey,
+    submitter: &Pubkey,
+    upheld: bool,
+) -> Instruction {
+    // Whoever the resolution goes against loses reputation
+    let loser = if upheld { submitter } else { disputer };
+    build(
+        accounts::ResolveDispute {
+            resolver: *resolver,
+            challenge: *challenge,
+            dispute: pda::dispute(challenge, submission_id).0,
+            disputer: *disputer,
+            challenge_tracker: pda::challenge_tracker().0,
+            token_program: crate::token_2022_program_id(),
+            treasury: pda::treasury(challenge).0,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            disputer_token_account: pda::token_account(disputer, mint),
+            loser_profile: pda::user_profile(loser).0,
+        },
+        instruction::ResolveDispute { upheld },
+    )
+}
+
+/// Anyone, once a dispute has gone unresolved past the resolution period;
+/// returns the bond and leaves the submission ranked.
+pub fn expire_dispute(
+    caller: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    submission_id: &Pubkey,
+    disputer: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ExpireDispute {
+            caller: *caller,
+            challenge: *challenge,
+            dispute: pda::dispute(challenge, submission_id).0,
+            disputer: *disputer,
+            token_program: crate::token_2022_program_id(),
+            treasury: pda::treasury(challenge).0,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            disputer_token_account: pda::token_account(disputer, mint),
+        },
+        instruction::ExpireDispute {},
+    )
+}
+
+/// The admin or a judge; the reason decides whether `submitter` gets the
+/// submission fee back.
+pub fn disqualify_submission(
+    moderator: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    submission_id: &Pubkey,
+    submitter: &Pubkey,
+    reason: DisqualificationReason,
+) -> Instruction {
+    build(
+        accounts::DisqualifySubmission {
+            moderator: *moderator,
+            challenge: *challenge,
+            moderation_record: pda::moderation_record(challenge, submission_id).0,
+            submission_id: *submission_id,
+            challenge_tracker: pda::challenge_tracker().0,
+            token_program: crate::token_2022_program_id(),
+            treasury: pda::treasury(challenge).0,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            submitter_token_account: pda::token_account(submitter, mint),
+            submitter_profile: pda::user_profile(submitter).0,
+            system_program: system_program::ID,
+        },
+        instruction::DisqualifySubmission { reason },
+    )
+}
+
+/// Who receives what when a challenge's results are settled. Payees' accounts
+/// are in the prize's mint (see [`Prize::account`]); the bounty, if any, goes
+/// to `authority`'s own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SettleResultsAccounts {
+    pub authority: Pubkey,
+    pub challenge: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub prize: Prize,
+    /// Wallet behind the winning submission.
+    pub winner: Pubkey,
+    /// Wallet behind the runner-up, if it is paid.
+    pub runner_up: Option<Pubkey>,
+    /// Whether the winner and runner-up have profiles for the program to
+    /// credit; see `rpc::has_profile`.
+    pub winner_has_profile: bool,
+    pub runner_up_has_profile: bool,
+    /// Season running at settlement, which the program requires while one
+    /// is; see `rpc::running_season`.
+    pub season: Option<u64>,
+    /// Tournament the leftover fees roll into instead of going to the
+    /// creator, for a round with `fees_to_tournament` set.
+    pub tournament: Option<Pubkey>,
+    pub winner_token_account: Pubkey,
+    pub runnerup_token_account: Pubkey,
+    pub platform_treasury_token_account: Pubkey,
+}
+
+pub fn settle_results(keys: &SettleResultsAccounts) -> Instruction {
+    build(
+        accounts::SettleResults {
+            authority: keys.authority,
+            challenge: keys.challenge,
+            token_program: crate::token_2022_program_id(),
+            winner_token_account: keys.winner_token_account,
+            runnerup_token_account: keys.runnerup_token_account,
+            treasury: pda::treasury(&keys.challenge).0,
+            treasury_token_account: pda::treasury_token_account(&keys.challenge, &keys.mint),
+            platform_treasury_token_account: keys.platform_treasury_token_account,
+            prize_escrow: keys.prize.escrow(&keys.challenge),
+            prize_token_program: keys.prize.token_program,
+            prize_mint: keys.prize.mint,
+            creator_token_account: match keys.tournament {
+                Some(tournament) => pda::tournament_token_account(&tournament),
+                None => pda::token_account(&keys.creator, &keys.mint),
+            },
+            creator: keys.creator,
+            challenge_tracker: pda::challenge_tracker().0,
+            crank_config: pda::crank_config().0,
+            cranker_token_account: keys.prize.account(&keys.authority),
+            winner_profile: keys
+                .winner_has_profile
+                .then(|| pda::user_profile(&keys.winner).0),
+            // A winner who also placed second is credited on one profile
+            runnerup_profile: keys
+                .runner_up
+                .filter(|runner_up| keys.runner_up_has_profile && *runner_up != keys.winner)
+                .map(|runner_up| pda::user_profile(&runner_up).0),
+            season_config: pda::season_config().0,
+            season: keys.season.map(|id| pda::season(id).0),
+            season_token_account: keys.season.map(pda::season_token_account),
+            system_program: system_program::ID,
+        },
+        instruction::SettleResults {},
+    )
+}
+
+/// `season` is the challenge's own, where the voter scores season points;
+/// zero when it settled outside any season.
+pub fn distribute_voting_treasury(
+    authority: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    voter: &Pubkey,
+    winning_voters_count: u64,
+    season: u64,
+) -> Instruction {
+    build(
+        accounts::DistributeVotingTreasury {
+            authority: *authority,
+            challenge: *challenge,
+            token_program: crate::token_2022_program_id(),
+            voting_treasury: pda::voting_treasury(challenge).0,
+            voting_treasury_token_account: pda::voting_treasury_token_account(challenge, mint),
+            voter_token_account: pda::token_account(voter, mint),
+            voter_profile: pda::user_profile(voter).0,
+            season: (season != 0).then(|| pda::season(season).0),
+        },
+        instruction::DistributeVotingTreasury {
+            voter: *voter,
+            voter_index: winning_voters_count,
+        },
+    )
+}
+
+pub fn claim_creator_reward(creator: &Pubkey, challenge: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimCreatorReward {
+            creator: *creator,
+            challenge: *challenge,
+            token_program: crate::token_2022_program_id(),
+            treasury: pda::treasury(challenge).0,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            creator_token_account: pda::token_account(creator, mint),
+            system_program: system_program::ID,
+        },
+        instruction::ClaimCreatorReward {},
+    )
+}
+
+/// Cancel a challenge as its creator (before any submission) or the admin.
+pub fn cancel_challenge(authority: &Pubkey, challenge: &Pubkey, creator: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelChallenge {
+            authority: *authority,
+            challenge: *challenge,
+            challenge_tracker: pda::challenge_tracker().0,
+            creator_profile: pda::user_profile(creator).0,
+        },
+        instruction::CancelChallenge {},
+    )
+}
+
+/// Refund `wallet`'s participation and voting fees from a cancelled
+/// challenge. Anyone can send this; the tokens go to `wallet`'s ATA.
+pub fn claim_refund(
+    payer: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    wallet: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ClaimRefund {
+            payer: *payer,
+            challenge: *challenge,
+            token_program: crate::token_2022_program_id(),
+            treasury: pda::treasury(challenge).0,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            voting_treasury: pda::voting_treasury(challenge).0,
+            voting_treasury_token_account: pda::voting_treasury_token_account(challenge, mint),
+            wallet_token_account: pda::token_account(wallet, mint),
+        },
+        instruction::ClaimRefund { wallet: *wallet },
+    )
+}
+
+/// Return `sponsor`'s deposits from a cancelled challenge and close their
+/// record. Anyone can send this; the tokens go to `sponsor`'s ATA.
+pub fn claim_sponsor_refund(
+    payer: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    sponsor: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ClaimSponsorRefund {
+            payer: *payer,
+            challenge: *challenge,
+            sponsor_record: pda::sponsor_record(challenge, sponsor).0,
+            sponsor: *sponsor,
+            token_program: crate::token_2022_program_id(),
+            treasury: pda::treasury(challenge).0,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            sponsor_token_account: pda::token_account(sponsor, mint),
+        },
+        instruction::ClaimSponsorRefund {},
+    )
+}
+
+/// Mint the trophy NFT for `rank` (1 for the winner, 2 for the runner-up) of
+/// a settled challenge to `winner`, the wallet behind that place.
+pub fn mint_trophy(payer: &Pubkey, challenge: &Pubkey, winner: &Pubkey, rank: u8) -> Instruction {
+    let trophy_mint = pda::trophy_mint(challenge, rank).0;
+    let collection_mint = pda::trophy_collection_mint().0;
+    build(
+        accounts::MintTrophy {
+            payer: *payer,
+            challenge: *challenge,
+            winner: *winner,
+            trophy: pda::trophy(challenge, rank).0,
+            trophy_mint,
+            winner_trophy_account: pda::token_account_in(
+                winner,
+                &trophy_mint,
+                &crate::token_program_id(),
+            ),
+            trophy_metadata: pda::metadata(&trophy_mint),
+            trophy_edition: pda::master_edition(&trophy_mint),
+            trophy_collection: pda::trophy_collection().0,
+            collection_mint,
+            collection_metadata: pda::metadata(&collection_mint),
+            collection_edition: pda::master_edition(&collection_mint),
+            token_program: crate::token_program_id(),
+            associated_token_program: crate::associated_token_program_id(),
+            token_metadata_program: crate::token_metadata_program_id(),
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::MintTrophy { rank },
+    )
+}
+
+/// Mint `wallet`'s soulbound badge for a settled challenge it entered, or
+/// voted in when the challenge awards voters too.
+pub fn claim_badge(payer: &Pubkey, challenge: &Pubkey, wallet: &Pubkey) -> Instruction {
+    let badge_mint = pda::badge_mint(challenge).0;
+    build(
+        accounts::ClaimBadge {
+            payer: *payer,
+            challenge: *challenge,
+            wallet: *wallet,
+            badge: pda::badge(challenge, wallet).0,
+            badge_mint,
+            wallet_badge_account: pda::token_account(wallet, &badge_mint),
+            token_program: crate::token_2022_program_id(),
+            associated_token_program: crate::associated_token_program_id(),
+            system_program: system_program::ID,
+        },
+        instruction::ClaimBadge {},
+    )
+}
+
+/// Close a finalized or fully refunded challenge, sweeping what is left, any
+/// escrowed prize included, to `creator`.
+pub fn close_challenge(
+    payer: &Pubkey,
+    challenge: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+    prize: &Prize,
+) -> Instruction {
+    build(
+        accounts::CloseChallenge {
+            payer: *payer,
+            challenge: *challenge,
+            creator: *creator,
+            creator_token_account: pda::token_account(creator, mint),
+            token_program: crate::token_2022_program_id(),
+            treasury: pda::treasury(challenge).0,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            voting_treasury: pda::voting_treasury(challenge).0,
+            voting_treasury_token_account: pda::voting_treasury_token_account(challenge, mint),
+            prize_escrow: prize.escrow(challenge),
+            prize_token_program: prize.token_program,
+            prize_mint: prize.mint,
+            creator_prize_account: prize.account(creator),
+            creator_profile: pda::user_profile(creator).0,
+        },
+        instruction::CloseChallenge {},
+    )
+}
+
+/// Create the Metaplex collection every trophy belongs to (admin, once).
+pub fn initialize_trophy_collection(
+    authority: &Pubkey,
+    symbol: String,
+    base_uri: String,
+) -> Instruction {
+    let collection_mint = pda::trophy_collection_mint().0;
+    let trophy_collection = pda::trophy_collection().0;
+    build(
+        accounts::InitializeTrophyCollection {
+            authority: *authority,
+            challenge_tracker: pda::challenge_tracker().0,
+            trophy_collection,
+            collection_mint,
+            collection_token_account: pda::token_account_in(
+                &trophy_collection,
+                &collection_mint,
+                &crate::token_program_id(),
+            ),
+            collection_metadata: pda::metadata(&collection_mint),
+            collection_edition: pda::master_edition(&collection_mint),
+            token_program: crate::token_program_id(),
+            associated_token_program: crate::associated_token_program_id(),
+            token_metadata_program: crate::token_metadata_program_id(),
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::InitializeTrophyCollection { symbol, base_uri },
+    )
+}
+
+/// View instruction; decode its return data as [`crate::AuditReport`].
+pub fn audit_challenge(challenge: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::AuditChallenge {
+            challenge: *challenge,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            voting_treasury_token_account: pda::voting_treasury_token_account(challenge, mint),
+        },
+        instruction::AuditChallenge {},
+    )
+}
+
+pub fn set_challenge_frozen(
+    authority: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    frozen: bool,
+) -> Instruction {
+    build(
+        accounts::SetChallengeFrozen {
+            authority: *authority,
+            challenge: *challenge,
+            challenge_tracker: pda::challenge_tracker().0,
+            treasury_token_account: pda::treasury_token_account(challenge, mint),
+            voting_treasury_token_account: pda::voting_treasury_token_account(challenge, mint),
+        },
+        instruction::SetChallengeFrozen { frozen },
+    )
+}
+
+/// View instruction; decode its return data as [`crate::FinalizationPreview`].
+pub fn preview_finalization(challenge: &Pubkey) -> Instruction {
+    build(
+        accounts::PreviewFinalization {
+            challenge: *challenge,
+            crank_config: pda::crank_config().0,
+        },
+        instruction::PreviewFinalization {},
+    )
+}
+
+/// View instruction; decode its return data as [`crate::VoterRewardPreview`].
+pub fn preview_voter_reward(challenge: &Pubkey) -> Instruction {
+    build(
+        accounts::PreviewVoterReward {
+            challenge: *challenge,
+        },
+        instruction::PreviewVoterReward {},
+    )
+}
+
+pub fn initialize_fee_tracker(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeFeeTracker {
+            authority: *authority,
+            fee_tracker: pda::fee_tracker().0,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeFeeTracker {},
+    )
+}
+
+pub fn initialize_challenge_tracker(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeChallengeTracker {
+            authority: *authority,
+            challenge_tracker: pda::challenge_tracker().0,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeChallengeTracker {},
+    )
+}
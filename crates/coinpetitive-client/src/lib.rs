@@ -0,0 +1,54 @@
+//! Rust client for the Coinpetitive program.
+//!
+//! Builds instructions with the right account lists, derives the program's
+//! PDAs and decodes its accounts, events, return data and errors, so Rust
+//! services never have to hand-assemble `AccountMeta`s.
+
+pub mod accounts;
+pub mod errors;
+pub mod event_log;
+pub mod instructions;
+pub mod pda;
+#[cfg(feature = "rpc")]
+pub mod rpc;
+
+pub use coinpetitive::constraints;
+pub use coinpetitive::events;
+pub use coinpetitive::payout;
+pub use coinpetitive::payout::{FinalizationPreview, PlacePayout, SeasonPrize, VoterRewardPreview};
+pub use coinpetitive::state::{
+    Badge, Challenge, ChallengeTracker, CrankConfig, Dispute, DisputeConfig,
+    DisqualificationReason, FeeTracker, JudgingMode, ModerationRecord, PrizeKind, ReputationConfig,
+    Season, SeasonConfig, SeasonEntry, SponsorRecord, Team, TokenState, Tournament, Trophy,
+    TrophyCollection, UserProfile, VoteCommitment, VoteStake, VotingMode,
+};
+pub use coinpetitive::{AuditReport, CreateChallengeParams, ID};
+pub use coinpetitive_math as math;
+
+use anchor_lang::prelude::Pubkey;
+use std::str::FromStr;
+
+/// Token-2022 program that holds every CPT account.
+pub fn token_2022_program_id() -> Pubkey {
+    Pubkey::from_str(coinpetitive::constraints::TOKEN_2022_PROGRAM_ID).unwrap()
+}
+
+/// SPL Token program that holds trophy NFTs.
+pub fn token_program_id() -> Pubkey {
+    Pubkey::from_str(coinpetitive::constraints::TOKEN_PROGRAM_ID).unwrap()
+}
+
+/// Metaplex Token Metadata program.
+pub fn token_metadata_program_id() -> Pubkey {
+    Pubkey::from_str(coinpetitive::constraints::TOKEN_METADATA_PROGRAM_ID).unwrap()
+}
+
+/// Associated Token Account program.
+pub fn associated_token_program_id() -> Pubkey {
+    Pubkey::from_str(coinpetitive::constraints::ASSOCIATED_TOKEN_PROGRAM_ID).unwrap()
+}
+
+/// Default CPT mint.
+pub fn cpt_mint() -> Pubkey {
+    Pubkey::from_str(coinpetitive::constraints::CPT_TOKEN_MINT).unwrap()
+}
@@ -0,0 +1,223 @@
+//! Program derived addresses and token accounts used by the program.
+
+use anchor_lang::prelude::Pubkey;
+
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const VOTING_TREASURY_SEED: &[u8] = b"voting_treasury";
+pub const FEE_TRACKER_SEED: &[u8] = b"fee_tracker";
+pub const CHALLENGE_TRACKER_SEED: &[u8] = b"challenge_tracker";
+pub const CRANK_CONFIG_SEED: &[u8] = b"crank_config";
+pub const VOTE_COMMITMENT_SEED: &[u8] = b"vote_commitment";
+pub const VOTE_STAKE_SEED: &[u8] = b"vote_stake";
+pub const DISPUTE_CONFIG_SEED: &[u8] = b"dispute_config";
+pub const DISPUTE_SEED: &[u8] = b"dispute";
+pub const MODERATION_SEED: &[u8] = b"moderation";
+pub const SPONSOR_SEED: &[u8] = b"sponsor";
+pub const TROPHY_COLLECTION_SEED: &[u8] = b"trophy_collection";
+pub const TROPHY_COLLECTION_MINT_SEED: &[u8] = b"trophy_collection_mint";
+pub const TROPHY_SEED: &[u8] = b"trophy";
+pub const TROPHY_MINT_SEED: &[u8] = b"trophy_mint";
+pub const BADGE_SEED: &[u8] = b"badge";
+pub const BADGE_MINT_SEED: &[u8] = b"badge_mint";
+pub const USER_PROFILE_SEED: &[u8] = b"user_profile";
+pub const TOKEN_STATE_SEED: &[u8] = b"token_state";
+pub const REPUTATION_CONFIG_SEED: &[u8] = b"reputation_config";
+pub const SEASON_CONFIG_SEED: &[u8] = b"season_config";
+pub const SEASON_SEED: &[u8] = b"season";
+pub const TOURNAMENT_SEED: &[u8] = b"tournament";
+pub const TEAM_SEED: &[u8] = b"team";
+
+/// Treasury PDA holding a challenge's participation and submission fees.
+pub fn treasury(challenge: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_SEED, challenge.as_ref()], &crate::ID)
+}
+
+/// Voting treasury PDA holding a challenge's voting fees.
+pub fn voting_treasury(challenge: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOTING_TREASURY_SEED, challenge.as_ref()], &crate::ID)
+}
+
+/// Global fee tracker.
+pub fn fee_tracker() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_TRACKER_SEED], &crate::ID)
+}
+
+/// Global challenge tracker; its authority is the platform admin.
+pub fn challenge_tracker() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CHALLENGE_TRACKER_SEED], &crate::ID)
+}
+
+/// Global crank configuration holding the finalization bounty.
+pub fn crank_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CRANK_CONFIG_SEED], &crate::ID)
+}
+
+/// Global dispute configuration holding the bond and window length.
+pub fn dispute_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DISPUTE_CONFIG_SEED], &crate::ID)
+}
+
+/// The open dispute against a submission, if any.
+pub fn dispute(challenge: &Pubkey, submission_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DISPUTE_SEED, challenge.as_ref(), submission_id.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// The record of a submission's disqualification.
+pub fn moderation_record(challenge: &Pubkey, submission_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MODERATION_SEED, challenge.as_ref(), submission_id.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// A sponsor's deposits into a challenge's reward.
+pub fn sponsor_record(challenge: &Pubkey, sponsor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SPONSOR_SEED, challenge.as_ref(), sponsor.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// The trophy collection, also the authority of every trophy mint.
+pub fn trophy_collection() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TROPHY_COLLECTION_SEED], &crate::ID)
+}
+
+/// Mint of the trophy collection NFT.
+pub fn trophy_collection_mint() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TROPHY_COLLECTION_MINT_SEED], &crate::ID)
+}
+
+/// The record of a challenge's trophy for `rank` (1 for the winner).
+pub fn trophy(challenge: &Pubkey, rank: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TROPHY_SEED, challenge.as_ref(), &[rank]], &crate::ID)
+}
+
+/// Mint of a challenge's trophy NFT for `rank`.
+pub fn trophy_mint(challenge: &Pubkey, rank: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TROPHY_MINT_SEED, challenge.as_ref(), &[rank]], &crate::ID)
+}
+
+/// A wallet's lifetime statistics.
+pub fn user_profile(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER_PROFILE_SEED, wallet.as_ref()], &crate::ID)
+}
+
+/// CPT supply and mint conditions, including the unique-wallet count.
+pub fn token_state() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TOKEN_STATE_SEED], &crate::ID)
+}
+
+/// Global reputation config; creators of large challenges need its minimum.
+pub fn reputation_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REPUTATION_CONFIG_SEED], &crate::ID)
+}
+
+/// Global season config, pointing at the latest season.
+pub fn season_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEASON_CONFIG_SEED], &crate::ID)
+}
+
+/// Season number `id`, counting from one.
+pub fn season(id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEASON_SEED, &id.to_le_bytes()], &crate::ID)
+}
+
+/// The team `captain` leads in a challenge. Its profile is the
+/// [`user_profile`] of this address.
+pub fn team(challenge: &Pubkey, captain: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[TEAM_SEED, challenge.as_ref(), captain.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Tournament number `id` of `creator`.
+pub fn tournament(creator: &Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[TOURNAMENT_SEED, creator.as_ref(), &id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// The record of `wallet`'s badge for a challenge.
+pub fn badge(challenge: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[BADGE_SEED, challenge.as_ref(), wallet.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// A challenge's non-transferable Token-2022 badge mint.
+pub fn badge_mint(challenge: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BADGE_MINT_SEED, challenge.as_ref()], &crate::ID)
+}
+
+/// Metaplex metadata account of `mint`.
+pub fn metadata(mint: &Pubkey) -> Pubkey {
+    let program = crate::token_metadata_program_id();
+    Pubkey::find_program_address(&[b"metadata", program.as_ref(), mint.as_ref()], &program).0
+}
+
+/// Metaplex master edition account of `mint`.
+pub fn master_edition(mint: &Pubkey) -> Pubkey {
+    let program = crate::token_metadata_program_id();
+    Pubkey::find_program_address(
+        &[b"metadata", program.as_ref(), mint.as_ref(), b"edition"],
+        &program,
+    )
+    .0
+}
+
+/// A voter's sealed vote in a commit-reveal challenge.
+pub fn vote_commitment(challenge: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOTE_COMMITMENT_SEED, challenge.as_ref(), voter.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// A voter's locked stake in a stake-weighted challenge.
+pub fn vote_stake(challenge: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOTE_STAKE_SEED, challenge.as_ref(), voter.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Token-2022 associated token account of `owner` for `mint`.
+pub fn token_account(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    token_account_in(owner, mint, &crate::token_2022_program_id())
+}
+
+/// Associated token account of `owner` for a `mint` of `token_program`.
+pub fn token_account_in(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address_with_program_id(
+        owner,
+        mint,
+        token_program,
+    )
+}
+
+/// Token account of a challenge's treasury.
+pub fn treasury_token_account(challenge: &Pubkey, mint: &Pubkey) -> Pubkey {
+    token_account(&treasury(challenge).0, mint)
+}
+
+/// CPT account holding a season's prize pool.
+pub fn season_token_account(id: u64) -> Pubkey {
+    token_account(&season(id).0, &crate::cpt_mint())
+}
+
+/// CPT account escrowing a tournament's prize.
+pub fn tournament_token_account(tournament: &Pubkey) -> Pubkey {
+    token_account(tournament, &crate::cpt_mint())
+}
+
+/// Token account of a challenge's voting treasury.
+pub fn voting_treasury_token_account(challenge: &Pubkey, mint: &Pubkey) -> Pubkey {
+    token_account(&voting_treasury(challenge).0, mint)
+}
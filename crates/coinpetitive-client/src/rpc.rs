@@ -0,0 +1,212 @@
+//! Blocking RPC helpers, enabled with the `rpc` feature.
+//!
+//! Thin wrappers over `solana-client` that fetch and decode program accounts,
+//! run view instructions through `simulateTransaction` and turn program
+//! errors back into `ErrorCode`s.
+
+use crate::{accounts, errors, pda, Challenge, SeasonConfig};
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator};
+use base64::Engine;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::client_error::ClientError;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSimulateTransactionConfig,
+};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::sysvar;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Rpc(Box<ClientError>),
+    #[error("program error {code}: {name}")]
+    Program {
+        code: u32,
+        name: String,
+        logs: Vec<String>,
+    },
+    #[error("transaction failed: {err}")]
+    Transaction {
+        err: TransactionError,
+        logs: Vec<String>,
+    },
+    #[error("could not decode {0}")]
+    Decode(String),
+    #[error("instruction returned no data")]
+    MissingReturnData,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl From<ClientError> for Error {
+    fn from(err: ClientError) -> Self {
+        Self::Rpc(Box::new(err))
+    }
+}
+
+/// `Name: message` for a custom error number, or just the number if it is not
+/// an `ErrorCode`.
+pub fn describe(code: u32) -> String {
+    match errors::error_code(code) {
+        Some(error) => format!("{}: {}", error.name(), error),
+        None => format!("custom error {code}"),
+    }
+}
+
+fn program_error(err: TransactionError, logs: Vec<String>) -> Error {
+    let code = match &err {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => Some(*code),
+        _ => errors::code_from_logs(&logs),
+    };
+    match code {
+        Some(code) => Error::Program {
+            code,
+            name: describe(code),
+            logs,
+        },
+        None => Error::Transaction { err, logs },
+    }
+}
+
+/// Fetch and decode a single program account.
+pub fn fetch<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> Result<T> {
+    let data = rpc.get_account_data(address)?;
+    accounts::decode(&data).map_err(|e| Error::Decode(format!("{address}: {e}")))
+}
+
+/// Every account of type `T` owned by the program, found with a
+/// discriminator filter on `getProgramAccounts`.
+pub fn fetch_all<T: AccountDeserialize + Discriminator>(
+    rpc: &RpcClient,
+) -> Result<Vec<(Pubkey, T)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            T::DISCRIMINATOR.to_vec(),
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    rpc.get_program_accounts_with_config(&crate::ID, config)?
+        .into_iter()
+        .map(|(address, account)| {
+            accounts::decode(&account.data)
+                .map(|decoded| (address, decoded))
+                .map_err(|e| Error::Decode(format!("{address}: {e}")))
+        })
+        .collect()
+}
+
+pub fn fetch_challenges(rpc: &RpcClient) -> Result<Vec<(Pubkey, Challenge)>> {
+    fetch_all(rpc)
+}
+
+/// Cluster time, which is what the program compares deadlines against.
+pub fn cluster_time(rpc: &RpcClient) -> Result<i64> {
+    let account = rpc.get_account(&sysvar::clock::id())?;
+    let clock: Clock = solana_sdk::account::from_account(&account)
+        .ok_or_else(|| Error::Decode("clock sysvar".to_string()))?;
+    Ok(clock.unix_timestamp)
+}
+
+/// The season running at `now`, which settling a challenge has to credit.
+/// `None` before the admin creates the first season.
+pub fn running_season(rpc: &RpcClient, now: i64) -> Result<Option<u64>> {
+    let address = pda::season_config().0;
+    let Some(account) = rpc
+        .get_account_with_commitment(&address, rpc.commitment())?
+        .value
+    else {
+        return Ok(None);
+    };
+    let config: SeasonConfig =
+        accounts::decode(&account.data).map_err(|e| Error::Decode(format!("{address}: {e}")))?;
+    Ok(config.running_at(now))
+}
+
+/// Whether `wallet` has a profile, which settling a challenge credits when
+/// it places.
+pub fn has_profile(rpc: &RpcClient, wallet: &Pubkey) -> Result<bool> {
+    let address = pda::user_profile(wallet).0;
+    Ok(rpc
+        .get_account_with_commitment(&address, rpc.commitment())?
+        .value
+        .is_some())
+}
+
+/// Run a view instruction through `simulateTransaction` and decode what it
+/// returned. `payer` only has to exist; nothing is signed or sent.
+pub fn simulate_view<T: AnchorDeserialize>(
+    rpc: &RpcClient,
+    payer: &Pubkey,
+    instruction: Instruction,
+) -> Result<T> {
+    let transaction = Transaction::new_with_payer(&[instruction], Some(payer));
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(rpc.commitment()),
+        ..Default::default()
+    };
+    let result = rpc
+        .simulate_transaction_with_config(&transaction, config)?
+        .value;
+
+    let logs = result.logs.unwrap_or_default();
+    if let Some(err) = result.err {
+        return Err(program_error(err, logs));
+    }
+
+    let (data, _) = result.return_data.ok_or(Error::MissingReturnData)?.data;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| Error::Decode(format!("return data: {e}")))?;
+    accounts::decode_return_data(&bytes).map_err(|e| Error::Decode(format!("return data: {e}")))
+}
+
+/// Sign with `payer` (fee payer) and `signers`, send and confirm.
+pub fn send(
+    rpc: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+) -> Result<Signature> {
+    let mut all_signers: Vec<&dyn Signer> = vec![payer];
+    all_signers.extend(signers.iter().map(|s| *s as &dyn Signer));
+
+    let blockhash = rpc.get_latest_blockhash()?;
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+
+    rpc.send_and_confirm_transaction(&transaction)
+        .map_err(|err| match err.get_transaction_error() {
+            Some(tx_err) => program_error(tx_err, preflight_logs(&err)),
+            None => err.into(),
+        })
+}
+
+fn preflight_logs(err: &ClientError) -> Vec<String> {
+    use solana_client::rpc_request::{RpcError, RpcResponseErrorData};
+
+    match err.kind() {
+        solana_client::client_error::ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+            ..
+        }) => result.logs.clone().unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,766 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountSerialize, AnchorSerialize};
+use coinpetitive_client::{
+    accounts, constraints, errors, instructions, pda, Challenge, CreateChallengeParams, FeeTracker,
+};
+
+#[test]
+fn error_tables_match_program_numbering() {
+    for (i, code) in errors::ERROR_CODES.iter().enumerate() {
+        assert_eq!(u32::from(*code), 6000 + i as u32, "{code:?}");
+        assert_eq!(
+            errors::error_code(u32::from(*code)).map(u32::from),
+            Some(u32::from(*code))
+        );
+    }
+    for (i, code) in errors::TOKEN_ERRORS.iter().enumerate() {
+        assert_eq!(u32::from(*code), 6000 + i as u32, "{code:?}");
+    }
+    assert!(errors::error_code(5999).is_none());
+    assert!(errors::error_code(6000 + errors::ERROR_CODES.len() as u32).is_none());
+}
+
+#[test]
+fn error_number_is_read_from_logs() {
+    let logs = [
+        "Program log: Instruction: FinalizeChallenge",
+        "Program log: AnchorError occurred. Error Code: NoVotes. Error Number: 6018. Error Message: No votes found for any submission.",
+    ];
+    let code = errors::code_from_logs(&logs).unwrap();
+    assert_eq!(errors::error_code(code).unwrap().name(), "NoVotes");
+}
+
+#[test]
+fn builders_use_derived_accounts() {
+    let creator = Pubkey::new_unique();
+    let challenge = Pubkey::new_unique();
+    let mint = coinpetitive_client::cpt_mint();
+
+    let ix = instructions::claim_creator_reward(&creator, &challenge, &mint);
+    assert_eq!(ix.program_id, coinpetitive_client::ID);
+    assert_eq!(ix.accounts[0].pubkey, creator);
+    assert!(ix.accounts[0].is_signer);
+    assert_eq!(ix.accounts[3].pubkey, pda::treasury(&challenge).0);
+    assert_eq!(
+        ix.accounts[4].pubkey,
+        pda::treasury_token_account(&challenge, &mint)
+    );
+    assert_eq!(ix.accounts[5].pubkey, pda::token_account(&creator, &mint));
+}
+
+#[test]
+fn challenges_are_sized_for_the_defaulted_participant_limit() {
+    let params = CreateChallengeParams {
+        reward: 1_000,
+        challenge_id: 7,
+        ..Default::default()
+    };
+    assert_eq!(
+        params.participant_limit(),
+        constraints::DEFAULT_MAX_PARTICIPANTS
+    );
+    let capped = CreateChallengeParams {
+        max_participants: constraints::MAX_PARTICIPANTS,
+        ..Default::default()
+    };
+    assert_eq!(capped.participant_limit(), constraints::MAX_PARTICIPANTS);
+
+    let user = Pubkey::new_unique();
+    let challenge = Pubkey::new_unique();
+    let mint = coinpetitive_client::cpt_mint();
+    let ix = instructions::create_challenge(
+        &user,
+        &challenge,
+        &mint,
+        &Pubkey::new_unique(),
+        params.clone(),
+    );
+    assert_eq!(ix.accounts[1].pubkey, challenge);
+    assert_eq!(&ix.data[8..], params.try_to_vec().unwrap().as_slice());
+}
+
+#[test]
+fn accounts_round_trip() {
+    let challenge = Challenge {
+        creator: Pubkey::new_unique(),
+        reward: 1_000,
+        submission_votes: vec![(Pubkey::new_unique(), 3)],
+        ..Default::default()
+    };
+    let mut data = Vec::new();
+    challenge.try_serialize(&mut data).unwrap();
+
+    assert!(accounts::is_account::<Challenge>(&data));
+    assert!(!accounts::is_account::<FeeTracker>(&data));
+    let decoded = accounts::decode_challenge(&data).unwrap();
+    assert_eq!(decoded.creator, challenge.creator);
+    assert_eq!(decoded.submission_votes, challenge.submission_votes);
+    assert!(accounts::decode_fee_tracker(&data).is_err());
+}
+
+#[test]
+fn return_data_round_trip() {
+    let report = coinpetitive_client::AuditReport {
+        challenge: Pubkey::new_unique(),
+        ledger_treasury: 10,
+        actual_treasury: 12,
+        treasury_surplus: 2,
+        ..Default::default()
+    };
+    let decoded: coinpetitive_client::AuditReport =
+        accounts::decode_return_data(&report.try_to_vec().unwrap()).unwrap();
+    assert_eq!(decoded, report);
+
+    // Tokens sent straight to a treasury leave a surplus, not a discrepancy
+    assert!(report.is_clean());
+    let short = coinpetitive_client::AuditReport {
+        actual_treasury: 9,
+        treasury_surplus: 0,
+        ..report
+    };
+    assert!(short.treasury_short());
+    assert!(!short.is_clean());
+}
+
+#[test]
+fn events_are_decoded_from_program_logs_only() {
+    use coinpetitive_client::event_log::{event_log_line, events_from_logs, Event};
+    use coinpetitive_client::events::{ParticipantJoined, SubmissionsClosed};
+
+    let challenge = Pubkey::new_unique();
+    let joined = ParticipantJoined {
+        challenge,
+        participant: Pubkey::new_unique(),
+        fee: 7,
+    };
+    let closed = SubmissionsClosed {
+        challenge,
+        submissions: 1,
+        participants: 2,
+    };
+    let program = coinpetitive_client::ID.to_string();
+    let token = coinpetitive_client::token_2022_program_id().to_string();
+    let logs = vec![
+        format!("Program {program} invoke [1]"),
+        "Program log: Instruction: PayParticipationFee".to_string(),
+        format!("Program {token} invoke [2]"),
+        // Emitted by another program, must be ignored
+        event_log_line(&closed),
+        format!("Program {token} success"),
+        event_log_line(&joined),
+        "Program data: not base64!".to_string(),
+        format!("Program {program} success"),
+    ];
+
+    let events = events_from_logs(&logs);
+    assert_eq!(events.len(), 1);
+    match &events[0] {
+        Event::ParticipantJoined(e) => {
+            assert_eq!(e.challenge, challenge);
+            assert_eq!(e.fee, 7);
+        }
+        _ => panic!("expected ParticipantJoined"),
+    }
+}
+
+#[test]
+fn commit_and_reveal_share_the_voters_commitment_account() {
+    use coinpetitive_client::VoteCommitment;
+
+    let voter = Pubkey::new_unique();
+    let challenge = Pubkey::new_unique();
+    let submission = Pubkey::new_unique();
+    let salt = [7u8; 32];
+    let commitment = VoteCommitment::hash(&challenge, &voter, &submission, &salt);
+    assert_ne!(
+        commitment,
+        VoteCommitment::hash(&challenge, &voter, &submission, &[8u8; 32])
+    );
+    assert_ne!(
+        commitment,
+        VoteCommitment::hash(&challenge, &voter, &Pubkey::new_unique(), &salt)
+    );
+    // Another voter, or the same vote in another challenge, can't reuse it
+    assert_ne!(
+        commitment,
+        VoteCommitment::hash(&challenge, &Pubkey::new_unique(), &submission, &salt)
+    );
+    assert_ne!(
+        commitment,
+        VoteCommitment::hash(&Pubkey::new_unique(), &voter, &submission, &salt)
+    );
+
+    let mint = coinpetitive_client::cpt_mint();
+    let commit = instructions::commit_vote(&voter, &challenge, &mint, commitment);
+    let reveal = instructions::reveal_vote(&voter, &challenge, &submission, salt);
+    let sealed = pda::vote_commitment(&challenge, &voter).0;
+    assert_eq!(commit.accounts[2].pubkey, sealed);
+    assert_eq!(reveal.accounts[2].pubkey, sealed);
+    assert_eq!(reveal.accounts[3].pubkey, submission);
+}
+
+#[test]
+fn weighted_voter_rewards_follow_weight() {
+    use coinpetitive_client::{payout, VotingMode};
+
+    let (small, large) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let winner = Pubkey::new_unique();
+    let mut challenge = Challenge {
+        voting_fee: 10,
+        voting_mode: VotingMode::Quadratic,
+        submitters: vec![(winner, Pubkey::new_unique())],
+        ..Default::default()
+    };
+    assert_eq!(challenge.vote_cost(3), Some(90));
+    challenge.add_weighted_vote(small, winner, 1).unwrap();
+    challenge.add_weighted_vote(large, winner, 3).unwrap();
+    assert_eq!(challenge.submission_votes, vec![(winner, 4)]);
+
+    challenge.winner = Some(winner);
+    challenge.winning_weight = challenge.weight_for(&winner);
+    challenge.voting_reward_pool = 1_000;
+    challenge.voting_treasury = 1_000;
+    assert_eq!(payout::voter_reward(&challenge, &small, 0), 250);
+    assert_eq!(payout::voter_reward(&challenge, &large, 0), 750);
+    assert!(payout::voter_rewards_pending(&challenge));
+
+    // distribute_voting_treasury zeroes a paid voter's weight
+    challenge.vote_weights = vec![0, 0];
+    assert!(!payout::voter_rewards_pending(&challenge));
+    assert_eq!(challenge.remove_votes_of(&large), vec![0]);
+    assert_eq!(challenge.voters, vec![(small, winner)]);
+}
+
+#[test]
+fn judged_challenges_rank_by_blended_score() {
+    use coinpetitive_client::{payout, JudgingMode};
+
+    let (popular, polished) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut challenge = Challenge {
+        reward: 1_000,
+        challenge_treasury: 2_000,
+        judging_mode: JudgingMode::Median,
+        judges: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+        judge_weight_bps: 7_500,
+        judge_fee_bps: 1_000,
+        submitters: vec![
+            (popular, Pubkey::new_unique()),
+            (polished, Pubkey::new_unique()),
+        ],
+        submission_votes: vec![(popular, 3), (polished, 1)],
+        ..Default::default()
+    };
+    assert!(!challenge.has_quorum());
+
+    // 20 / 100 and 3 of 4 votes: 0.75 * 2000 + 0.25 * 7500 = 3375 points
+    challenge.set_score(0, 0, 20);
+    // 90 / 100 and 1 of 4 votes: 0.75 * 9000 + 0.25 * 2500 = 7375 points
+    challenge.set_score(0, 1, 90);
+    challenge.set_score(1, 1, 90);
+    assert!(challenge.has_quorum());
+
+    let plan = payout::plan_finalization(&challenge, 0).unwrap();
+    assert_eq!(plan.places[0].submission, polished);
+    assert_eq!(plan.places[0].points, 7_375);
+    assert_eq!(plan.places[1].points, 3_375);
+    // Judges get 10% of the 1000 fees left over, split between both scorers
+    assert_eq!(plan.judge_fee, 100);
+    assert_eq!(plan.creator_remainder, 900);
+    challenge.judge_fee_pool = plan.judge_fee;
+    assert_eq!(challenge.judge_fee_share(), 50);
+    assert_eq!(challenge.judge_fees_owed(), 100);
+}
+
+#[test]
+fn disqualified_submissions_are_not_refunded_again() {
+    let (alice, bob, carol) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let videos: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let mut challenge = Challenge {
+        submitters: vec![
+            (videos[0], alice),
+            (videos[1], bob),
+            (videos[2], alice),
+            (videos[3], carol),
+        ],
+        ..Default::default()
+    };
+    challenge.disqualify(&videos[1]).unwrap();
+    challenge.disqualify(&videos[3]).unwrap();
+
+    // Bob and Carol's only entries were struck, their fees are settled
+    assert_eq!(challenge.refundable_wallets(), vec![alice]);
+
+    assert_eq!(challenge.remove_submissions_of(&alice), 2);
+    assert_eq!(challenge.submitters.len(), 2);
+    assert!(challenge.is_disqualified(&videos[1]));
+    assert!(challenge.is_disqualified(&videos[3]));
+    assert_eq!(challenge.remove_submissions_of(&bob), 0);
+    assert!(challenge.is_disqualified(&videos[3]));
+}
+
+#[test]
+fn sponsors_are_refunded_pro_rata() {
+    let challenge = Challenge {
+        reward: 1_000,
+        challenge_treasury: 1_400,
+        sponsors: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+        sponsored: 600,
+        ..Default::default()
+    };
+    // A full treasury returns every deposit as it was made
+    assert_eq!(challenge.sponsor_refund(200), 200);
+    assert_eq!(challenge.sponsor_refund(400), 400);
+
+    // A short one is split in proportion to what each sponsor put in
+    let short = Challenge {
+        challenge_treasury: 300,
+        ..challenge
+    };
+    assert_eq!(short.sponsor_refund(200), 100);
+    assert_eq!(short.sponsor_refund(400), 200);
+}
+
+#[test]
+fn escrowed_prizes_leave_the_fees_to_the_creator() {
+    use coinpetitive_client::instructions::Prize;
+    use coinpetitive_client::{payout, PrizeKind};
+
+    let (winner, runner_up) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let address = Pubkey::new_unique();
+    let sol = Challenge {
+        reward: 1_000,
+        challenge_treasury: 300,
+        prize_kind: PrizeKind::Sol,
+        prize_token_program: anchor_lang::system_program::ID,
+        prize_pool: 1_000,
+        treasury: pda::treasury(&address).0,
+        submitters: vec![
+            (winner, Pubkey::new_unique()),
+            (runner_up, Pubkey::new_unique()),
+        ],
+        submission_votes: vec![(winner, 3), (runner_up, 1)],
+        ..Default::default()
+    };
+
+    // The prize comes out of the escrow, so every CPT fee is left over
+    let plan = payout::plan_finalization(&sol, 0).unwrap();
+    assert_eq!(plan.total_reward, 1_000);
+    assert_eq!(plan.creator_remainder, 300);
+    let short = Challenge {
+        prize_pool: 999,
+        ..sol.clone()
+    };
+    assert!(payout::plan_finalization(&short, 0).is_err());
+
+    // SOL sits in the treasury PDA and is paid straight to wallets
+    let prize = Prize::of(&sol);
+    assert_eq!(prize.escrow(&address), sol.treasury);
+    assert_eq!(prize.account(&winner), winner);
+
+    // Tokens sit in the treasury's account for the mint, under its own program
+    let (mint, token_program) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let spl = Prize {
+        kind: PrizeKind::Token,
+        mint,
+        token_program,
+    };
+    assert_eq!(
+        spl.escrow(&address),
+        pda::token_account_in(&sol.treasury, &mint, &token_program)
+    );
+    let ix = instructions::fund_prize(&winner, &address, &spl);
+    assert_eq!(ix.accounts[2].pubkey, mint);
+    assert_eq!(ix.accounts[3].pubkey, token_program);
+    assert_eq!(ix.accounts[6].pubkey, spl.escrow(&address));
+
+    // Closing sweeps the escrow with a checked transfer, which needs the mint
+    let ix = instructions::close_challenge(&winner, &address, &winner, &mint, &spl);
+    assert_eq!(ix.accounts[9].pubkey, spl.escrow(&address));
+    assert_eq!(ix.accounts[11].pubkey, mint);
+
+    // A CPT prize is the default and is paid from the fees as before
+    assert_eq!(
+        Prize::cpt(&mint).account(&winner),
+        pda::token_account(&winner, &mint)
+    );
+}
+
+#[test]
+fn each_place_gets_its_own_trophy_in_the_collection() {
+    let (payer, winner) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let challenge = Pubkey::new_unique();
+    assert_ne!(pda::trophy(&challenge, 1), pda::trophy(&challenge, 2));
+    assert_ne!(
+        pda::trophy_mint(&challenge, 1),
+        pda::trophy_mint(&challenge, 2)
+    );
+
+    let trophy_mint = pda::trophy_mint(&challenge, 1).0;
+    let ix = instructions::mint_trophy(&payer, &challenge, &winner, 1);
+    assert_eq!(ix.accounts[2].pubkey, winner);
+    assert_eq!(ix.accounts[3].pubkey, pda::trophy(&challenge, 1).0);
+    assert_eq!(ix.accounts[4].pubkey, trophy_mint);
+    // Trophies are classic SPL Token NFTs held in the winner's own account
+    assert_eq!(
+        ix.accounts[5].pubkey,
+        pda::token_account_in(
+            &winner,
+            &trophy_mint,
+            &coinpetitive_client::token_program_id()
+        )
+    );
+    assert_eq!(ix.accounts[6].pubkey, pda::metadata(&trophy_mint));
+    assert_ne!(
+        pda::metadata(&trophy_mint),
+        pda::master_edition(&trophy_mint)
+    );
+    assert_eq!(ix.accounts[8].pubkey, pda::trophy_collection().0);
+    assert_eq!(ix.accounts[9].pubkey, pda::trophy_collection_mint().0);
+}
+
+#[test]
+fn badges_share_one_mint_per_challenge() {
+    let (payer, alice, bob) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let challenge = Pubkey::new_unique();
+    assert_ne!(pda::badge(&challenge, &alice), pda::badge(&challenge, &bob));
+
+    let badge_mint = pda::badge_mint(&challenge).0;
+    let ix = instructions::claim_badge(&payer, &challenge, &alice);
+    assert_eq!(ix.accounts[2].pubkey, alice);
+    assert_eq!(ix.accounts[3].pubkey, pda::badge(&challenge, &alice).0);
+    assert_eq!(ix.accounts[4].pubkey, badge_mint);
+    // Badges live in Token-2022 accounts, like CPT itself
+    assert_eq!(
+        ix.accounts[5].pubkey,
+        pda::token_account(&alice, &badge_mint)
+    );
+    assert!(ix.accounts[5].is_writable);
+}
+
+#[test]
+fn profiles_are_credited_once_per_wallet() {
+    use coinpetitive_client::instructions::{Prize, SettleResultsAccounts};
+    use coinpetitive_client::TokenState;
+
+    let (winner, runner_up, mint) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let keys = SettleResultsAccounts {
+        authority: Pubkey::new_unique(),
+        challenge: Pubkey::new_unique(),
+        creator: Pubkey::new_unique(),
+        mint,
+        prize: Prize::cpt(&mint),
+        winner,
+        runner_up: Some(runner_up),
+        winner_has_profile: true,
+        runner_up_has_profile: true,
+        season: None,
+        tournament: None,
+        winner_token_account: pda::token_account(&winner, &mint),
+        runnerup_token_account: pda::token_account(&runner_up, &mint),
+        platform_treasury_token_account: Pubkey::new_unique(),
+    };
+    let ix = instructions::settle_results(&keys);
+    assert_eq!(ix.accounts[16].pubkey, pda::user_profile(&winner).0);
+    assert_eq!(ix.accounts[17].pubkey, pda::user_profile(&runner_up).0);
+
+    // Both places going to one wallet leave the runner-up profile out
+    let ix = instructions::settle_results(&SettleResultsAccounts {
+        runner_up: Some(winner),
+        ..keys
+    });
+    assert_eq!(ix.accounts[17].pubkey, coinpetitive_client::ID);
+
+    // A placed wallet without a profile goes uncredited rather than blocking
+    // settlement
+    let ix = instructions::settle_results(&SettleResultsAccounts {
+        winner_has_profile: false,
+        ..keys
+    });
+    assert_eq!(ix.accounts[16].pubkey, coinpetitive_client::ID);
+    assert_eq!(ix.accounts[17].pubkey, pda::user_profile(&runner_up).0);
+
+    // Only the wallet that reaches a milestone meets its mint condition
+    let mut state = TokenState {
+        unique_wallets: 249_998,
+        ..Default::default()
+    };
+    assert_eq!(state.track_new_wallet(), None);
+    assert_eq!(state.track_new_wallet(), Some(4));
+    assert!(state.mint_conditions_met[4]);
+    assert_eq!(state.pending_mint_milestone, Some(4));
+    assert_eq!(state.track_new_wallet(), None);
+
+    // A mint past the initial supply uses the condition up, once
+    assert_eq!(state.use_pending_milestone(), Some(4));
+    assert!(state.mint_conditions_used[4]);
+    assert_eq!(state.pending_mint_milestone, None);
+    assert_eq!(state.use_pending_milestone(), None);
+
+    // A milestone reached meanwhile waits its turn behind the pending one
+    state.mint_conditions_met[5] = true;
+    state.pending_mint_milestone = Some(6);
+    assert_eq!(state.use_pending_milestone(), Some(6));
+    assert_eq!(state.pending_mint_milestone, Some(5));
+}
+
+#[test]
+fn reputation_is_charged_to_whoever_loses_a_dispute() {
+    use coinpetitive_client::ReputationConfig;
+
+    let (challenge, mint, submission) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let (disputer, submitter) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let resolve = |upheld| {
+        let ix = instructions::resolve_dispute(
+            &Pubkey::new_unique(),
+            &challenge,
+            &mint,
+            &submission,
+            &disputer,
+            &submitter,
+            upheld,
+        );
+        ix.accounts.last().unwrap().pubkey
+    };
+    assert_eq!(resolve(true), pda::user_profile(&submitter).0);
+    assert_eq!(resolve(false), pda::user_profile(&disputer).0);
+
+    // Only rewards from the threshold up need the creator minimum
+    let config = ReputationConfig {
+        large_reward: 1_000,
+        min_creator_reputation: 300,
+        ..Default::default()
+    };
+    assert_eq!(config.required_for(999), 0);
+    assert_eq!(config.required_for(1_000), 300);
+    let off = ReputationConfig {
+        min_creator_reputation: 300,
+        ..Default::default()
+    };
+    assert_eq!(off.required_for(u64::MAX), 0);
+}
+
+#[test]
+fn season_leaderboard_keeps_the_top_totals() {
+    use coinpetitive_client::constraints::SEASON_LEADERBOARD_SIZE;
+    use coinpetitive_client::{payout, Season};
+
+    let mut season = Season {
+        id: 1,
+        payout_places: 3,
+        prize_pool: 1_000,
+        ..Default::default()
+    };
+    let wallets: Vec<Pubkey> = (0..=SEASON_LEADERBOARD_SIZE)
+        .map(|_| Pubkey::new_unique())
+        .collect();
+    for (i, wallet) in wallets.iter().enumerate() {
+        season.record(*wallet, 10 + i as u64);
+    }
+
+    // The first wallet had the fewest points and was pushed off
+    assert!(season.leaderboard.iter().all(|e| e.wallet != wallets[0]));
+    assert_eq!(
+        season.leaderboard[0].wallet,
+        wallets[SEASON_LEADERBOARD_SIZE]
+    );
+
+    // A new total moves the wallet up past everyone it now beats
+    season.record(wallets[1], 100);
+    assert_eq!(season.leaderboard[0].wallet, wallets[1]);
+    assert!(season
+        .leaderboard
+        .windows(2)
+        .all(|pair| pair[0].points >= pair[1].points));
+
+    // Only the paid places share the pool, by points, dust to first
+    let prizes = payout::plan_season_prizes(&season);
+    assert_eq!(prizes.len(), 3);
+    assert_eq!(prizes.iter().map(|p| p.amount).sum::<u64>(), 1_000);
+    assert!(prizes[0].amount > prizes[1].amount);
+
+    let placed: Vec<Pubkey> = prizes.iter().map(|p| p.wallet).collect();
+    let ix = instructions::settle_season(&Pubkey::new_unique(), 1, &placed, &Pubkey::new_unique());
+    assert_eq!(ix.accounts[1].pubkey, pda::season(1).0);
+    assert_eq!(
+        ix.accounts.last().unwrap().pubkey,
+        pda::token_account(&placed[2], &coinpetitive_client::cpt_mint())
+    );
+}
+
+#[test]
+fn tournament_rounds_admit_only_qualifiers() {
+    use coinpetitive_client::instructions::{Prize, SettleResultsAccounts};
+    use coinpetitive_client::Tournament;
+
+    let (creator, alice, bob) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let rounds = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    let mut tournament = Tournament {
+        creator,
+        round_count: 2,
+        advance_count: 1,
+        rounds: rounds.clone(),
+        ..Default::default()
+    };
+
+    // Anyone may enter the first round, nobody the next one yet
+    assert!(tournament.admits(0, &bob));
+    assert!(!tournament.admits(1, &alice));
+    assert!(!tournament.is_final_round());
+
+    tournament.current_round = 1;
+    tournament.qualified = vec![alice];
+    assert!(tournament.admits(1, &alice));
+    assert!(!tournament.admits(1, &bob));
+    assert!(tournament.is_final_round());
+    assert_eq!(tournament.current_round_challenge(), Some(rounds[1]));
+
+    // A round rolling its fees pays the leftover into the tournament escrow
+    let address = pda::tournament(&creator, 7).0;
+    let mint = coinpetitive_client::cpt_mint();
+    let keys = SettleResultsAccounts {
+        authority: Pubkey::new_unique(),
+        challenge: rounds[1],
+        creator,
+        mint,
+        prize: Prize::cpt(&mint),
+        winner: alice,
+        runner_up: None,
+        winner_has_profile: true,
+        runner_up_has_profile: false,
+        season: None,
+        tournament: Some(address),
+        winner_token_account: pda::token_account(&alice, &mint),
+        runnerup_token_account: pda::token_account(&alice, &mint),
+        platform_treasury_token_account: Pubkey::new_unique(),
+    };
+    let metas = instructions::settle_results(&keys).accounts;
+    assert!(metas
+        .iter()
+        .any(|m| m.pubkey == pda::tournament_token_account(&address)));
+    assert!(metas
+        .iter()
+        .all(|m| m.pubkey != pda::token_account(&creator, &mint)));
+
+    let ix = instructions::finish_tournament(&bob, &address, &rounds[1], &alice);
+    assert_eq!(
+        ix.accounts[3].pubkey,
+        pda::tournament_token_account(&address)
+    );
+    assert_eq!(ix.accounts[4].pubkey, pda::token_account(&alice, &mint));
+}
+
+#[test]
+fn team_splits_give_the_captain_the_dust() {
+    use coinpetitive_client::instructions::Prize;
+    use coinpetitive_client::Team;
+
+    let (challenge, captain, member) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let team = Team {
+        challenge,
+        captain,
+        members: vec![captain, member, Pubkey::new_unique()],
+        shares_bps: vec![3_334, 3_333, 3_333],
+        paid: vec![false; 3],
+        ..Default::default()
+    };
+    assert_eq!(team.split(100), vec![34, 33, 33]);
+    assert_eq!(team.split(10), vec![4, 3, 3]);
+    assert_eq!(team.member_index(&member), Some(1));
+    assert!(!team.is_fully_paid());
+
+    // Members are paid in their own accounts, in member order
+    let address = pda::team(&challenge, &captain).0;
+    let mint = coinpetitive_client::cpt_mint();
+    let ix = instructions::split_team_reward(
+        &Pubkey::new_unique(),
+        &challenge,
+        &captain,
+        &team.members,
+        &Prize::cpt(&mint),
+    );
+    assert_eq!(ix.accounts[1].pubkey, address);
+    assert_eq!(ix.accounts[3].pubkey, pda::token_account(&address, &mint));
+    assert_eq!(ix.accounts[7].pubkey, pda::token_account(&member, &mint));
+}
+
+#[test]
+fn duel_winner_takes_the_pot_and_unmatched_duels_cancel() {
+    use coinpetitive_client::{math, payout};
+
+    let (creator, opponent) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (theirs, ours) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let open = Challenge {
+        creator,
+        is_duel: true,
+        duel_accept_by: 100,
+        participation_fee: 500,
+        reward: 500,
+        challenge_treasury: 500,
+        min_participants: 2,
+        max_participants: 2,
+        participants: vec![creator],
+        submission_end: 200,
+        voting_end: 300,
+        ..Default::default()
+    };
+    assert!(open.can_accept_duel(&opponent));
+    assert!(!open.can_accept_duel(&creator));
+    let named = Challenge {
+        duel_opponent: opponent,
+        ..open.clone()
+    };
+    assert!(!named.can_accept_duel(&Pubkey::new_unique()));
+
+    // Nobody matched the stake in time, so anyone can cancel and refund it
+    assert!(!open.quorum_missed(99));
+    assert!(open.quorum_missed(100));
+
+    // Both stakes, less the platform fee, go to the winner alone; duelists
+    // enter their videos free, so nothing is left for the creator
+    let matched = Challenge {
+        reward: 1_000,
+        challenge_treasury: 1_000,
+        participants: vec![creator, opponent],
+        submitters: vec![(theirs, opponent), (ours, creator)],
+        submission_votes: vec![(theirs, 2), (ours, 1)],
+        ..named
+    };
+    assert!(matched.duel_matched() && !matched.quorum_missed(150));
+    let plan = payout::plan_finalization(&matched, 0).unwrap();
+    assert_eq!(plan.platform_fee, math::platform_fee(1_000));
+    assert_eq!(plan.places[0].submission, theirs);
+    assert_eq!(plan.places[0].amount, 1_000 - plan.platform_fee);
+    assert_eq!(plan.places[1].amount, 0);
+    assert_eq!(matched.submission_fee(), 0);
+    assert_eq!(plan.creator_remainder, 0);
+
+    let (address, mint) = (Pubkey::new_unique(), coinpetitive_client::cpt_mint());
+    let ix = instructions::accept_duel(&opponent, &address, &mint);
+    assert_eq!(ix.accounts[1].pubkey, address);
+    assert_eq!(ix.accounts[2].pubkey, pda::user_profile(&opponent).0);
+    assert_eq!(ix.accounts[5].pubkey, pda::token_account(&opponent, &mint));
+}
@@ -0,0 +1,202 @@
+//! Fee and payout arithmetic for Coinpetitive challenges.
+//!
+//! The on-chain program and every off-chain client call into this crate, so a
+//! payout estimate made off-chain is always exactly what the program pays.
+//! Everything here is pure integer math: no allocation, no `std`, no Solana
+//! types.
+#![no_std]
+
+/// Platform fee taken from a challenge reward: 2.1% = 210 basis points.
+pub const PLATFORM_FEE_RATE: u16 = 210;
+
+/// Denominator for basis point rates.
+pub const BASIS_POINTS: u16 = 10_000;
+
+/// Share of the fee-adjusted reward paid to the winner, in percent. The
+/// runner-up receives the rest.
+pub const WINNER_SHARE_PERCENT: u8 = 75;
+
+/// Burn rate on liquidity pool transfers, in percent.
+pub const BURN_RATE: u8 = 1;
+
+/// Highest score a judge can give a submission.
+pub const MAX_SCORE: u8 = 100;
+
+/// How a challenge reward is split at finalization.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PlaceRewards {
+    pub platform_fee: u64,
+    pub winner: u64,
+    pub runner_up: u64,
+}
+
+impl PlaceRewards {
+    /// Sum of every component; always equal to the reward that was split.
+    pub fn total(&self) -> u64 {
+        self.platform_fee + self.winner + self.runner_up
+    }
+}
+
+/// How a liquidity pool transfer is split between burn and recipient.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BurnSplit {
+    pub burn: u64,
+    pub transfer: u64,
+}
+
+/// `amount * numerator / denominator`, rounded down, without intermediate
+/// overflow. `numerator` must not exceed `denominator`.
+fn mul_div_floor(amount: u64, numerator: u64, denominator: u64) -> u64 {
+    debug_assert!(numerator <= denominator);
+    (amount as u128 * numerator as u128 / denominator as u128) as u64
+}
+
+/// Platform fee on `total_reward`, rounded down.
+pub fn platform_fee(total_reward: u64) -> u64 {
+    mul_div_floor(total_reward, PLATFORM_FEE_RATE as u64, BASIS_POINTS as u64)
+}
+
+/// Split `total_reward` into the platform fee, then 75% / 25% of the rest
+/// between winner and runner-up. Rounding dust goes to the runner-up, so the
+/// parts always add up to `total_reward`.
+pub fn place_rewards(total_reward: u64) -> PlaceRewards {
+    let platform_fee = platform_fee(total_reward);
+    let reward_after_fee = total_reward - platform_fee;
+    let winner = mul_div_floor(reward_after_fee, WINNER_SHARE_PERCENT as u64, 100);
+
+    PlaceRewards {
+        platform_fee,
+        winner,
+        runner_up: reward_after_fee - winner,
+    }
+}
+
+/// Split a duel's pot of both stakes: the platform fee, then all of the rest
+/// to the winner. There is no runner-up prize.
+pub fn duel_rewards(pot: u64) -> PlaceRewards {
+    let platform_fee = platform_fee(pot);
+
+    PlaceRewards {
+        platform_fee,
+        winner: pot - platform_fee,
+        runner_up: 0,
+    }
+}
+
+/// Bounty paid to whoever cranks a finalization, taken out of the platform
+/// fee. `bounty_bps` is capped at the whole fee.
+pub fn crank_bounty(platform_fee: u64, bounty_bps: u16) -> u64 {
+    let bounty_bps = bounty_bps.min(BASIS_POINTS);
+    mul_div_floor(platform_fee, bounty_bps as u64, BASIS_POINTS as u64)
+}
+
+/// Share of a platform fee paid into the running season's prize pool.
+/// `pool_share_bps` is capped at the whole fee.
+pub fn season_pool_share(platform_fee: u64, pool_share_bps: u16) -> u64 {
+    let pool_share_bps = pool_share_bps.min(BASIS_POINTS);
+    mul_div_floor(platform_fee, pool_share_bps as u64, BASIS_POINTS as u64)
+}
+
+/// A team member's cut of `amount` for a `share_bps` share, rounded down.
+/// The captain takes whatever dust the members' cuts leave.
+pub fn team_share(amount: u64, share_bps: u16) -> u64 {
+    let share_bps = share_bps.min(BASIS_POINTS);
+    mul_div_floor(amount, share_bps as u64, BASIS_POINTS as u64)
+}
+
+/// What is left for the creator once the reward has been paid out of the
+/// fees collected by the treasury. `None` when the fees don't cover it.
+pub fn creator_remainder(collected_fees: u64, total_reward: u64) -> Option<u64> {
+    collected_fees.checked_sub(total_reward)
+}
+
+/// Equal share of `voting_pool` for each of `winning_voters`, rounded down.
+/// Zero when nobody backed the winner.
+pub fn voter_share(voting_pool: u64, winning_voters: u64) -> u64 {
+    voting_pool.checked_div(winning_voters).unwrap_or(0)
+}
+
+/// Share of `voting_pool` for a voter holding `weight` out of the
+/// `total_weight` behind the winner, rounded down. Zero when nobody backed
+/// the winner; a weight above the total is capped at the whole pool.
+pub fn weighted_share(voting_pool: u64, weight: u64, total_weight: u64) -> u64 {
+    if total_weight == 0 {
+        return 0;
+    }
+    mul_div_floor(voting_pool, weight.min(total_weight), total_weight)
+}
+
+/// Cost of casting `votes` quadratic votes at `voting_fee` per unit: the fee
+/// times `votes` squared. `None` on overflow.
+pub fn quadratic_cost(voting_fee: u64, votes: u64) -> Option<u64> {
+    votes.checked_mul(votes)?.checked_mul(voting_fee)
+}
+
+/// Share of the fees left for the creator that goes to the judges instead.
+/// `judge_fee_bps` is capped at the whole remainder.
+pub fn judge_fee(creator_remainder: u64, judge_fee_bps: u16) -> u64 {
+    let judge_fee_bps = judge_fee_bps.min(BASIS_POINTS);
+    mul_div_floor(creator_remainder, judge_fee_bps as u64, BASIS_POINTS as u64)
+}
+
+/// Mean of the scores a submission received, rounded down. Zero when it has
+/// none.
+pub fn mean_score(scores: &[u8]) -> u64 {
+    let total: u64 = scores.iter().map(|&s| s as u64).sum();
+    total.checked_div(scores.len() as u64).unwrap_or(0)
+}
+
+/// Median of the scores a submission received; with an even count, the mean
+/// of the middle two rounded down. Zero when it has none. Sorts `scores`.
+pub fn median_score(scores: &mut [u8]) -> u64 {
+    if scores.is_empty() {
+        return 0;
+    }
+    scores.sort_unstable();
+    let mid = scores.len() / 2;
+    if scores.len() % 2 == 1 {
+        scores[mid] as u64
+    } else {
+        (scores[mid - 1] as u64 + scores[mid] as u64) / 2
+    }
+}
+
+/// A score out of `MAX_SCORE` as basis points, so it can be blended with a
+/// share of the vote. Scores above `MAX_SCORE` count as `MAX_SCORE`.
+pub fn score_points(score: u64) -> u64 {
+    mul_div_floor(
+        BASIS_POINTS as u64,
+        score.min(MAX_SCORE as u64),
+        MAX_SCORE as u64,
+    )
+}
+
+/// A submission's share of `total_votes` in basis points, rounded down. Zero
+/// when nobody voted.
+pub fn vote_points(votes: u64, total_votes: u64) -> u64 {
+    if total_votes == 0 {
+        return 0;
+    }
+    mul_div_floor(BASIS_POINTS as u64, votes.min(total_votes), total_votes)
+}
+
+/// Weighted average of the judges' and the public's points, with
+/// `judge_weight_bps` going to the judges. The weight is capped at judges
+/// alone.
+pub fn blend_points(judge_points: u64, vote_points: u64, judge_weight_bps: u16) -> u64 {
+    let judge_weight = judge_weight_bps.min(BASIS_POINTS) as u128;
+    let vote_weight = BASIS_POINTS as u128 - judge_weight;
+    ((judge_points as u128 * judge_weight + vote_points as u128 * vote_weight)
+        / BASIS_POINTS as u128) as u64
+}
+
+/// Split a liquidity pool transfer into the burned part and the part that
+/// reaches the recipient.
+pub fn burn_split(amount: u64) -> BurnSplit {
+    let burn = mul_div_floor(amount, BURN_RATE as u64, 100);
+
+    BurnSplit {
+        burn,
+        transfer: amount - burn,
+    }
+}
@@ -0,0 +1,231 @@
+use coinpetitive_math::*;
+use proptest::prelude::*;
+
+// Every amount up to this bound is checked exhaustively on top of the
+// randomised runs, which is where rounding edge cases live
+const EXHAUSTIVE_LIMIT: u64 = 100_000;
+
+fn check_place_rewards(total_reward: u64) {
+    let rewards = place_rewards(total_reward);
+
+    // Nothing is created or lost
+    assert_eq!(rewards.total(), total_reward);
+
+    // Platform fee is 2.1% rounded down
+    let fee = rewards.platform_fee as u128;
+    let scaled = total_reward as u128 * PLATFORM_FEE_RATE as u128;
+    assert!(fee * BASIS_POINTS as u128 <= scaled);
+    assert!(scaled < (fee + 1) * BASIS_POINTS as u128);
+
+    // Winner gets 75% of the rest rounded down, runner-up keeps the dust
+    let after_fee = (total_reward - rewards.platform_fee) as u128;
+    let winner = rewards.winner as u128;
+    assert!(winner * 100 <= after_fee * WINNER_SHARE_PERCENT as u128);
+    assert!(after_fee * (WINNER_SHARE_PERCENT as u128) < (winner + 1) * 100);
+    assert!(rewards.winner >= rewards.runner_up || total_reward < 4);
+}
+
+fn check_burn_split(amount: u64) {
+    let split = burn_split(amount);
+
+    assert_eq!(split.burn + split.transfer, amount);
+    assert_eq!(split.burn as u128, amount as u128 * BURN_RATE as u128 / 100);
+}
+
+#[test]
+fn place_rewards_exhaustive_small_amounts() {
+    for total_reward in 0..=EXHAUSTIVE_LIMIT {
+        check_place_rewards(total_reward);
+    }
+}
+
+#[test]
+fn burn_split_exhaustive_small_amounts() {
+    for amount in 0..=EXHAUSTIVE_LIMIT {
+        check_burn_split(amount);
+    }
+}
+
+#[test]
+fn voter_share_exhaustive_small_pools() {
+    for voting_pool in 0..=1_000 {
+        for winning_voters in 0..=150 {
+            let share = voter_share(voting_pool, winning_voters);
+            if winning_voters == 0 {
+                assert_eq!(share, 0);
+            } else {
+                assert!(share * winning_voters <= voting_pool);
+                assert!(voting_pool - share * winning_voters < winning_voters);
+            }
+        }
+    }
+}
+
+#[test]
+fn extremes() {
+    check_place_rewards(u64::MAX);
+    check_burn_split(u64::MAX);
+    assert_eq!(platform_fee(0), 0);
+    assert_eq!(voter_share(u64::MAX, 1), u64::MAX);
+    assert_eq!(creator_remainder(0, 0), Some(0));
+    assert_eq!(creator_remainder(0, 1), None);
+}
+
+#[test]
+fn matches_documented_rates() {
+    // 1000 CPT reward: 21 CPT fee, 734.25 to the winner, 244.75 to the runner-up
+    let cpt = 1_000_000_000;
+    let rewards = place_rewards(1_000 * cpt);
+    assert_eq!(rewards.platform_fee, 21 * cpt);
+    assert_eq!(rewards.winner, 734_250_000_000);
+    assert_eq!(rewards.runner_up, 244_750_000_000);
+
+    assert_eq!(burn_split(100 * cpt).burn, cpt);
+
+    // A 10% bounty on that fee is 2.1 CPT
+    assert_eq!(crank_bounty(rewards.platform_fee, 1_000), 2_100_000_000);
+
+    // Judges' 70 and 90 average to 80 / 100 = 8000 points; blended 50/50 with
+    // a quarter of the vote that is 5250
+    assert_eq!(median_score(&mut [90, 70]), 80);
+    assert_eq!(score_points(80), 8_000);
+    assert_eq!(blend_points(8_000, vote_points(1, 4), 5_000), 5_250);
+}
+
+proptest! {
+    #[test]
+    fn place_rewards_conserve_and_round_down(total_reward in any::<u64>()) {
+        check_place_rewards(total_reward);
+    }
+
+    #[test]
+    fn platform_fee_is_monotonic(a in any::<u64>(), b in any::<u64>()) {
+        let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+        prop_assert!(platform_fee(lo) <= platform_fee(hi));
+        prop_assert!(place_rewards(lo).winner <= place_rewards(hi).winner);
+    }
+
+    #[test]
+    fn voter_shares_never_exceed_pool(voting_pool in any::<u64>(), winning_voters in any::<u64>()) {
+        let share = voter_share(voting_pool, winning_voters);
+        if winning_voters == 0 {
+            prop_assert_eq!(share, 0);
+        } else {
+            let paid = share as u128 * winning_voters as u128;
+            prop_assert!(paid <= voting_pool as u128);
+            prop_assert!(voting_pool as u128 - paid < winning_voters as u128);
+        }
+    }
+
+    #[test]
+    fn creator_remainder_is_checked(collected_fees in any::<u64>(), total_reward in any::<u64>()) {
+        match creator_remainder(collected_fees, total_reward) {
+            Some(remainder) => prop_assert_eq!(remainder + total_reward, collected_fees),
+            None => prop_assert!(collected_fees < total_reward),
+        }
+    }
+
+    #[test]
+    fn crank_bounty_stays_within_platform_fee(platform_fee in any::<u64>(), bounty_bps in any::<u16>()) {
+        let bounty = crank_bounty(platform_fee, bounty_bps);
+        prop_assert!(bounty <= platform_fee);
+        if bounty_bps >= BASIS_POINTS {
+            prop_assert_eq!(bounty, platform_fee);
+        }
+    }
+
+    #[test]
+    fn season_pool_share_stays_within_platform_fee(platform_fee in any::<u64>(), pool_share_bps in any::<u16>()) {
+        let share = season_pool_share(platform_fee, pool_share_bps);
+        prop_assert!(share <= platform_fee);
+        prop_assert!(share as u128 * BASIS_POINTS as u128 <= platform_fee as u128 * pool_share_bps as u128);
+        if pool_share_bps >= BASIS_POINTS {
+            prop_assert_eq!(share, platform_fee);
+        }
+    }
+
+    #[test]
+    fn team_shares_never_exceed_amount(
+        amount in any::<u64>(),
+        shares in prop::collection::vec(0..=BASIS_POINTS, 1..5),
+    ) {
+        let total_bps: u32 = shares.iter().map(|&s| s as u32).sum();
+        prop_assume!(total_bps <= BASIS_POINTS as u32);
+        let paid: u128 = shares.iter().map(|&s| team_share(amount, s) as u128).sum();
+        prop_assert!(paid <= amount as u128);
+        if total_bps == BASIS_POINTS as u32 {
+            // Each floor loses under one unit, so the captain's dust is small
+            prop_assert!(amount as u128 - paid < shares.len() as u128);
+        }
+    }
+
+    #[test]
+    fn duel_winner_takes_pot_after_fee(pot in any::<u64>()) {
+        let rewards = duel_rewards(pot);
+        prop_assert_eq!(rewards.total(), pot);
+        prop_assert_eq!(rewards.platform_fee, platform_fee(pot));
+        prop_assert_eq!(rewards.runner_up, 0);
+    }
+
+    #[test]
+    fn burn_split_conserves(amount in any::<u64>()) {
+        check_burn_split(amount);
+    }
+
+    #[test]
+    fn weighted_shares_never_exceed_pool(
+        voting_pool in any::<u64>(),
+        weights in prop::collection::vec(any::<u32>(), 1..50),
+    ) {
+        let weights: Vec<u64> = weights.into_iter().map(u64::from).collect();
+        let total: u64 = weights.iter().sum();
+        let paid: u128 = weights
+            .iter()
+            .map(|w| weighted_share(voting_pool, *w, total) as u128)
+            .sum();
+        prop_assert!(paid <= voting_pool as u128);
+        if total > 0 {
+            // Each voter loses less than one token to rounding
+            prop_assert!(voting_pool as u128 - paid < weights.len() as u128);
+        }
+    }
+
+    #[test]
+    fn quadratic_cost_is_checked(voting_fee in any::<u64>(), votes in any::<u32>()) {
+        let exact = voting_fee as u128 * (votes as u128 * votes as u128);
+        match quadratic_cost(voting_fee, votes.into()) {
+            Some(cost) => prop_assert_eq!(cost as u128, exact),
+            None => prop_assert!(exact > u64::MAX as u128),
+        }
+    }
+
+    #[test]
+    fn aggregate_scores_stay_within_range(mut scores in prop::collection::vec(any::<u8>(), 1..20)) {
+        let (min, max) = (
+            *scores.iter().min().unwrap() as u64,
+            *scores.iter().max().unwrap() as u64,
+        );
+        let mean = mean_score(&scores);
+        let median = median_score(&mut scores);
+        prop_assert!(min <= mean && mean <= max);
+        prop_assert!(min <= median && median <= max);
+    }
+
+    #[test]
+    fn blended_points_stay_between_inputs(
+        judge_points in 0..=BASIS_POINTS as u64,
+        vote_points in 0..=BASIS_POINTS as u64,
+        judge_weight_bps in any::<u16>(),
+    ) {
+        let blended = blend_points(judge_points, vote_points, judge_weight_bps);
+        prop_assert!(judge_points.min(vote_points) <= blended);
+        prop_assert!(blended <= judge_points.max(vote_points));
+        prop_assert_eq!(blend_points(judge_points, vote_points, BASIS_POINTS), judge_points);
+        prop_assert_eq!(blend_points(judge_points, vote_points, 0), vote_points);
+    }
+
+    #[test]
+    fn judge_fee_stays_within_remainder(creator_remainder in any::<u64>(), judge_fee_bps in any::<u16>()) {
+        prop_assert!(judge_fee(creator_remainder, judge_fee_bps) <= creator_remainder);
+    }
+}
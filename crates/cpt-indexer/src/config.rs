@@ -0,0 +1,60 @@
+//! Command line and environment configuration.
+
+use clap::Parser;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Clone, Debug, Parser)]
+#[command(
+    name = "cpt-indexer",
+    version,
+    about = "Index Coinpetitive program events into SQLite"
+)]
+pub struct Config {
+    /// RPC endpoint, defaults to a local validator
+    #[arg(
+        long,
+        short = 'u',
+        env = "CPT_RPC_URL",
+        default_value = "http://127.0.0.1:8899"
+    )]
+    pub url: String,
+
+    /// Websocket endpoint for `--follow`, derived from the RPC URL by default
+    #[arg(long, env = "CPT_WS_URL")]
+    pub ws_url: Option<String>,
+
+    /// SQLite database, created if missing
+    #[arg(long, env = "CPT_INDEXER_DB", default_value = "coinpetitive.db")]
+    pub db: PathBuf,
+
+    /// Re-read history back to this slot instead of stopping at the last
+    /// indexed transaction; already indexed transactions are skipped
+    #[arg(long)]
+    pub from_slot: Option<u64>,
+
+    /// Keep running and index new transactions as they land
+    #[arg(long)]
+    pub follow: bool,
+
+    /// Seconds between catch-ups in `--follow` mode when no notification arrives
+    #[arg(long, env = "CPT_INDEXER_INTERVAL", default_value_t = 30)]
+    pub interval_secs: u64,
+}
+
+impl Config {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs)
+    }
+
+    pub fn ws_url(&self) -> String {
+        self.ws_url.clone().unwrap_or_else(|| {
+            let url = self
+                .url
+                .replacen("https://", "wss://", 1)
+                .replacen("http://", "ws://", 1);
+            // The test validator serves websockets on the port after RPC
+            url.replace(":8899", ":8900")
+        })
+    }
+}
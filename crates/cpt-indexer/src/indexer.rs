@@ -0,0 +1,153 @@
+//! Walks the program's history and feeds it to the store.
+
+use crate::source::{IndexedTransaction, SignatureInfo, Source};
+use crate::store::Store;
+use anyhow::{Context, Result};
+use coinpetitive_client::ID;
+use solana_client::pubsub_client::PubsubClient;
+use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BackfillReport {
+    /// Signatures listed in the requested range.
+    pub seen: usize,
+    /// Transactions written to the store by this run.
+    pub indexed: usize,
+    /// Transactions that were already indexed.
+    pub skipped: usize,
+}
+
+pub struct Indexer<S> {
+    source: S,
+    store: Store,
+}
+
+impl<S: Source> Indexer<S> {
+    pub fn new(source: S, store: Store) -> Self {
+        Self { source, store }
+    }
+
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    pub fn into_parts(self) -> (S, Store) {
+        (self.source, self.store)
+    }
+
+    /// Index every transaction from `from_slot` onwards, oldest first. Without
+    /// a slot, only transactions newer than the last indexed one are read.
+    pub fn backfill(&mut self, from_slot: Option<u64>) -> Result<BackfillReport> {
+        let until = match from_slot {
+            Some(_) => None,
+            None => self.store.cursor()?.map(|cursor| cursor.signature),
+        };
+        let from_slot = from_slot.unwrap_or(0);
+
+        // Pages come newest first; collect the whole range before applying
+        // anything so the store only ever moves forward in time
+        let mut pending: Vec<SignatureInfo> = Vec::new();
+        let mut before: Option<String> = None;
+        'pages: loop {
+            let page = self
+                .source
+                .signatures(before.as_deref(), until.as_deref())
+                .context("listing program signatures")?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            before = Some(last.signature.clone());
+            for info in page {
+                if info.slot < from_slot {
+                    break 'pages;
+                }
+                pending.push(info);
+            }
+        }
+
+        let mut report = BackfillReport {
+            seen: pending.len(),
+            ..BackfillReport::default()
+        };
+        for info in pending.iter().rev() {
+            if self.store.is_indexed(&info.signature)? {
+                report.skipped += 1;
+                continue;
+            }
+            let tx = if info.failed {
+                IndexedTransaction::failed(info)
+            } else {
+                self.source
+                    .transaction(info)
+                    .with_context(|| format!("fetching transaction {}", info.signature))?
+            };
+            if self.store.apply(&tx)? {
+                report.indexed += 1;
+            } else {
+                report.skipped += 1;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Refresh challenge rows from their accounts as of `slot`.
+    pub fn sync_challenges(&mut self, slot: u64) -> Result<usize> {
+        let challenges = self
+            .source
+            .challenges()
+            .context("fetching challenge accounts")?;
+        self.store.sync_challenges(&challenges, slot)?;
+        Ok(challenges.len())
+    }
+
+    /// Follow the program forever. Every log notification, or `interval`
+    /// without one, triggers a catch-up from the cursor, so notifications
+    /// lost while the websocket was down are picked up by the next one.
+    pub fn follow(&mut self, ws_url: &str, interval: Duration, slot: impl Fn() -> u64) -> ! {
+        loop {
+            self.catch_up(&slot);
+
+            let filter = RpcTransactionLogsFilter::Mentions(vec![ID.to_string()]);
+            let config = RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            };
+            let (_subscription, notifications) =
+                match PubsubClient::logs_subscribe(ws_url, filter, config) {
+                    Ok(subscription) => subscription,
+                    Err(e) => {
+                        log::warn!("subscribing to {ws_url} failed: {e}");
+                        std::thread::sleep(interval);
+                        continue;
+                    }
+                };
+            log::info!("subscribed to program logs on {ws_url}");
+
+            loop {
+                match notifications.recv_timeout(interval) {
+                    Ok(_) => self.catch_up(&slot),
+                    Err(e) if e.is_timeout() => self.catch_up(&slot),
+                    Err(_) => {
+                        log::warn!("log subscription dropped, resubscribing");
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    fn catch_up(&mut self, slot: &impl Fn() -> u64) {
+        match self.backfill(None) {
+            Ok(report) if report.indexed > 0 => {
+                log::info!("indexed {} transactions", report.indexed)
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("catch-up failed: {e:#}"),
+        }
+        if let Err(e) = self.sync_challenges(slot()) {
+            log::warn!("challenge sync failed: {e:#}");
+        }
+    }
+}
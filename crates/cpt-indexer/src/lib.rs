@@ -0,0 +1,12 @@
+//! Indexer for the Coinpetitive program.
+//!
+//! Reads the program's transactions oldest first, decodes the events each one
+//! emitted and materialises them into a normalised SQLite schema: challenges,
+//! participants, submissions, votes, payouts and fee tracker snapshots.
+//! Every transaction is applied atomically and keyed by its signature, so
+//! backfilling over a range that was already indexed changes nothing.
+
+pub mod config;
+pub mod indexer;
+pub mod source;
+pub mod store;
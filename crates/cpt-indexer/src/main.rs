@@ -0,0 +1,36 @@
+use anyhow::Context;
+use clap::Parser;
+use cpt_indexer::config::Config;
+use cpt_indexer::indexer::Indexer;
+use cpt_indexer::source::RpcSource;
+use cpt_indexer::store::Store;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+
+fn main() -> anyhow::Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let config = Config::parse();
+
+    let store = Store::open(&config.db)
+        .with_context(|| format!("opening database {}", config.db.display()))?;
+    let rpc = || RpcClient::new_with_commitment(config.url.clone(), CommitmentConfig::confirmed());
+    let slots = rpc();
+    let slot = || slots.get_slot().unwrap_or_default();
+    let mut indexer = Indexer::new(RpcSource::new(rpc()), store);
+
+    let report = indexer.backfill(config.from_slot)?;
+    let challenges = indexer.sync_challenges(slot())?;
+    log::info!(
+        "backfilled {} transactions ({} new, {} already indexed), synced {} challenges into {}",
+        report.seen,
+        report.indexed,
+        report.skipped,
+        challenges,
+        config.db.display()
+    );
+
+    if config.follow {
+        indexer.follow(&config.ws_url(), config.interval(), slot);
+    }
+    Ok(())
+}
@@ -0,0 +1,134 @@
+//! Where transactions come from. `RpcSource` reads them from an RPC node;
+//! tests feed the indexer from memory.
+
+use coinpetitive_client::event_log::{self, Event};
+use coinpetitive_client::{rpc, Challenge};
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::UiTransactionEncoding;
+use std::str::FromStr;
+
+/// A transaction that mentions the program, as listed by
+/// `getSignaturesForAddress`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+    pub failed: bool,
+}
+
+/// A transaction and the program events it emitted, in emission order.
+pub struct IndexedTransaction {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub failed: bool,
+    pub events: Vec<Event>,
+}
+
+impl IndexedTransaction {
+    /// Failed transactions are recorded so they are not listed again, but
+    /// none of their events took effect.
+    pub fn failed(info: &SignatureInfo) -> Self {
+        Self {
+            signature: info.signature.clone(),
+            slot: info.slot,
+            block_time: None,
+            failed: true,
+            events: Vec::new(),
+        }
+    }
+}
+
+pub trait Source {
+    /// One page of signatures, newest first, older than `before` and newer
+    /// than `until`. An empty page means history is exhausted.
+    fn signatures(
+        &self,
+        before: Option<&str>,
+        until: Option<&str>,
+    ) -> rpc::Result<Vec<SignatureInfo>>;
+
+    fn transaction(&self, info: &SignatureInfo) -> rpc::Result<IndexedTransaction>;
+
+    /// Current state of every challenge account.
+    fn challenges(&self) -> rpc::Result<Vec<(Pubkey, Challenge)>>;
+}
+
+pub struct RpcSource {
+    rpc: RpcClient,
+}
+
+impl RpcSource {
+    pub fn new(rpc: RpcClient) -> Self {
+        Self { rpc }
+    }
+}
+
+fn parse_signature(signature: &str) -> rpc::Result<Signature> {
+    Signature::from_str(signature).map_err(|e| rpc::Error::Decode(format!("{signature}: {e}")))
+}
+
+impl Source for RpcSource {
+    fn signatures(
+        &self,
+        before: Option<&str>,
+        until: Option<&str>,
+    ) -> rpc::Result<Vec<SignatureInfo>> {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before: before.map(parse_signature).transpose()?,
+            until: until.map(parse_signature).transpose()?,
+            limit: None,
+            commitment: Some(self.rpc.commitment()),
+        };
+        let page = self
+            .rpc
+            .get_signatures_for_address_with_config(&coinpetitive_client::ID, config)?;
+        Ok(page
+            .into_iter()
+            .map(|status| SignatureInfo {
+                signature: status.signature,
+                slot: status.slot,
+                failed: status.err.is_some(),
+            })
+            .collect())
+    }
+
+    fn transaction(&self, info: &SignatureInfo) -> rpc::Result<IndexedTransaction> {
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Json),
+            commitment: Some(self.rpc.commitment()),
+            max_supported_transaction_version: Some(0),
+        };
+        let tx = self
+            .rpc
+            .get_transaction_with_config(&parse_signature(&info.signature)?, config)?;
+        let meta = tx
+            .transaction
+            .meta
+            .ok_or_else(|| rpc::Error::Decode(format!("{}: no status meta", info.signature)))?;
+        let logs = match meta.log_messages {
+            OptionSerializer::Some(logs) => logs,
+            _ => Vec::new(),
+        };
+
+        Ok(IndexedTransaction {
+            signature: info.signature.clone(),
+            slot: tx.slot,
+            block_time: tx.block_time,
+            failed: meta.err.is_some(),
+            // A failed transaction's logs can hold events that were rolled back
+            events: match meta.err {
+                Some(_) => Vec::new(),
+                None => event_log::events_from_logs(&logs),
+            },
+        })
+    }
+
+    fn challenges(&self) -> rpc::Result<Vec<(Pubkey, Challenge)>> {
+        rpc::fetch_challenges(&self.rpc)
+    }
+}
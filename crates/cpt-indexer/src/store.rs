@@ -0,0 +1,1199 @@
+//! The SQLite schema and how each event is written into it.
+
+use coinpetitive_client::constraints::BASE_REPUTATION;
+use coinpetitive_client::event_log::Event;
+use coinpetitive_client::events::PayoutKind;
+use coinpetitive_client::{pda, DisqualificationReason};
+use coinpetitive_client::{Challenge, JudgingMode, PrizeKind, VotingMode};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use solana_sdk::pubkey::Pubkey;
+use std::path::Path;
+
+use crate::source::IndexedTransaction;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    signature  TEXT PRIMARY KEY,
+    slot       INTEGER NOT NULL,
+    block_time INTEGER,
+    failed     INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS challenges (
+    address            TEXT PRIMARY KEY,
+    creator            TEXT,
+    reward_token_mint  TEXT,
+    reward             INTEGER,
+    prize_kind         TEXT NOT NULL DEFAULT 'cpt',
+    prize_mint         TEXT,
+    participation_fee  INTEGER,
+    voting_fee         INTEGER,
+    max_participants   INTEGER,
+    min_participants   INTEGER,
+    min_voters         INTEGER,
+    max_voters         INTEGER,
+    submission_end     INTEGER,
+    voting_end         INTEGER,
+    commit_reveal      INTEGER NOT NULL DEFAULT 0,
+    reveal_end         INTEGER,
+    vote_commitments   INTEGER NOT NULL DEFAULT 0,
+    voting_mode        TEXT NOT NULL DEFAULT 'flat',
+    locked_stake       INTEGER NOT NULL DEFAULT 0,
+    judging_mode       TEXT NOT NULL DEFAULT 'public_vote',
+    judge_weight_bps   INTEGER NOT NULL DEFAULT 0,
+    judge_fee_bps      INTEGER NOT NULL DEFAULT 0,
+    voter_badges       INTEGER NOT NULL DEFAULT 0,
+    min_participant_reputation INTEGER NOT NULL DEFAULT 0,
+    min_voter_reputation       INTEGER NOT NULL DEFAULT 0,
+    dispute_end        INTEGER,
+    status             TEXT NOT NULL DEFAULT 'active',
+    is_frozen          INTEGER NOT NULL DEFAULT 0,
+    submissions_closed INTEGER NOT NULL DEFAULT 0,
+    winning_submission TEXT,
+    winning_votes      INTEGER,
+    finalized_by       TEXT,
+    challenge_treasury INTEGER,
+    voting_treasury    INTEGER,
+    created_slot       INTEGER,
+    updated_slot       INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS participants (
+    challenge TEXT NOT NULL,
+    wallet    TEXT NOT NULL,
+    fee       INTEGER NOT NULL,
+    refunded  INTEGER NOT NULL DEFAULT 0,
+    slot      INTEGER NOT NULL,
+    signature TEXT NOT NULL,
+    PRIMARY KEY (challenge, wallet)
+);
+
+CREATE TABLE IF NOT EXISTS submissions (
+    challenge    TEXT NOT NULL,
+    submission   TEXT NOT NULL,
+    participant  TEXT NOT NULL,
+    fee          INTEGER NOT NULL,
+    votes        INTEGER NOT NULL DEFAULT 0,
+    disqualified INTEGER NOT NULL DEFAULT 0,
+    slot         INTEGER NOT NULL,
+    signature    TEXT NOT NULL,
+    PRIMARY KEY (challenge, submission)
+);
+
+CREATE TABLE IF NOT EXISTS votes (
+    challenge  TEXT NOT NULL,
+    voter      TEXT NOT NULL,
+    submission TEXT NOT NULL,
+    fee        INTEGER NOT NULL,
+    weight     INTEGER NOT NULL DEFAULT 1,
+    stake      INTEGER NOT NULL DEFAULT 0,
+    slot       INTEGER NOT NULL,
+    signature  TEXT NOT NULL,
+    PRIMARY KEY (challenge, voter, submission)
+);
+
+CREATE TABLE IF NOT EXISTS judges (
+    challenge TEXT NOT NULL,
+    judge     TEXT NOT NULL,
+    PRIMARY KEY (challenge, judge)
+);
+
+CREATE TABLE IF NOT EXISTS judge_scores (
+    challenge  TEXT NOT NULL,
+    judge      TEXT NOT NULL,
+    submission TEXT NOT NULL,
+    score      INTEGER NOT NULL,
+    slot       INTEGER NOT NULL,
+    signature  TEXT NOT NULL,
+    PRIMARY KEY (challenge, judge, submission)
+);
+
+CREATE TABLE IF NOT EXISTS disputes (
+    challenge     TEXT NOT NULL,
+    submission    TEXT NOT NULL,
+    disputer      TEXT NOT NULL,
+    bond          INTEGER NOT NULL,
+    status        TEXT NOT NULL DEFAULT 'open',
+    resolved_by   TEXT,
+    slot          INTEGER NOT NULL,
+    signature     TEXT NOT NULL,
+    resolved_slot INTEGER,
+    PRIMARY KEY (challenge, submission, signature)
+);
+
+CREATE TABLE IF NOT EXISTS moderation (
+    challenge    TEXT NOT NULL,
+    submission   TEXT NOT NULL,
+    submitter    TEXT NOT NULL,
+    moderator    TEXT NOT NULL,
+    reason       TEXT NOT NULL,
+    fee_refunded INTEGER NOT NULL,
+    slot         INTEGER NOT NULL,
+    signature    TEXT NOT NULL,
+    PRIMARY KEY (challenge, submission)
+);
+
+CREATE TABLE IF NOT EXISTS sponsors (
+    challenge    TEXT NOT NULL,
+    sponsor      TEXT NOT NULL,
+    amount       INTEGER NOT NULL,
+    metadata_uri TEXT,
+    refunded     INTEGER NOT NULL DEFAULT 0,
+    slot         INTEGER NOT NULL,
+    signature    TEXT NOT NULL,
+    PRIMARY KEY (challenge, sponsor)
+);
+
+CREATE TABLE IF NOT EXISTS trophies (
+    challenge  TEXT NOT NULL,
+    rank       INTEGER NOT NULL,
+    submission TEXT NOT NULL,
+    winner     TEXT NOT NULL,
+    mint       TEXT NOT NULL,
+    votes      INTEGER NOT NULL,
+    slot       INTEGER NOT NULL,
+    signature  TEXT NOT NULL,
+    PRIMARY KEY (challenge, rank)
+);
+
+CREATE TABLE IF NOT EXISTS badges (
+    challenge   TEXT NOT NULL,
+    wallet      TEXT NOT NULL,
+    mint        TEXT NOT NULL,
+    participant INTEGER NOT NULL,
+    voter       INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    signature   TEXT NOT NULL,
+    PRIMARY KEY (challenge, wallet)
+);
+
+CREATE TABLE IF NOT EXISTS user_profiles (
+    wallet     TEXT PRIMARY KEY,
+    ordinal    INTEGER NOT NULL,
+    reputation INTEGER NOT NULL,
+    slot       INTEGER NOT NULL,
+    signature  TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS seasons (
+    id             INTEGER PRIMARY KEY,
+    start_time     INTEGER NOT NULL,
+    end_time       INTEGER NOT NULL,
+    pool_share_bps INTEGER NOT NULL,
+    payout_places  INTEGER NOT NULL,
+    prize_pool     INTEGER NOT NULL DEFAULT 0,
+    settled        INTEGER NOT NULL DEFAULT 0,
+    slot           INTEGER NOT NULL,
+    signature      TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS season_points (
+    season INTEGER NOT NULL,
+    wallet TEXT NOT NULL,
+    points INTEGER NOT NULL,
+    rank   INTEGER,
+    prize  INTEGER,
+    PRIMARY KEY (season, wallet)
+);
+
+CREATE TABLE IF NOT EXISTS tournaments (
+    address       TEXT PRIMARY KEY,
+    creator       TEXT NOT NULL,
+    round_count   INTEGER NOT NULL,
+    advance_count INTEGER NOT NULL,
+    prize         INTEGER NOT NULL,
+    roll_fees     INTEGER NOT NULL,
+    current_round INTEGER NOT NULL DEFAULT 0,
+    status        TEXT NOT NULL DEFAULT 'active',
+    recipient     TEXT,
+    payout        INTEGER,
+    slot          INTEGER NOT NULL,
+    signature     TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS tournament_rounds (
+    tournament TEXT NOT NULL,
+    round      INTEGER NOT NULL,
+    challenge  TEXT NOT NULL,
+    PRIMARY KEY (tournament, round)
+);
+
+CREATE TABLE IF NOT EXISTS tournament_qualifiers (
+    tournament TEXT NOT NULL,
+    round      INTEGER NOT NULL,
+    wallet     TEXT NOT NULL,
+    PRIMARY KEY (tournament, round, wallet)
+);
+
+CREATE TABLE IF NOT EXISTS teams (
+    address   TEXT PRIMARY KEY,
+    challenge TEXT NOT NULL,
+    captain   TEXT NOT NULL,
+    entered   INTEGER NOT NULL DEFAULT 0,
+    slot      INTEGER NOT NULL,
+    signature TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS team_members (
+    team      TEXT NOT NULL,
+    member    TEXT NOT NULL,
+    share_bps INTEGER NOT NULL,
+    paid      INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (team, member)
+);
+
+CREATE TABLE IF NOT EXISTS duels (
+    challenge TEXT PRIMARY KEY,
+    creator   TEXT NOT NULL,
+    opponent  TEXT,
+    stake     INTEGER NOT NULL,
+    accept_by INTEGER NOT NULL,
+    pot       INTEGER NOT NULL,
+    matched   INTEGER NOT NULL DEFAULT 0,
+    slot      INTEGER NOT NULL,
+    signature TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS payouts (
+    signature TEXT NOT NULL,
+    idx       INTEGER NOT NULL,
+    challenge TEXT NOT NULL,
+    kind      TEXT NOT NULL,
+    recipient TEXT NOT NULL,
+    amount    INTEGER NOT NULL,
+    slot      INTEGER NOT NULL,
+    PRIMARY KEY (signature, idx)
+);
+
+CREATE TABLE IF NOT EXISTS fee_tracker_snapshots (
+    signature                TEXT NOT NULL,
+    idx                      INTEGER NOT NULL,
+    slot                     INTEGER NOT NULL,
+    total_participation_fees INTEGER NOT NULL,
+    total_voting_fees        INTEGER NOT NULL,
+    total_challenges         INTEGER NOT NULL,
+    PRIMARY KEY (signature, idx)
+);
+
+CREATE TABLE IF NOT EXISTS cursor (
+    id        INTEGER PRIMARY KEY CHECK (id = 0),
+    slot      INTEGER NOT NULL,
+    signature TEXT NOT NULL
+);
+";
+
+/// The newest transaction indexed so far.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cursor {
+    pub slot: u64,
+    pub signature: String,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Read-only access for queries and tests.
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    pub fn cursor(&self) -> rusqlite::Result<Option<Cursor>> {
+        self.conn
+            .query_row(
+                "SELECT slot, signature FROM cursor WHERE id = 0",
+                [],
+                |row| {
+                    Ok(Cursor {
+                        slot: row.get(0)?,
+                        signature: row.get(1)?,
+                    })
+                },
+            )
+            .optional()
+    }
+
+    pub fn is_indexed(&self, signature: &str) -> rusqlite::Result<bool> {
+        self.conn
+            .query_row(
+                "SELECT 1 FROM transactions WHERE signature = ?1",
+                [signature],
+                |_| Ok(()),
+            )
+            .optional()
+            .map(|row| row.is_some())
+    }
+
+    /// Apply one transaction's events atomically. Returns false, changing
+    /// nothing, when the transaction was already indexed.
+    pub fn apply(&mut self, tx: &IndexedTransaction) -> rusqlite::Result<bool> {
+        let db = self.conn.transaction()?;
+
+        let inserted = db.execute(
+            "INSERT OR IGNORE INTO transactions (signature, slot, block_time, failed)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![tx.signature, tx.slot, tx.block_time, tx.failed],
+        )?;
+        if inserted == 0 {
+            return Ok(false);
+        }
+
+        for (idx, event) in tx.events.iter().enumerate() {
+            apply_event(&db, tx, idx, event)?;
+        }
+
+        db.execute(
+            "INSERT INTO cursor (id, slot, signature) VALUES (0, ?1, ?2)
+             ON CONFLICT (id) DO UPDATE SET slot = excluded.slot, signature = excluded.signature
+             WHERE excluded.slot >= cursor.slot",
+            params![tx.slot, tx.signature],
+        )?;
+
+        db.commit()?;
+        Ok(true)
+    }
+
+    /// Overwrite challenge rows with current account state, which also fills
+    /// in challenges created before the program emitted events.
+    pub fn sync_challenges(
+        &mut self,
+        challenges: &[(Pubkey, Challenge)],
+        slot: u64,
+    ) -> rusqlite::Result<()> {
+        let db = self.conn.transaction()?;
+        for (address, challenge) in challenges {
+            db.execute(
+                "INSERT INTO challenges (
+                    address, creator, reward_token_mint, reward, participation_fee, voting_fee,
+                    max_participants, min_participants, min_voters, max_voters, submission_end,
+                    voting_end, commit_reveal, reveal_end, vote_commitments, voting_mode,
+                    locked_stake, judging_mode, judge_weight_bps, judge_fee_bps, status, is_frozen,
+                    submissions_closed, winning_submission, winning_votes, challenge_treasury,
+                    voting_treasury, dispute_end, prize_kind, prize_mint, voter_badges,
+                    min_participant_reputation, min_voter_reputation, updated_slot
+                 ) VALUES (
+                    ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18,
+                    ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34
+                 )
+                 ON CONFLICT (address) DO UPDATE SET
+                    creator = excluded.creator,
+                    reward_token_mint = excluded.reward_token_mint,
+                    reward = excluded.reward,
+                    participation_fee = excluded.participation_fee,
+                    voting_fee = excluded.voting_fee,
+                    max_participants = excluded.max_participants,
+                    min_participants = excluded.min_participants,
+                    min_voters = excluded.min_voters,
+                    max_voters = excluded.max_voters,
+                    submission_end = excluded.submission_end,
+                    voting_end = excluded.voting_end,
+                    commit_reveal = excluded.commit_reveal,
+                    reveal_end = excluded.reveal_end,
+                    vote_commitments = excluded.vote_commitments,
+                    voting_mode = excluded.voting_mode,
+                    locked_stake = excluded.locked_stake,
+                    judging_mode = excluded.judging_mode,
+                    judge_weight_bps = excluded.judge_weight_bps,
+                    judge_fee_bps = excluded.judge_fee_bps,
+                    status = excluded.status,
+                    is_frozen = excluded.is_frozen,
+                    submissions_closed = excluded.submissions_closed,
+                    winning_submission = excluded.winning_submission,
+                    winning_votes = excluded.winning_votes,
+                    challenge_treasury = excluded.challenge_treasury,
+                    voting_treasury = excluded.voting_treasury,
+                    dispute_end = excluded.dispute_end,
+                    prize_kind = excluded.prize_kind,
+                    prize_mint = excluded.prize_mint,
+                    voter_badges = excluded.voter_badges,
+                    min_participant_reputation = excluded.min_participant_reputation,
+                    min_voter_reputation = excluded.min_voter_reputation,
+                    updated_slot = excluded.updated_slot
+                 WHERE excluded.updated_slot >= challenges.updated_slot",
+                params![
+                    address.to_string(),
+                    challenge.creator.to_string(),
+                    challenge.reward_token_mint.to_string(),
+                    challenge.reward,
+                    challenge.participation_fee,
+                    challenge.voting_fee,
+                    challenge.max_participants,
+                    challenge.min_participants,
+                    challenge.min_voters,
+                    challenge.max_voters,
+                    challenge.submission_end,
+                    challenge.voting_end,
+                    challenge.commit_reveal,
+                    challenge.reveal_end,
+                    challenge.vote_commitments,
+                    voting_mode(challenge.voting_mode),
+                    challenge.locked_stake,
+                    judging_mode(challenge.judging_mode),
+                    challenge.judge_weight_bps,
+                    challenge.judge_fee_bps,
+                    status(challenge),
+                    challenge.is_frozen,
+                    challenge.submissions_closed,
+                    challenge.winner.map(|w| w.to_string()),
+                    challenge.winner.map(|_| challenge.winning_votes),
+                    challenge.challenge_treasury,
+                    challenge.voting_treasury,
+                    challenge.results_proposed.then_some(challenge.dispute_end),
+                    prize_kind(challenge.prize_kind),
+                    prize_mint(challenge.prize_kind, &challenge.prize_mint),
+                    challenge.voter_badges,
+                    challenge.min_participant_reputation,
+                    challenge.min_voter_reputation,
+                    slot,
+                ],
+            )?;
+        }
+        db.commit()
+    }
+}
+
+/// Lifecycle status as stored in `challenges.status`.
+pub fn status(challenge: &Challenge) -> &'static str {
+    if challenge.is_cancelled {
+        "cancelled"
+    } else if challenge.is_active && challenge.results_proposed {
+        "proposed"
+    } else if challenge.is_active {
+        "active"
+    } else {
+        "finalized"
+    }
+}
+
+pub fn voting_mode(mode: VotingMode) -> &'static str {
+    match mode {
+        VotingMode::Flat => "flat",
+        VotingMode::StakeWeighted => "stake_weighted",
+        VotingMode::Quadratic => "quadratic",
+    }
+}
+
+pub fn judging_mode(mode: JudgingMode) -> &'static str {
+    match mode {
+        JudgingMode::PublicVote => "public_vote",
+        JudgingMode::Mean => "mean",
+        JudgingMode::Median => "median",
+    }
+}
+
+pub fn prize_kind(kind: PrizeKind) -> &'static str {
+    match kind {
+        PrizeKind::Cpt => "cpt",
+        PrizeKind::Token => "token",
+        PrizeKind::Sol => "sol",
+    }
+}
+
+// CPT prizes are paid in `reward_token_mint` and SOL ones have no mint
+fn prize_mint(kind: PrizeKind, mint: &Pubkey) -> Option<String> {
+    (kind == PrizeKind::Token).then(|| mint.to_string())
+}
+
+pub fn disqualification_reason(reason: DisqualificationReason) -> &'static str {
+    match reason {
+        DisqualificationReason::Spam => "spam",
+        DisqualificationReason::Abusive => "abusive",
+        DisqualificationReason::Plagiarism => "plagiarism",
+        DisqualificationReason::Ineligible => "ineligible",
+        DisqualificationReason::Duplicate => "duplicate",
+    }
+}
+
+pub fn payout_kind(kind: PayoutKind) -> &'static str {
+    match kind {
+        PayoutKind::Winner => "winner",
+        PayoutKind::RunnerUp => "runner_up",
+        PayoutKind::Platform => "platform",
+        PayoutKind::Creator => "creator",
+        PayoutKind::Voter => "voter",
+        PayoutKind::Judge => "judge",
+    }
+}
+
+/// Make sure a challenge row exists before an event refers to it.
+fn touch_challenge(db: &Transaction, challenge: &Pubkey, slot: u64) -> rusqlite::Result<()> {
+    db.execute(
+        "INSERT INTO challenges (address, updated_slot) VALUES (?1, ?2)
+         ON CONFLICT (address) DO UPDATE SET updated_slot = max(updated_slot, excluded.updated_slot)",
+        params![challenge.to_string(), slot],
+    )?;
+    Ok(())
+}
+
+fn insert_payout(
+    db: &Transaction,
+    tx: &IndexedTransaction,
+    idx: usize,
+    challenge: &Pubkey,
+    kind: &str,
+    recipient: &Pubkey,
+    amount: u64,
+) -> rusqlite::Result<()> {
+    db.execute(
+        "INSERT OR IGNORE INTO payouts (signature, idx, challenge, kind, recipient, amount, slot)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            tx.signature,
+            idx,
+            challenge.to_string(),
+            kind,
+            recipient.to_string(),
+            amount,
+            tx.slot
+        ],
+    )?;
+    Ok(())
+}
+
+fn apply_event(
+    db: &Transaction,
+    tx: &IndexedTransaction,
+    idx: usize,
+    event: &Event,
+) -> rusqlite::Result<()> {
+    let slot = tx.slot;
+    match event {
+        Event::ChallengeCreated(e) => {
+            db.execute(
+                "INSERT INTO challenges (
+                    address, creator, reward_token_mint, reward, participation_fee, voting_fee,
+                    max_participants, min_participants, min_voters, max_voters, submission_end,
+                    voting_end, commit_reveal, reveal_end, voting_mode, judging_mode,
+                    judge_weight_bps, judge_fee_bps, voter_badges, min_participant_reputation,
+                    min_voter_reputation, created_slot, updated_slot
+                 ) VALUES (
+                    ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18,
+                    ?19, ?20, ?21, ?22, ?22
+                 )
+                 ON CONFLICT (address) DO UPDATE SET
+                    creator = excluded.creator,
+                    reward_token_mint = excluded.reward_token_mint,
+                    reward = excluded.reward,
+                    participation_fee = excluded.participation_fee,
+                    voting_fee = excluded.voting_fee,
+                    max_participants = excluded.max_participants,
+                    min_participants = excluded.min_participants,
+                    min_voters = excluded.min_voters,
+                    max_voters = excluded.max_voters,
+                    submission_end = excluded.submission_end,
+                    voting_end = excluded.voting_end,
+                    commit_reveal = excluded.commit_reveal,
+                    reveal_end = excluded.reveal_end,
+                    voting_mode = excluded.voting_mode,
+                    judging_mode = excluded.judging_mode,
+                    judge_weight_bps = excluded.judge_weight_bps,
+                    judge_fee_bps = excluded.judge_fee_bps,
+                    voter_badges = excluded.voter_badges,
+                    min_participant_reputation = excluded.min_participant_reputation,
+                    min_voter_reputation = excluded.min_voter_reputation,
+                    created_slot = excluded.created_slot",
+                params![
+                    e.challenge.to_string(),
+                    e.creator.to_string(),
+                    e.reward_token_mint.to_string(),
+                    e.reward,
+                    e.participation_fee,
+                    e.voting_fee,
+                    e.max_participants,
+                    e.min_participants,
+                    e.min_voters,
+                    e.max_voters,
+                    e.submission_end,
+                    e.voting_end,
+                    e.commit_reveal,
+                    e.reveal_end,
+                    voting_mode(e.voting_mode),
+                    judging_mode(e.judging_mode),
+                    e.judge_weight_bps,
+                    e.judge_fee_bps,
+                    e.voter_badges,
+                    e.min_participant_reputation,
+                    e.min_voter_reputation,
+                    slot,
+                ],
+            )?;
+            for judge in &e.judges {
+                db.execute(
+                    "INSERT OR IGNORE INTO judges (challenge, judge) VALUES (?1, ?2)",
+                    params![e.challenge.to_string(), judge.to_string()],
+                )?;
+            }
+        }
+        Event::PrizeFunded(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "UPDATE challenges SET prize_kind = ?2, prize_mint = ?3 WHERE address = ?1",
+                params![
+                    e.challenge.to_string(),
+                    prize_kind(e.kind),
+                    prize_mint(e.kind, &e.mint)
+                ],
+            )?;
+        }
+        Event::ParticipantJoined(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "INSERT OR IGNORE INTO participants (challenge, wallet, fee, slot, signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    e.challenge.to_string(),
+                    e.participant.to_string(),
+                    e.fee,
+                    slot,
+                    tx.signature
+                ],
+            )?;
+        }
+        Event::ChallengeSponsored(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            // The event carries running totals, so replays land on the same row
+            db.execute(
+                "INSERT INTO sponsors (challenge, sponsor, amount, metadata_uri, slot, signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT (challenge, sponsor) DO UPDATE SET
+                    amount = excluded.amount,
+                    metadata_uri = excluded.metadata_uri,
+                    slot = excluded.slot,
+                    signature = excluded.signature
+                 WHERE excluded.slot >= sponsors.slot",
+                params![
+                    e.challenge.to_string(),
+                    e.sponsor.to_string(),
+                    e.total_sponsored,
+                    e.metadata_uri,
+                    slot,
+                    tx.signature
+                ],
+            )?;
+            db.execute(
+                "UPDATE challenges SET reward = max(reward, ?2) WHERE address = ?1",
+                params![e.challenge.to_string(), e.reward],
+            )?;
+        }
+        Event::FeeTrackerUpdated(e) => {
+            db.execute(
+                "INSERT OR IGNORE INTO fee_tracker_snapshots (
+                    signature, idx, slot, total_participation_fees, total_voting_fees,
+                    total_challenges
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    tx.signature,
+                    idx,
+                    slot,
+                    e.total_participation_fees,
+                    e.total_voting_fees,
+                    e.total_challenges
+                ],
+            )?;
+        }
+        Event::VideoSubmitted(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "INSERT OR IGNORE INTO submissions (
+                    challenge, submission, participant, fee, slot, signature
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    e.challenge.to_string(),
+                    e.submission.to_string(),
+                    e.participant.to_string(),
+                    e.fee,
+                    slot,
+                    tx.signature
+                ],
+            )?;
+        }
+        // Sealed votes only land in `votes` once revealed
+        Event::VoteCommitted(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "UPDATE challenges SET vote_commitments = vote_commitments + 1 WHERE address = ?1",
+                params![e.challenge.to_string()],
+            )?;
+        }
+        Event::VoteCast(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            let inserted = db.execute(
+                "INSERT OR IGNORE INTO votes (
+                    challenge, voter, submission, fee, weight, stake, slot, signature
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    e.challenge.to_string(),
+                    e.voter.to_string(),
+                    e.submission.to_string(),
+                    e.fee,
+                    e.weight,
+                    e.stake,
+                    slot,
+                    tx.signature
+                ],
+            )?;
+            if inserted > 0 {
+                db.execute(
+                    "UPDATE submissions SET votes = votes + ?3
+                     WHERE challenge = ?1 AND submission = ?2",
+                    params![e.challenge.to_string(), e.submission.to_string(), e.weight],
+                )?;
+                db.execute(
+                    "UPDATE challenges SET locked_stake = locked_stake + ?2 WHERE address = ?1",
+                    params![e.challenge.to_string(), e.stake],
+                )?;
+            }
+        }
+        // A judge can change their mind, the latest score wins
+        Event::SubmissionScored(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "INSERT INTO judge_scores (challenge, judge, submission, score, slot, signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT (challenge, judge, submission) DO UPDATE SET
+                    score = excluded.score, slot = excluded.slot, signature = excluded.signature
+                 WHERE excluded.slot >= judge_scores.slot",
+                params![
+                    e.challenge.to_string(),
+                    e.judge.to_string(),
+                    e.submission.to_string(),
+                    e.score,
+                    slot,
+                    tx.signature
+                ],
+            )?;
+        }
+        Event::ResultsProposed(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "UPDATE challenges SET status = 'proposed', dispute_end = ?2 WHERE address = ?1",
+                params![e.challenge.to_string(), e.dispute_end],
+            )?;
+        }
+        Event::DisputeRaised(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "INSERT OR IGNORE INTO disputes (
+                    challenge, submission, disputer, bond, slot, signature
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    e.challenge.to_string(),
+                    e.submission.to_string(),
+                    e.disputer.to_string(),
+                    e.bond,
+                    slot,
+                    tx.signature
+                ],
+            )?;
+        }
+        // Only one dispute per submission is open at a time
+        Event::DisputeResolved(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "UPDATE disputes SET status = ?3, resolved_by = ?4, resolved_slot = ?5
+                 WHERE challenge = ?1 AND submission = ?2 AND status = 'open'",
+                params![
+                    e.challenge.to_string(),
+                    e.submission.to_string(),
+                    if e.upheld { "upheld" } else { "rejected" },
+                    e.resolved_by.to_string(),
+                    slot
+                ],
+            )?;
+            if e.upheld {
+                db.execute(
+                    "UPDATE submissions SET disqualified = 1 WHERE challenge = ?1 AND submission = ?2",
+                    params![e.challenge.to_string(), e.submission.to_string()],
+                )?;
+            }
+        }
+        Event::DisputeExpired(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "UPDATE disputes SET status = 'expired', resolved_slot = ?3
+                 WHERE challenge = ?1 AND submission = ?2 AND status = 'open'",
+                params![e.challenge.to_string(), e.submission.to_string(), slot],
+            )?;
+        }
+        Event::SubmissionDisqualified(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "INSERT OR IGNORE INTO moderation (
+                    challenge, submission, submitter, moderator, reason, fee_refunded, slot,
+                    signature
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    e.challenge.to_string(),
+                    e.submission.to_string(),
+                    e.submitter.to_string(),
+                    e.moderator.to_string(),
+                    disqualification_reason(e.reason),
+                    e.fee_refunded,
+                    slot,
+                    tx.signature
+                ],
+            )?;
+            db.execute(
+                "UPDATE submissions SET disqualified = 1 WHERE challenge = ?1 AND submission = ?2",
+                params![e.challenge.to_string(), e.submission.to_string()],
+            )?;
+        }
+        Event::ChallengeFinalized(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "UPDATE challenges SET status = 'finalized', winning_submission = ?2,
+                    winning_votes = ?3, finalized_by = ?4
+                 WHERE address = ?1",
+                params![
+                    e.challenge.to_string(),
+                    e.winning_submission.to_string(),
+                    e.winning_votes,
+                    e.finalized_by.to_string()
+                ],
+            )?;
+        }
+        Event::PayoutIssued(e) => insert_payout(
+            db,
+            tx,
+            idx,
+            &e.challenge,
+            payout_kind(e.kind),
+            &e.recipient,
+            e.amount,
+        )?,
+        Event::CrankBountyPaid(e) => insert_payout(
+            db,
+            tx,
+            idx,
+            &e.challenge,
+            "crank_bounty",
+            &e.cranker,
+            e.amount,
+        )?,
+        Event::RefundIssued(e) => {
+            let amount = e
+                .participation_refund
+                .saturating_add(e.submission_refund)
+                .saturating_add(e.voting_refund);
+            insert_payout(db, tx, idx, &e.challenge, "refund", &e.wallet, amount)?;
+            db.execute(
+                "UPDATE participants SET refunded = 1 WHERE challenge = ?1 AND wallet = ?2",
+                params![e.challenge.to_string(), e.wallet.to_string()],
+            )?;
+        }
+        Event::SponsorRefunded(e) => {
+            insert_payout(
+                db,
+                tx,
+                idx,
+                &e.challenge,
+                "sponsor_refund",
+                &e.sponsor,
+                e.amount,
+            )?;
+            db.execute(
+                "UPDATE sponsors SET refunded = 1 WHERE challenge = ?1 AND sponsor = ?2",
+                params![e.challenge.to_string(), e.sponsor.to_string()],
+            )?;
+        }
+        Event::StakeWithdrawn(e) => {
+            insert_payout(db, tx, idx, &e.challenge, "stake", &e.voter, e.amount)?;
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "UPDATE challenges SET locked_stake = max(locked_stake - ?2, 0) WHERE address = ?1",
+                params![e.challenge.to_string(), e.amount],
+            )?;
+        }
+        Event::ChallengeFrozenChanged(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "UPDATE challenges SET is_frozen = ?2 WHERE address = ?1",
+                params![e.challenge.to_string(), e.is_frozen],
+            )?;
+        }
+        Event::SubmissionsClosed(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "UPDATE challenges SET submissions_closed = 1 WHERE address = ?1",
+                params![e.challenge.to_string()],
+            )?;
+        }
+        Event::ChallengeCancelled(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "UPDATE challenges SET status = 'cancelled' WHERE address = ?1",
+                params![e.challenge.to_string()],
+            )?;
+        }
+        Event::TrophyMinted(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "INSERT OR IGNORE INTO trophies (
+                    challenge, rank, submission, winner, mint, votes, slot, signature
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    e.challenge.to_string(),
+                    e.rank,
+                    e.submission.to_string(),
+                    e.winner.to_string(),
+                    e.mint.to_string(),
+                    e.votes,
+                    slot,
+                    tx.signature
+                ],
+            )?;
+        }
+        Event::BadgeMinted(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "INSERT OR IGNORE INTO badges (
+                    challenge, wallet, mint, participant, voter, slot, signature
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    e.challenge.to_string(),
+                    e.wallet.to_string(),
+                    e.mint.to_string(),
+                    e.participant,
+                    e.voter,
+                    slot,
+                    tx.signature
+                ],
+            )?;
+        }
+        Event::UserProfileCreated(e) => {
+            db.execute(
+                "INSERT OR IGNORE INTO user_profiles (wallet, ordinal, reputation, slot, signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    e.wallet.to_string(),
+                    e.unique_wallets,
+                    BASE_REPUTATION,
+                    slot,
+                    tx.signature
+                ],
+            )?;
+        }
+        Event::ReputationChanged(e) => {
+            db.execute(
+                "UPDATE user_profiles SET reputation = ?2 WHERE wallet = ?1",
+                params![e.wallet.to_string(), e.reputation],
+            )?;
+        }
+        Event::SeasonCreated(e) => {
+            db.execute(
+                "INSERT OR IGNORE INTO seasons (
+                    id, start_time, end_time, pool_share_bps, payout_places, slot, signature
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    e.season,
+                    e.start_time,
+                    e.end_time,
+                    e.pool_share_bps,
+                    e.payout_places,
+                    slot,
+                    tx.signature
+                ],
+            )?;
+        }
+        Event::SeasonPointsAwarded(e) => {
+            db.execute(
+                "INSERT INTO season_points (season, wallet, points) VALUES (?1, ?2, ?3)
+                 ON CONFLICT (season, wallet) DO UPDATE SET points = excluded.points",
+                params![e.season, e.wallet.to_string(), e.total],
+            )?;
+        }
+        Event::SeasonPoolFunded(e) => {
+            insert_payout(
+                db,
+                tx,
+                idx,
+                &e.challenge,
+                "season",
+                &pda::season(e.season).0,
+                e.amount,
+            )?;
+            db.execute(
+                "UPDATE seasons SET prize_pool = ?2 WHERE id = ?1",
+                params![e.season, e.prize_pool],
+            )?;
+        }
+        Event::SeasonPrizePaid(e) => {
+            db.execute(
+                "UPDATE season_points SET rank = ?3, prize = ?4 WHERE season = ?1 AND wallet = ?2",
+                params![e.season, e.wallet.to_string(), e.rank, e.amount],
+            )?;
+        }
+        Event::SeasonSettled(e) => {
+            db.execute(
+                "UPDATE seasons SET settled = 1 WHERE id = ?1",
+                params![e.season],
+            )?;
+        }
+        Event::TournamentCreated(e) => {
+            db.execute(
+                "INSERT OR IGNORE INTO tournaments (
+                    address, creator, round_count, advance_count, prize, roll_fees, slot,
+                    signature
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    e.tournament.to_string(),
+                    e.creator.to_string(),
+                    e.round_count,
+                    e.advance_count,
+                    e.prize,
+                    e.roll_fees,
+                    slot,
+                    tx.signature
+                ],
+            )?;
+        }
+        Event::TournamentRoundAdded(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "INSERT OR IGNORE INTO tournament_rounds (tournament, round, challenge)
+                 VALUES (?1, ?2, ?3)",
+                params![e.tournament.to_string(), e.round, e.challenge.to_string()],
+            )?;
+        }
+        Event::TournamentAdvanced(e) => {
+            db.execute(
+                "UPDATE tournaments SET current_round = ?2 WHERE address = ?1",
+                params![e.tournament.to_string(), e.round],
+            )?;
+            for wallet in &e.qualified {
+                db.execute(
+                    "INSERT OR IGNORE INTO tournament_qualifiers (tournament, round, wallet)
+                     VALUES (?1, ?2, ?3)",
+                    params![e.tournament.to_string(), e.round, wallet.to_string()],
+                )?;
+            }
+        }
+        Event::TournamentFinished(e) => {
+            db.execute(
+                "UPDATE tournaments SET status = ?2, recipient = ?3, payout = ?4
+                 WHERE address = ?1",
+                params![
+                    e.tournament.to_string(),
+                    if e.refunded { "refunded" } else { "finished" },
+                    e.recipient.to_string(),
+                    e.amount
+                ],
+            )?;
+        }
+        Event::TeamCreated(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "INSERT OR IGNORE INTO teams (address, challenge, captain, slot, signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    e.team.to_string(),
+                    e.challenge.to_string(),
+                    e.captain.to_string(),
+                    slot,
+                    tx.signature
+                ],
+            )?;
+            for (member, share_bps) in e.members.iter().zip(&e.shares_bps) {
+                db.execute(
+                    "INSERT OR IGNORE INTO team_members (team, member, share_bps)
+                     VALUES (?1, ?2, ?3)",
+                    params![e.team.to_string(), member.to_string(), share_bps],
+                )?;
+            }
+        }
+        Event::TeamFeePaid(e) => {
+            // The payment that completes the fee covers whoever was left
+            if e.entered {
+                db.execute(
+                    "UPDATE team_members SET paid = 1 WHERE team = ?1",
+                    params![e.team.to_string()],
+                )?;
+                db.execute(
+                    "UPDATE teams SET entered = 1 WHERE address = ?1",
+                    params![e.team.to_string()],
+                )?;
+            } else {
+                db.execute(
+                    "UPDATE team_members SET paid = 1 WHERE team = ?1 AND member = ?2",
+                    params![e.team.to_string(), e.payer.to_string()],
+                )?;
+            }
+        }
+        Event::TeamFeeRefunded(e) => {
+            db.execute(
+                "UPDATE team_members SET paid = 0 WHERE team = ?1 AND member = ?2",
+                params![e.team.to_string(), e.member.to_string()],
+            )?;
+        }
+        Event::TeamSharePaid(e) => {
+            insert_payout(db, tx, idx, &e.challenge, "team_share", &e.member, e.amount)?;
+        }
+        Event::DuelOpened(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            // An open duel has no opponent until someone matches the stake
+            let opponent = (e.opponent != Pubkey::default()).then(|| e.opponent.to_string());
+            db.execute(
+                "INSERT OR IGNORE INTO duels
+                    (challenge, creator, opponent, stake, accept_by, pot, slot, signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?4, ?6, ?7)",
+                params![
+                    e.challenge.to_string(),
+                    e.creator.to_string(),
+                    opponent,
+                    e.stake,
+                    e.accept_by,
+                    slot,
+                    tx.signature
+                ],
+            )?;
+            db.execute(
+                "UPDATE challenges SET reward = ?2, participation_fee = ?2,
+                    min_participants = 2, max_participants = 2
+                 WHERE address = ?1",
+                params![e.challenge.to_string(), e.stake],
+            )?;
+        }
+        Event::DuelAccepted(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "UPDATE duels SET opponent = ?2, pot = ?3, matched = 1 WHERE challenge = ?1",
+                params![e.challenge.to_string(), e.opponent.to_string(), e.pot],
+            )?;
+            db.execute(
+                "UPDATE challenges SET reward = max(reward, ?2) WHERE address = ?1",
+                params![e.challenge.to_string(), e.pot],
+            )?;
+        }
+        Event::ChallengeClosed(e) => {
+            touch_challenge(db, &e.challenge, slot)?;
+            db.execute(
+                "UPDATE challenges SET status = 'closed', challenge_treasury = 0,
+                    voting_treasury = 0
+                 WHERE address = ?1",
+                params![e.challenge.to_string()],
+            )?;
+        }
+        // Admin and audit events don't map onto any table
+        Event::ChallengeAuditDiscrepancy(_)
+        | Event::CrankBountyChanged(_)
+        | Event::DisputeConfigChanged(_)
+        | Event::ReputationConfigChanged(_)
+        | Event::TrophyCollectionCreated(_)
+        | Event::WalletMilestoneReached(_) => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,868 @@
+use coinpetitive_client::event_log::Event;
+use coinpetitive_client::events::*;
+use coinpetitive_client::{rpc, Challenge, JudgingMode, PrizeKind, VotingMode};
+use cpt_indexer::indexer::Indexer;
+use cpt_indexer::source::{IndexedTransaction, SignatureInfo, Source};
+use cpt_indexer::store::Store;
+use solana_sdk::pubkey::Pubkey;
+use std::cell::RefCell;
+
+/// A transaction and the events it logs, built from the test's keys.
+type Scripted = (SignatureInfo, fn(&Keys) -> Vec<Event>);
+
+/// Serves transactions from memory, oldest first, in pages of two so paging
+/// is exercised.
+struct MemorySource {
+    transactions: Vec<Scripted>,
+    keys: Keys,
+    fetched: RefCell<Vec<String>>,
+}
+
+#[derive(Clone, Copy)]
+struct Keys {
+    challenge: Pubkey,
+    creator: Pubkey,
+    alice: Pubkey,
+    bob: Pubkey,
+    voter: Pubkey,
+    video: Pubkey,
+}
+
+impl Source for MemorySource {
+    fn signatures(
+        &self,
+        before: Option<&str>,
+        until: Option<&str>,
+    ) -> rpc::Result<Vec<SignatureInfo>> {
+        let newest_first = self.transactions.iter().rev().map(|(info, _)| info);
+        let mut page: Vec<SignatureInfo> = newest_first
+            .skip_while(|info| before.is_some_and(|b| info.signature != b))
+            .skip(usize::from(before.is_some()))
+            .take_while(|info| until != Some(info.signature.as_str()))
+            .cloned()
+            .collect();
+        page.truncate(2);
+        Ok(page)
+    }
+
+    fn transaction(&self, info: &SignatureInfo) -> rpc::Result<IndexedTransaction> {
+        self.fetched.borrow_mut().push(info.signature.clone());
+        let (_, events) = self
+            .transactions
+            .iter()
+            .find(|(i, _)| i == info)
+            .expect("known signature");
+        Ok(IndexedTransaction {
+            signature: info.signature.clone(),
+            slot: info.slot,
+            block_time: Some(1_700_000_000 + info.slot as i64),
+            failed: false,
+            events: events(&self.keys),
+        })
+    }
+
+    fn challenges(&self) -> rpc::Result<Vec<(Pubkey, Challenge)>> {
+        Ok(Vec::new())
+    }
+}
+
+fn info(signature: &str, slot: u64, failed: bool) -> SignatureInfo {
+    SignatureInfo {
+        signature: signature.to_string(),
+        slot,
+        failed,
+    }
+}
+
+fn lifecycle() -> MemorySource {
+    let keys = Keys {
+        challenge: Pubkey::new_unique(),
+        creator: Pubkey::new_unique(),
+        alice: Pubkey::new_unique(),
+        bob: Pubkey::new_unique(),
+        voter: Pubkey::new_unique(),
+        video: Pubkey::new_unique(),
+    };
+    let transactions: Vec<Scripted> = vec![
+        (info("create", 10, false), |k| {
+            vec![Event::ChallengeCreated(ChallengeCreated {
+                challenge: k.challenge,
+                creator: k.creator,
+                reward_token_mint: Pubkey::new_unique(),
+                reward: 1_000,
+                participation_fee: 10,
+                voting_fee: 5,
+                max_participants: 50,
+                submission_end: 100,
+                voting_end: 200,
+                min_participants: 1,
+                min_voters: 1,
+                max_voters: 50,
+                commit_reveal: false,
+                reveal_end: 200,
+                voting_mode: VotingMode::Flat,
+                judging_mode: JudgingMode::PublicVote,
+                judges: Vec::new(),
+                judge_weight_bps: 0,
+                judge_fee_bps: 0,
+                voter_badges: true,
+                min_participant_reputation: 0,
+                min_voter_reputation: 0,
+            })]
+        }),
+        (info("join-alice", 11, false), |k| {
+            vec![
+                Event::ParticipantJoined(ParticipantJoined {
+                    challenge: k.challenge,
+                    participant: k.alice,
+                    fee: 10,
+                }),
+                Event::FeeTrackerUpdated(FeeTrackerUpdated {
+                    total_participation_fees: 10,
+                    total_voting_fees: 0,
+                    total_challenges: 0,
+                }),
+            ]
+        }),
+        // Rejected on-chain; nothing it logged may be applied
+        (info("join-bob-failed", 12, true), |_| Vec::new()),
+        (info("submit", 13, false), |k| {
+            vec![Event::VideoSubmitted(VideoSubmitted {
+                challenge: k.challenge,
+                submission: k.video,
+                participant: k.alice,
+                fee: 100,
+            })]
+        }),
+        (info("vote", 14, false), |k| {
+            vec![Event::VoteCast(VoteCast {
+                challenge: k.challenge,
+                voter: k.voter,
+                submission: k.video,
+                fee: 5,
+                weight: 1,
+                stake: 0,
+            })]
+        }),
+        (info("finalize", 20, false), |k| {
+            vec![
+                Event::ChallengeFinalized(ChallengeFinalized {
+                    challenge: k.challenge,
+                    winning_submission: k.video,
+                    winning_votes: 1,
+                    finalized_by: k.bob,
+                }),
+                Event::PayoutIssued(PayoutIssued {
+                    challenge: k.challenge,
+                    kind: PayoutKind::Winner,
+                    recipient: k.alice,
+                    amount: 700,
+                }),
+                Event::CrankBountyPaid(CrankBountyPaid {
+                    challenge: k.challenge,
+                    cranker: k.bob,
+                    amount: 3,
+                }),
+            ]
+        }),
+    ];
+    MemorySource {
+        transactions,
+        keys,
+        fetched: RefCell::new(Vec::new()),
+    }
+}
+
+fn count(store: &Store, table: &str) -> i64 {
+    store
+        .connection()
+        .query_row(&format!("SELECT count(*) FROM {table}"), [], |row| {
+            row.get(0)
+        })
+        .unwrap()
+}
+
+#[test]
+fn backfill_materialises_the_challenge_lifecycle() {
+    let source = lifecycle();
+    let keys = source.keys;
+    let mut indexer = Indexer::new(source, Store::open_in_memory().unwrap());
+
+    let report = indexer.backfill(None).unwrap();
+    assert_eq!(report.seen, 6);
+    assert_eq!(report.indexed, 6);
+
+    let store = indexer.store();
+    let (status, winner, reward): (String, String, u64) = store
+        .connection()
+        .query_row(
+            "SELECT status, winning_submission, reward FROM challenges WHERE address = ?1",
+            [keys.challenge.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(status, "finalized");
+    assert_eq!(winner, keys.video.to_string());
+    assert_eq!(reward, 1_000);
+
+    assert_eq!(count(store, "participants"), 1);
+    assert_eq!(count(store, "fee_tracker_snapshots"), 1);
+    let votes: i64 = store
+        .connection()
+        .query_row("SELECT votes FROM submissions", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(votes, 1);
+
+    let payouts: Vec<(String, u64)> = store
+        .connection()
+        .prepare("SELECT kind, amount FROM payouts ORDER BY idx")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        payouts,
+        vec![("winner".to_string(), 700), ("crank_bounty".to_string(), 3)]
+    );
+
+    let cursor = store.cursor().unwrap().unwrap();
+    assert_eq!((cursor.slot, cursor.signature.as_str()), (20, "finalize"));
+}
+
+#[test]
+fn failed_transactions_are_recorded_but_never_fetched() {
+    let mut indexer = Indexer::new(lifecycle(), Store::open_in_memory().unwrap());
+    indexer.backfill(None).unwrap();
+
+    assert_eq!(count(indexer.store(), "transactions"), 6);
+    let failed: i64 = indexer
+        .store()
+        .connection()
+        .query_row(
+            "SELECT count(*) FROM transactions WHERE failed",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(failed, 1);
+
+    let (source, _) = indexer.into_parts();
+    assert!(!source
+        .fetched
+        .borrow()
+        .iter()
+        .any(|s| s == "join-bob-failed"));
+}
+
+#[test]
+fn replaying_from_a_slot_is_idempotent() {
+    let source = lifecycle();
+    let mut indexer = Indexer::new(source, Store::open_in_memory().unwrap());
+    indexer.backfill(None).unwrap();
+
+    let report = indexer.backfill(Some(0)).unwrap();
+    assert_eq!(report.indexed, 0);
+    assert_eq!(report.skipped, 6);
+
+    // Votes are counted once, not once per replay
+    let votes: i64 = indexer
+        .store()
+        .connection()
+        .query_row("SELECT votes FROM submissions", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(votes, 1);
+    assert_eq!(count(indexer.store(), "payouts"), 2);
+}
+
+#[test]
+fn backfill_from_slot_skips_older_history() {
+    let mut indexer = Indexer::new(lifecycle(), Store::open_in_memory().unwrap());
+
+    let report = indexer.backfill(Some(13)).unwrap();
+    assert_eq!(report.seen, 3);
+    assert_eq!(report.indexed, 3);
+
+    // Events for a challenge whose creation wasn't indexed still land
+    assert_eq!(count(indexer.store(), "challenges"), 1);
+    assert_eq!(count(indexer.store(), "participants"), 0);
+    assert_eq!(count(indexer.store(), "submissions"), 1);
+}
+
+#[test]
+fn catching_up_only_reads_new_transactions() {
+    let mut source = lifecycle();
+    let finalize = source.transactions.pop().unwrap();
+    let mut indexer = Indexer::new(source, Store::open_in_memory().unwrap());
+    indexer.backfill(None).unwrap();
+
+    let mut indexer = lifecycle_with(indexer, finalize);
+    let report = indexer.backfill(None).unwrap();
+    assert_eq!((report.seen, report.indexed), (1, 1));
+
+    let (source, _) = indexer.into_parts();
+    assert_eq!(*source.fetched.borrow(), vec!["finalize".to_string()]);
+}
+
+/// Rebuild an indexer over the same store with one more transaction on chain,
+/// as if it had just landed.
+fn lifecycle_with(indexer: Indexer<MemorySource>, next: Scripted) -> Indexer<MemorySource> {
+    let (mut source, store) = indexer.into_parts();
+    source.transactions.push(next);
+    source.fetched.borrow_mut().clear();
+    Indexer::new(source, store)
+}
+
+#[test]
+fn disputes_are_tracked_until_resolved() {
+    let mut source = lifecycle();
+    source.transactions.pop();
+    let mut indexer = Indexer::new(source, Store::open_in_memory().unwrap());
+    indexer.backfill(None).unwrap();
+
+    let propose: Scripted = (info("propose", 21, false), |k| {
+        vec![
+            Event::ResultsProposed(ResultsProposed {
+                challenge: k.challenge,
+                winning_submission: k.video,
+                runner_up: None,
+                dispute_end: 300,
+                proposed_by: k.bob,
+            }),
+            Event::DisputeRaised(DisputeRaised {
+                challenge: k.challenge,
+                submission: k.video,
+                disputer: k.bob,
+                bond: 50,
+            }),
+        ]
+    });
+    let mut indexer = lifecycle_with(indexer, propose);
+    indexer.backfill(None).unwrap();
+
+    let challenge = |indexer: &Indexer<MemorySource>| -> (String, i64) {
+        indexer
+            .store()
+            .connection()
+            .query_row("SELECT status, dispute_end FROM challenges", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap()
+    };
+    assert_eq!(challenge(&indexer), ("proposed".to_string(), 300));
+
+    let resolve: Scripted = (info("resolve", 22, false), |k| {
+        vec![Event::DisputeResolved(DisputeResolved {
+            challenge: k.challenge,
+            submission: k.video,
+            disputer: k.bob,
+            upheld: true,
+            resolved_by: k.creator,
+        })]
+    });
+    let mut indexer = lifecycle_with(indexer, resolve);
+    indexer.backfill(None).unwrap();
+
+    let store = indexer.store();
+    let (status, bond): (String, u64) = store
+        .connection()
+        .query_row("SELECT status, bond FROM disputes", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!((status.as_str(), bond), ("upheld", 50));
+    let disqualified: bool = store
+        .connection()
+        .query_row("SELECT disqualified FROM submissions", [], |row| row.get(0))
+        .unwrap();
+    assert!(disqualified);
+}
+
+#[test]
+fn sponsorships_raise_the_reward_and_are_refunded_on_cancel() {
+    let mut source = lifecycle();
+    source.transactions.pop();
+    let indexer = Indexer::new(source, Store::open_in_memory().unwrap());
+
+    let sponsor: Scripted = (info("sponsor", 15, false), |k| {
+        vec![Event::ChallengeSponsored(ChallengeSponsored {
+            challenge: k.challenge,
+            sponsor: k.bob,
+            amount: 500,
+            total_sponsored: 500,
+            reward: 1_500,
+            metadata_uri: Some("https://example.com/brand.json".to_string()),
+        })]
+    });
+    let mut indexer = lifecycle_with(indexer, sponsor);
+    indexer.backfill(None).unwrap();
+
+    let reward: u64 = indexer
+        .store()
+        .connection()
+        .query_row("SELECT reward FROM challenges", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(reward, 1_500);
+
+    let cancel: Scripted = (info("cancel", 21, false), |k| {
+        vec![
+            Event::ChallengeCancelled(ChallengeCancelled {
+                challenge: k.challenge,
+                authority: k.creator,
+            }),
+            Event::SponsorRefunded(SponsorRefunded {
+                challenge: k.challenge,
+                sponsor: k.bob,
+                amount: 500,
+            }),
+        ]
+    });
+    let mut indexer = lifecycle_with(indexer, cancel);
+    indexer.backfill(None).unwrap();
+
+    let store = indexer.store();
+    let (amount, uri, refunded): (u64, String, bool) = store
+        .connection()
+        .query_row(
+            "SELECT amount, metadata_uri, refunded FROM sponsors",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(amount, 500);
+    assert_eq!(uri, "https://example.com/brand.json");
+    assert!(refunded);
+    let refund: (String, u64) = store
+        .connection()
+        .query_row("SELECT kind, amount FROM payouts", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(refund, ("sponsor_refund".to_string(), 500));
+}
+
+#[test]
+fn funded_prizes_record_their_kind_and_mint() {
+    let mut source = lifecycle();
+    source.transactions.truncate(1);
+    let indexer = Indexer::new(source, Store::open_in_memory().unwrap());
+
+    let fund: Scripted = (info("fund", 12, false), |k| {
+        vec![Event::PrizeFunded(PrizeFunded {
+            challenge: k.challenge,
+            kind: PrizeKind::Token,
+            mint: k.video,
+            amount: 1_000,
+        })]
+    });
+    let mut indexer = lifecycle_with(indexer, fund);
+    indexer.backfill(None).unwrap();
+
+    let (source, store) = indexer.into_parts();
+    let (kind, mint): (String, Option<String>) = store
+        .connection()
+        .query_row("SELECT prize_kind, prize_mint FROM challenges", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(kind, "token");
+    assert_eq!(mint, Some(source.keys.video.to_string()));
+}
+
+#[test]
+fn trophies_are_recorded_once_per_place() {
+    let indexer = Indexer::new(lifecycle(), Store::open_in_memory().unwrap());
+
+    let trophy: Scripted = (info("trophy", 40, false), |k| {
+        vec![Event::TrophyMinted(TrophyMinted {
+            challenge: k.challenge,
+            submission: k.video,
+            winner: k.alice,
+            mint: k.bob,
+            rank: 1,
+            votes: 3,
+        })]
+    });
+    let mut indexer = lifecycle_with(indexer, trophy);
+    indexer.backfill(None).unwrap();
+
+    let (source, store) = indexer.into_parts();
+    let (rank, winner, votes): (u8, String, u64) = store
+        .connection()
+        .query_row("SELECT rank, winner, votes FROM trophies", [], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })
+        .unwrap();
+    assert_eq!((rank, votes), (1, 3));
+    assert_eq!(winner, source.keys.alice.to_string());
+}
+
+#[test]
+fn badges_record_how_each_wallet_took_part() {
+    let indexer = Indexer::new(lifecycle(), Store::open_in_memory().unwrap());
+
+    let badges: Scripted = (info("badges", 40, false), |k| {
+        let badge = |wallet, participant, voter| {
+            Event::BadgeMinted(BadgeMinted {
+                challenge: k.challenge,
+                wallet,
+                mint: k.video,
+                participant,
+                voter,
+            })
+        };
+        vec![badge(k.alice, true, false), badge(k.voter, false, true)]
+    });
+    let mut indexer = lifecycle_with(indexer, badges);
+    indexer.backfill(None).unwrap();
+
+    let (source, store) = indexer.into_parts();
+    let voter_badges: bool = store
+        .connection()
+        .query_row("SELECT voter_badges FROM challenges", [], |row| row.get(0))
+        .unwrap();
+    assert!(voter_badges);
+    let (participant, voter): (bool, bool) = store
+        .connection()
+        .query_row(
+            "SELECT participant, voter FROM badges WHERE wallet = ?1",
+            [source.keys.voter.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!((participant, voter), (false, true));
+}
+
+#[test]
+fn user_profiles_keep_the_order_wallets_arrived_in() {
+    let indexer = Indexer::new(lifecycle(), Store::open_in_memory().unwrap());
+
+    let profiles: Scripted = (info("profiles", 40, false), |k| {
+        vec![
+            Event::UserProfileCreated(UserProfileCreated {
+                wallet: k.alice,
+                unique_wallets: 1,
+            }),
+            Event::UserProfileCreated(UserProfileCreated {
+                wallet: k.bob,
+                unique_wallets: 2,
+            }),
+        ]
+    });
+    let mut indexer = lifecycle_with(indexer, profiles);
+    indexer.backfill(None).unwrap();
+
+    let (source, store) = indexer.into_parts();
+    let ordinal: u64 = store
+        .connection()
+        .query_row(
+            "SELECT ordinal FROM user_profiles WHERE wallet = ?1",
+            [source.keys.bob.to_string()],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(ordinal, 2);
+}
+
+#[test]
+fn reputation_follows_the_latest_change() {
+    let indexer = Indexer::new(lifecycle(), Store::open_in_memory().unwrap());
+
+    let profiles: Scripted = (info("reputation", 40, false), |k| {
+        vec![
+            Event::UserProfileCreated(UserProfileCreated {
+                wallet: k.alice,
+                unique_wallets: 1,
+            }),
+            Event::ReputationChanged(ReputationChanged {
+                wallet: k.alice,
+                delta: 50,
+                reputation: 150,
+            }),
+            Event::ReputationChanged(ReputationChanged {
+                wallet: k.alice,
+                delta: -20,
+                reputation: 130,
+            }),
+        ]
+    });
+    let mut indexer = lifecycle_with(indexer, profiles);
+    indexer.backfill(None).unwrap();
+
+    let (source, store) = indexer.into_parts();
+    let reputation: u64 = store
+        .connection()
+        .query_row(
+            "SELECT reputation FROM user_profiles WHERE wallet = ?1",
+            [source.keys.alice.to_string()],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(reputation, 130);
+}
+
+#[test]
+fn seasons_track_points_pool_and_prizes() {
+    let indexer = Indexer::new(lifecycle(), Store::open_in_memory().unwrap());
+
+    let season: Scripted = (info("season", 40, false), |k| {
+        vec![
+            Event::SeasonCreated(SeasonCreated {
+                season: 1,
+                start_time: 0,
+                end_time: 100,
+                pool_share_bps: 5_000,
+                payout_places: 2,
+            }),
+            Event::SeasonPointsAwarded(SeasonPointsAwarded {
+                season: 1,
+                wallet: k.alice,
+                points: 100,
+                total: 100,
+            }),
+            Event::SeasonPointsAwarded(SeasonPointsAwarded {
+                season: 1,
+                wallet: k.alice,
+                points: 10,
+                total: 110,
+            }),
+            Event::SeasonPoolFunded(SeasonPoolFunded {
+                season: 1,
+                challenge: k.challenge,
+                amount: 7,
+                prize_pool: 7,
+            }),
+            Event::SeasonPrizePaid(SeasonPrizePaid {
+                season: 1,
+                wallet: k.alice,
+                rank: 1,
+                points: 110,
+                amount: 7,
+            }),
+            Event::SeasonSettled(SeasonSettled {
+                season: 1,
+                prize_pool: 7,
+                places_paid: 1,
+            }),
+        ]
+    });
+    let mut indexer = lifecycle_with(indexer, season);
+    indexer.backfill(None).unwrap();
+
+    let (source, store) = indexer.into_parts();
+    let (points, rank, prize): (u64, u8, u64) = store
+        .connection()
+        .query_row(
+            "SELECT points, rank, prize FROM season_points WHERE season = 1 AND wallet = ?1",
+            [source.keys.alice.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!((points, rank, prize), (110, 1, 7));
+    let (prize_pool, settled): (u64, bool) = store
+        .connection()
+        .query_row(
+            "SELECT prize_pool, settled FROM seasons WHERE id = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!((prize_pool, settled), (7, true));
+    let season_payout: u64 = store
+        .connection()
+        .query_row(
+            "SELECT amount FROM payouts WHERE kind = 'season'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(season_payout, 7);
+}
+
+#[test]
+fn tournaments_track_rounds_qualifiers_and_the_prize() {
+    use coinpetitive_client::pda;
+
+    let indexer = Indexer::new(lifecycle(), Store::open_in_memory().unwrap());
+
+    let tournament: Scripted = (info("tournament", 40, false), |k| {
+        let tournament = pda::tournament(&k.creator, 1).0;
+        vec![
+            Event::TournamentCreated(TournamentCreated {
+                tournament,
+                creator: k.creator,
+                round_count: 2,
+                advance_count: 1,
+                prize: 500,
+                roll_fees: true,
+            }),
+            Event::TournamentRoundAdded(TournamentRoundAdded {
+                tournament,
+                challenge: k.challenge,
+                round: 0,
+            }),
+            Event::TournamentAdvanced(TournamentAdvanced {
+                tournament,
+                round: 1,
+                qualified: vec![k.alice],
+            }),
+            Event::TournamentFinished(TournamentFinished {
+                tournament,
+                recipient: k.alice,
+                amount: 520,
+                refunded: false,
+            }),
+        ]
+    });
+    let mut indexer = lifecycle_with(indexer, tournament);
+    indexer.backfill(None).unwrap();
+
+    let (source, store) = indexer.into_parts();
+    let address = pda::tournament(&source.keys.creator, 1).0.to_string();
+    let (current_round, status, recipient, payout): (u8, String, String, u64) = store
+        .connection()
+        .query_row(
+            "SELECT current_round, status, recipient, payout FROM tournaments WHERE address = ?1",
+            [&address],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(
+        (current_round, status.as_str(), payout),
+        (1, "finished", 520)
+    );
+    assert_eq!(recipient, source.keys.alice.to_string());
+    let round: String = store
+        .connection()
+        .query_row(
+            "SELECT challenge FROM tournament_rounds WHERE tournament = ?1 AND round = 0",
+            [&address],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(round, source.keys.challenge.to_string());
+    let qualified: String = store
+        .connection()
+        .query_row(
+            "SELECT wallet FROM tournament_qualifiers WHERE tournament = ?1 AND round = 1",
+            [&address],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(qualified, source.keys.alice.to_string());
+}
+
+#[test]
+fn teams_track_members_fees_and_shares() {
+    let indexer = Indexer::new(lifecycle(), Store::open_in_memory().unwrap());
+
+    let team: Scripted = (info("team", 40, false), |k| {
+        vec![
+            Event::TeamCreated(TeamCreated {
+                team: k.video,
+                challenge: k.challenge,
+                captain: k.alice,
+                members: vec![k.alice, k.bob],
+                shares_bps: vec![6_000, 4_000],
+            }),
+            Event::TeamFeePaid(TeamFeePaid {
+                team: k.video,
+                payer: k.bob,
+                members: 1,
+                amount: 4,
+                entered: false,
+            }),
+            Event::TeamFeePaid(TeamFeePaid {
+                team: k.video,
+                payer: k.alice,
+                members: 1,
+                amount: 6,
+                entered: true,
+            }),
+            Event::TeamSharePaid(TeamSharePaid {
+                team: k.video,
+                challenge: k.challenge,
+                member: k.bob,
+                mint: Pubkey::default(),
+                amount: 40,
+            }),
+        ]
+    });
+    let mut indexer = lifecycle_with(indexer, team);
+    indexer.backfill(None).unwrap();
+
+    let (source, store) = indexer.into_parts();
+    let team = source.keys.video.to_string();
+    let entered: bool = store
+        .connection()
+        .query_row(
+            "SELECT entered FROM teams WHERE address = ?1",
+            [&team],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert!(entered);
+    let (members, paid): (u32, u32) = store
+        .connection()
+        .query_row(
+            "SELECT count(*), sum(paid) FROM team_members WHERE team = ?1",
+            [&team],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!((members, paid), (2, 2));
+    let share: u64 = store
+        .connection()
+        .query_row(
+            "SELECT amount FROM payouts WHERE kind = 'team_share' AND recipient = ?1",
+            [source.keys.bob.to_string()],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(share, 40);
+}
+
+#[test]
+fn duels_record_the_opponent_once_the_stake_is_matched() {
+    let indexer = Indexer::new(lifecycle(), Store::open_in_memory().unwrap());
+
+    let duel: Scripted = (info("duel", 40, false), |k| {
+        vec![
+            Event::DuelOpened(DuelOpened {
+                challenge: k.challenge,
+                creator: k.creator,
+                opponent: Pubkey::default(),
+                stake: 500,
+                accept_by: 90,
+            }),
+            Event::DuelAccepted(DuelAccepted {
+                challenge: k.challenge,
+                opponent: k.bob,
+                pot: 1_000,
+            }),
+        ]
+    });
+    let mut indexer = lifecycle_with(indexer, duel);
+    indexer.backfill(None).unwrap();
+
+    let (source, store) = indexer.into_parts();
+    let (opponent, pot, matched): (String, u64, bool) = store
+        .connection()
+        .query_row(
+            "SELECT opponent, pot, matched FROM duels WHERE challenge = ?1",
+            [source.keys.challenge.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(opponent, source.keys.bob.to_string());
+    assert_eq!((pot, matched), (1_000, true));
+    let (reward, stake): (u64, u64) = store
+        .connection()
+        .query_row(
+            "SELECT reward, participation_fee FROM challenges WHERE address = ?1",
+            [source.keys.challenge.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!((reward, stake), (1_000, 500));
+}
@@ -0,0 +1,51 @@
+//! Runs against a live `solana-test-validator` with the program deployed:
+//!
+//! ```text
+//! solana-test-validator --reset \
+//!     --bpf-program 5NLxDYs6Br5H8D3F3eq4JjGa8wX292onmvwKbLC3wEbU target/deploy/coinpetitive.so
+//! cargo test -p cpt-indexer --test validator -- --ignored
+//! ```
+
+use clap::Parser;
+use coinpetitive_client::instructions;
+use cpt_indexer::config::Config;
+use cpt_indexer::indexer::Indexer;
+use cpt_indexer::source::RpcSource;
+use cpt_indexer::store::Store;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+#[test]
+#[ignore = "needs solana-test-validator with the program deployed"]
+fn indexes_a_local_validator_twice() {
+    let config = Config::parse_from(["cpt-indexer", "--db", ":memory:"]);
+    let rpc = RpcClient::new_with_commitment(config.url.clone(), CommitmentConfig::confirmed());
+
+    let signer = Keypair::new();
+    let signature = rpc
+        .request_airdrop(&signer.pubkey(), 1_000_000_000)
+        .expect("airdrop");
+    rpc.poll_for_signature(&signature).expect("airdrop landed");
+
+    // Touch the program so there is at least one transaction to index; it
+    // doesn't matter whether the trackers already existed
+    let tx = Transaction::new_signed_with_payer(
+        &[instructions::initialize_fee_tracker(&signer.pubkey())],
+        Some(&signer.pubkey()),
+        &[&signer],
+        rpc.get_latest_blockhash().unwrap(),
+    );
+    let _ = rpc.send_and_confirm_transaction(&tx);
+
+    let slot = rpc.get_slot().unwrap();
+    let mut indexer = Indexer::new(RpcSource::new(rpc), Store::open_in_memory().unwrap());
+    let first = indexer.backfill(Some(0)).expect("backfill");
+    assert!(first.seen > 0);
+    indexer.sync_challenges(slot).expect("sync");
+
+    let replay = indexer.backfill(Some(0)).expect("replay");
+    assert_eq!(replay.indexed, 0);
+    assert_eq!(replay.skipped, first.seen);
+}
@@ -0,0 +1,70 @@
+//! The keeper's view of the cluster. `RpcChain` talks to a real RPC node;
+//! tests drive the keeper with an in-memory implementation.
+
+use coinpetitive_client::{pda, rpc, Challenge, ChallengeTracker};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+
+pub trait Chain {
+    /// Wallet that signs and pays for every crank.
+    fn keeper(&self) -> Pubkey;
+
+    /// Cluster time, which is what the program compares deadlines against.
+    fn now(&self) -> rpc::Result<i64>;
+
+    fn challenges(&self) -> rpc::Result<Vec<(Pubkey, Challenge)>>;
+
+    /// Its authority is the platform admin, who must receive platform fees.
+    fn challenge_tracker(&self) -> rpc::Result<ChallengeTracker>;
+
+    /// Season running at `now`, which settlements have to credit.
+    fn running_season(&self, now: i64) -> rpc::Result<Option<u64>>;
+
+    /// Whether `wallet` has a profile for settlements to credit.
+    fn has_profile(&self, wallet: &Pubkey) -> rpc::Result<bool>;
+
+    fn send(&self, instruction: Instruction) -> rpc::Result<Signature>;
+}
+
+pub struct RpcChain {
+    rpc: RpcClient,
+    signer: Keypair,
+}
+
+impl RpcChain {
+    pub fn new(rpc: RpcClient, signer: Keypair) -> Self {
+        Self { rpc, signer }
+    }
+}
+
+impl Chain for RpcChain {
+    fn keeper(&self) -> Pubkey {
+        self.signer.pubkey()
+    }
+
+    fn now(&self) -> rpc::Result<i64> {
+        rpc::cluster_time(&self.rpc)
+    }
+
+    fn challenges(&self) -> rpc::Result<Vec<(Pubkey, Challenge)>> {
+        rpc::fetch_challenges(&self.rpc)
+    }
+
+    fn challenge_tracker(&self) -> rpc::Result<ChallengeTracker> {
+        rpc::fetch(&self.rpc, &pda::challenge_tracker().0)
+    }
+
+    fn running_season(&self, now: i64) -> rpc::Result<Option<u64>> {
+        rpc::running_season(&self.rpc, now)
+    }
+
+    fn has_profile(&self, wallet: &Pubkey) -> rpc::Result<bool> {
+        rpc::has_profile(&self.rpc, wallet)
+    }
+
+    fn send(&self, instruction: Instruction) -> rpc::Result<Signature> {
+        rpc::send(&self.rpc, &[instruction], &self.signer, &[])
+    }
+}
@@ -0,0 +1,65 @@
+//! Command line and environment configuration.
+
+use clap::Parser;
+use solana_sdk::pubkey::Pubkey;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Clone, Debug, Parser)]
+#[command(
+    name = "cpt-keeper",
+    version,
+    about = "Crank Coinpetitive challenges as their deadlines pass"
+)]
+pub struct Config {
+    /// RPC endpoint, defaults to a local validator
+    #[arg(
+        long,
+        short = 'u',
+        env = "CPT_RPC_URL",
+        default_value = "http://127.0.0.1:8899"
+    )]
+    pub url: String,
+
+    /// Keypair that signs and pays for every crank
+    #[arg(long, short = 'k', env = "CPT_KEYPAIR")]
+    pub keypair: Option<PathBuf>,
+
+    /// Token account receiving platform fees, defaults to the platform
+    /// admin's, which is the only owner the program accepts
+    #[arg(long, env = "CPT_PLATFORM_TOKEN_ACCOUNT")]
+    pub platform_token_account: Option<Pubkey>,
+
+    /// Seconds between scans of all challenges
+    #[arg(long, env = "CPT_KEEPER_INTERVAL", default_value_t = 30)]
+    pub interval_secs: u64,
+
+    /// Attempts per crank when the RPC node fails, not counting the first
+    #[arg(long, default_value_t = 3)]
+    pub max_retries: u32,
+
+    /// Delay before the first retry, doubled on each further attempt
+    #[arg(long, default_value_t = 500)]
+    pub retry_backoff_ms: u64,
+
+    /// Run a single scan and exit
+    #[arg(long)]
+    pub once: bool,
+}
+
+impl Config {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs)
+    }
+
+    pub fn retry_backoff(&self) -> Duration {
+        Duration::from_millis(self.retry_backoff_ms)
+    }
+
+    pub fn keypair_path(&self) -> PathBuf {
+        self.keypair.clone().unwrap_or_else(|| {
+            let home = std::env::var_os("HOME").unwrap_or_default();
+            PathBuf::from(home).join(".config/solana/id.json")
+        })
+    }
+}
@@ -0,0 +1,172 @@
+//! The scan-plan-send loop.
+//!
+//! Sending is retried only when the RPC node fails. Every crank is guarded
+//! on-chain (closed flags, `is_active`, refunded entries, closed accounts), so
+//! a retry of a transaction that did land just fails with a program error,
+//! which is logged and left for the next scan to re-evaluate.
+
+use crate::chain::Chain;
+use crate::config::Config;
+use crate::plan::{self, Action};
+use coinpetitive_client::instructions::{self, Prize};
+use coinpetitive_client::{rpc, Challenge, PrizeKind};
+use log::{debug, info, warn};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::time::Duration;
+
+/// What one scan did.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TickReport {
+    pub challenges: usize,
+    pub sent: usize,
+    pub failed: usize,
+}
+
+pub struct Keeper<C> {
+    chain: C,
+    platform_token_account: Option<Pubkey>,
+    max_retries: u32,
+    retry_backoff: Duration,
+    interval: Duration,
+}
+
+impl<C: Chain> Keeper<C> {
+    pub fn new(chain: C, config: &Config) -> Self {
+        Self {
+            chain,
+            platform_token_account: config.platform_token_account,
+            max_retries: config.max_retries,
+            retry_backoff: config.retry_backoff(),
+            interval: config.interval(),
+        }
+    }
+
+    pub fn chain(&self) -> &C {
+        &self.chain
+    }
+
+    /// Scan forever, sleeping `interval` between scans.
+    pub fn run(&self) -> ! {
+        loop {
+            match self.tick() {
+                Ok(report) => debug!("scan finished: {report:?}"),
+                Err(err) => warn!("scan failed: {err}"),
+            }
+            std::thread::sleep(self.interval);
+        }
+    }
+
+    /// Scan every challenge once and send whatever is due.
+    pub fn tick(&self) -> rpc::Result<TickReport> {
+        let now = self.chain.now()?;
+        let challenges = self.chain.challenges()?;
+        let mut report = TickReport {
+            challenges: challenges.len(),
+            ..Default::default()
+        };
+
+        for (address, challenge) in &challenges {
+            for action in plan::plan(challenge, now) {
+                let sent = self
+                    .instruction(address, challenge, &action)
+                    .and_then(|instruction| self.send_with_retry(instruction));
+                match sent {
+                    Ok(signature) => {
+                        info!("{address}: {} sent in {signature}", action.name());
+                        report.sent += 1;
+                    }
+                    Err(err) => {
+                        warn!("{address}: {} failed: {err}", action.name());
+                        report.failed += 1;
+                        // Later actions depend on this one, try again next scan
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    pub fn instruction(
+        &self,
+        address: &Pubkey,
+        challenge: &Challenge,
+        action: &Action,
+    ) -> rpc::Result<Instruction> {
+        let keeper = self.chain.keeper();
+        let mint = challenge.reward_token_mint;
+        let prize = Prize::of(challenge);
+
+        Ok(match action {
+            Action::CloseSubmissions => instructions::close_submissions(&keeper, address),
+            Action::ProposeResults => instructions::propose_results(&keeper, address),
+            Action::Settle { winner, runner_up } => {
+                instructions::settle_results(&instructions::SettleResultsAccounts {
+                    authority: keeper,
+                    challenge: *address,
+                    creator: challenge.creator,
+                    mint,
+                    prize,
+                    winner: *winner,
+                    runner_up: Some(*runner_up),
+                    winner_has_profile: self.chain.has_profile(winner)?,
+                    runner_up_has_profile: self.chain.has_profile(runner_up)?,
+                    season: self.chain.running_season(self.chain.now()?)?,
+                    tournament: challenge.fees_to_tournament.then_some(challenge.tournament),
+                    winner_token_account: prize.account(winner),
+                    runnerup_token_account: prize.account(runner_up),
+                    platform_treasury_token_account: self.platform_prize_account(&prize)?,
+                })
+            }
+            Action::CancelForQuorum => {
+                instructions::cancel_challenge(&keeper, address, &challenge.creator)
+            }
+            Action::Refund { wallet } => {
+                instructions::claim_refund(&keeper, address, &mint, wallet)
+            }
+            Action::RefundSponsor { sponsor } => {
+                instructions::claim_sponsor_refund(&keeper, address, &mint, sponsor)
+            }
+            Action::PayJudge { judge } => {
+                instructions::claim_judge_fee(&keeper, address, &mint, judge)
+            }
+            Action::Close => {
+                instructions::close_challenge(&keeper, address, &challenge.creator, &mint, &prize)
+            }
+        })
+    }
+
+    // The program only accepts platform fees into the platform admin's account.
+    // A configured account is a CPT one, so other prizes use the admin's own
+    fn platform_prize_account(&self, prize: &Prize) -> rpc::Result<Pubkey> {
+        match self.platform_token_account {
+            Some(account) if prize.kind == PrizeKind::Cpt => Ok(account),
+            _ => {
+                let admin = self.chain.challenge_tracker()?.authority;
+                Ok(prize.account(&admin))
+            }
+        }
+    }
+
+    fn send_with_retry(&self, instruction: Instruction) -> rpc::Result<Signature> {
+        let mut delay = self.retry_backoff;
+        let mut attempt = 0;
+        loop {
+            match self.chain.send(instruction.clone()) {
+                Err(rpc::Error::Rpc(err)) if attempt < self.max_retries => {
+                    attempt += 1;
+                    warn!(
+                        "rpc error ({err}), retry {attempt}/{} in {delay:?}",
+                        self.max_retries
+                    );
+                    std::thread::sleep(delay);
+                    delay *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+}
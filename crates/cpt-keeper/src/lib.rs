@@ -0,0 +1,13 @@
+//! Keeper for the Coinpetitive program.
+//!
+//! Watches every `Challenge` account and, as phase deadlines pass, sends the
+//! permissionless instruction that moves it along: close submissions,
+//! propose results, settle them once the dispute window passes, cancel when
+//! the quorum was missed, refund a cancelled challenge's entrants and sponsors
+//! and close settled accounts. Each tick re-reads chain state, so a crashed or
+//! duplicated keeper never does anything twice.
+
+pub mod chain;
+pub mod config;
+pub mod keeper;
+pub mod plan;
@@ -0,0 +1,35 @@
+use anyhow::Context;
+use clap::Parser;
+use cpt_keeper::chain::RpcChain;
+use cpt_keeper::config::Config;
+use cpt_keeper::keeper::Keeper;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{read_keypair_file, Signer};
+
+fn main() -> anyhow::Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let config = Config::parse();
+
+    let keypair_path = config.keypair_path();
+    let signer = read_keypair_file(&keypair_path)
+        .map_err(|e| anyhow::anyhow!("{e}"))
+        .with_context(|| format!("reading keypair {}", keypair_path.display()))?;
+    log::info!("keeper {} watching {}", signer.pubkey(), config.url);
+
+    let rpc = RpcClient::new_with_commitment(config.url.clone(), CommitmentConfig::confirmed());
+    let keeper = Keeper::new(RpcChain::new(rpc, signer), &config);
+
+    if config.once {
+        let report = keeper.tick()?;
+        log::info!(
+            "scanned {} challenges, sent {}, failed {}",
+            report.challenges,
+            report.sent,
+            report.failed
+        );
+        return Ok(());
+    }
+
+    keeper.run()
+}
@@ -0,0 +1,152 @@
+//! Deciding what a challenge needs next. Pure functions of account state and
+//! cluster time, so they are tested without a validator.
+
+use coinpetitive_client::{payout, Challenge};
+use solana_sdk::pubkey::Pubkey;
+
+/// One crank the keeper can send for a challenge.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    CloseSubmissions,
+    /// Open the dispute window on the current ranking.
+    ProposeResults,
+    /// Pay the wallets that entered the winning and runner-up submissions.
+    Settle {
+        winner: Pubkey,
+        runner_up: Pubkey,
+    },
+    CancelForQuorum,
+    Refund {
+        wallet: Pubkey,
+    },
+    /// Return a sponsor's deposits from a cancelled challenge.
+    RefundSponsor {
+        sponsor: Pubkey,
+    },
+    /// Pay a judge who scored their share of the judge fees.
+    PayJudge {
+        judge: Pubkey,
+    },
+    Close,
+}
+
+impl Action {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::CloseSubmissions => "close_submissions",
+            Action::ProposeResults => "propose_results",
+            Action::Settle { .. } => "settle_results",
+            Action::CancelForQuorum => "cancel_challenge",
+            Action::Refund { .. } => "claim_refund",
+            Action::RefundSponsor { .. } => "claim_sponsor_refund",
+            Action::PayJudge { .. } => "claim_judge_fee",
+            Action::Close => "close_challenge",
+        }
+    }
+}
+
+/// Everything due for `challenge` at cluster time `now`, in the order it has
+/// to be sent. Frozen challenges are left to the admin.
+pub fn plan(challenge: &Challenge, now: i64) -> Vec<Action> {
+    let mut actions = Vec::new();
+
+    if challenge.is_frozen {
+        return actions;
+    }
+
+    if challenge.is_cancelled {
+        let owed = challenge.refundable_wallets();
+        if owed.is_empty() && challenge.sponsors.is_empty() && challenge.locked_stake == 0 {
+            actions.push(Action::Close);
+        }
+        actions.extend(owed.into_iter().map(|wallet| Action::Refund { wallet }));
+        actions.extend(
+            challenge
+                .sponsors
+                .iter()
+                .map(|&sponsor| Action::RefundSponsor { sponsor }),
+        );
+        return actions;
+    }
+
+    if !challenge.is_active {
+        let owed_judges = judges_owed(challenge);
+        if owed_judges.is_empty()
+            && challenge.locked_stake == 0
+            && !payout::voter_rewards_pending(challenge)
+        {
+            actions.push(Action::Close);
+        }
+        actions.extend(
+            owed_judges
+                .into_iter()
+                .map(|judge| Action::PayJudge { judge }),
+        );
+        return actions;
+    }
+
+    if !challenge.submissions_closed && now >= challenge.submission_end {
+        actions.push(Action::CloseSubmissions);
+    }
+
+    // Open disputes block cancellation as well as settlement, so they are
+    // left to the admin and judges, or to expire_dispute once they lapse
+    if challenge.open_disputes > 0 {
+        return actions;
+    }
+
+    if challenge.quorum_missed(now) {
+        actions.push(Action::CancelForQuorum);
+    } else if !challenge.results_proposed {
+        if now >= challenge.results_at() && settle_action(challenge).is_some() {
+            actions.push(Action::ProposeResults);
+        }
+    } else if challenge.can_settle(now) {
+        if let Some(settle) = settle_action(challenge) {
+            actions.push(settle);
+        }
+    }
+
+    actions
+}
+
+// Mirrors claim_judge_fee: judges who scored and haven't been paid
+fn judges_owed(challenge: &Challenge) -> Vec<Pubkey> {
+    if challenge.judge_fee_share() == 0 {
+        return Vec::new();
+    }
+    let unpaid = challenge.scoring_judges() & !challenge.judges_paid;
+    challenge
+        .judges
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| unpaid & (1 << i) != 0)
+        .map(|(_, judge)| *judge)
+        .collect()
+}
+
+fn settle_action(challenge: &Challenge) -> Option<Action> {
+    // The bounty doesn't change whether a challenge can be settled
+    let plan = match payout::plan_finalization(challenge, 0) {
+        Ok(plan) => plan,
+        Err(err) => {
+            log::warn!("cannot settle yet: {err}");
+            return None;
+        }
+    };
+
+    let submitter = |place: &payout::PlacePayout| challenge.submitter_of(&place.submission);
+    let winner = match submitter(&plan.places[0]) {
+        Some(winner) => winner,
+        None => {
+            log::warn!(
+                "winning submission {} has no submitter",
+                plan.places[0].submission
+            );
+            return None;
+        }
+    };
+    let runner_up = plan.places.get(1).and_then(submitter).unwrap_or(winner);
+
+    Some(Action::Settle { winner, runner_up })
+}
@@ -0,0 +1,214 @@
+use clap::Parser;
+use coinpetitive_client::instructions::{self, Prize};
+use coinpetitive_client::{rpc, Challenge, ChallengeTracker};
+use cpt_keeper::chain::Chain;
+use cpt_keeper::config::Config;
+use cpt_keeper::keeper::{Keeper, TickReport};
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+const ADMIN: Pubkey = Pubkey::new_from_array([9; 32]);
+
+/// Answers sends from a script, then succeeds.
+struct ScriptedChain {
+    now: i64,
+    challenges: Vec<(Pubkey, Challenge)>,
+    script: RefCell<VecDeque<rpc::Result<Signature>>>,
+    sent: RefCell<Vec<Instruction>>,
+}
+
+impl ScriptedChain {
+    fn new(challenges: Vec<(Pubkey, Challenge)>, script: Vec<rpc::Result<Signature>>) -> Self {
+        Self {
+            now: 5_000,
+            challenges,
+            script: RefCell::new(script.into()),
+            sent: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl Chain for ScriptedChain {
+    fn keeper(&self) -> Pubkey {
+        Pubkey::new_from_array([7; 32])
+    }
+
+    fn now(&self) -> rpc::Result<i64> {
+        Ok(self.now)
+    }
+
+    fn challenges(&self) -> rpc::Result<Vec<(Pubkey, Challenge)>> {
+        Ok(self.challenges.clone())
+    }
+
+    fn challenge_tracker(&self) -> rpc::Result<ChallengeTracker> {
+        Ok(ChallengeTracker {
+            total_challenges: 0,
+            authority: ADMIN,
+        })
+    }
+
+    fn running_season(&self, _now: i64) -> rpc::Result<Option<u64>> {
+        Ok(None)
+    }
+
+    fn has_profile(&self, _wallet: &Pubkey) -> rpc::Result<bool> {
+        Ok(true)
+    }
+
+    fn send(&self, instruction: Instruction) -> rpc::Result<Signature> {
+        self.sent.borrow_mut().push(instruction);
+        self.script
+            .borrow_mut()
+            .pop_front()
+            .unwrap_or_else(|| Ok(Signature::default()))
+    }
+}
+
+fn config() -> Config {
+    Config::parse_from([
+        "cpt-keeper",
+        "--retry-backoff-ms",
+        "0",
+        "--max-retries",
+        "2",
+    ])
+}
+
+fn transient() -> rpc::Error {
+    ClientError::from(ClientErrorKind::Custom("connection reset".to_string())).into()
+}
+
+fn program_error() -> rpc::Error {
+    rpc::Error::Program {
+        code: 6000,
+        name: "ChallengeNotActive".to_string(),
+        logs: Vec::new(),
+    }
+}
+
+// Submissions still open and no votes after the voting deadline: close then cancel
+fn expired_challenge() -> (Pubkey, Challenge) {
+    let challenge = Challenge {
+        is_active: true,
+        submission_end: 1_000,
+        voting_end: 2_000,
+        ..Default::default()
+    };
+    (Pubkey::new_unique(), challenge)
+}
+
+#[test]
+fn due_actions_are_sent_in_order() {
+    let chain = ScriptedChain::new(vec![expired_challenge()], Vec::new());
+    let keeper = Keeper::new(chain, &config());
+
+    let report = keeper.tick().unwrap();
+    assert_eq!(
+        report,
+        TickReport {
+            challenges: 1,
+            sent: 2,
+            failed: 0
+        }
+    );
+
+    let sent = keeper.chain().sent.borrow();
+    let (address, _) = &keeper.chain().challenges[0];
+    assert!(sent.iter().all(|ix| ix.accounts[1].pubkey == *address));
+    assert_ne!(sent[0].data, sent[1].data);
+}
+
+#[test]
+fn settlement_pays_the_platform_admin_and_the_keeper() {
+    let winner = Pubkey::new_unique();
+    let submission = Pubkey::new_unique();
+    let challenge = Challenge {
+        is_active: true,
+        submissions_closed: true,
+        submission_end: 1_000,
+        voting_end: 2_000,
+        reward: 1_000,
+        challenge_treasury: 1_000,
+        submission_votes: vec![(submission, 1)],
+        submitters: vec![(submission, winner)],
+        voters: vec![(Pubkey::new_unique(), submission)],
+        results_proposed: true,
+        dispute_end: 2_000,
+        ..Default::default()
+    };
+    let address = Pubkey::new_unique();
+    let keeper = Keeper::new(
+        ScriptedChain::new(vec![(address, challenge.clone())], Vec::new()),
+        &config(),
+    );
+
+    assert_eq!(keeper.tick().unwrap().sent, 1);
+
+    let prize = Prize::of(&challenge);
+    let expected = instructions::settle_results(&instructions::SettleResultsAccounts {
+        authority: keeper.chain().keeper(),
+        challenge: address,
+        creator: challenge.creator,
+        mint: challenge.reward_token_mint,
+        prize,
+        winner,
+        runner_up: Some(winner),
+        winner_has_profile: true,
+        runner_up_has_profile: true,
+        season: None,
+        tournament: None,
+        winner_token_account: prize.account(&winner),
+        runnerup_token_account: prize.account(&winner),
+        platform_treasury_token_account: prize.account(&ADMIN),
+    });
+    assert_eq!(keeper.chain().sent.borrow()[0], expected);
+}
+
+#[test]
+fn rpc_failures_are_retried() {
+    let chain = ScriptedChain::new(
+        vec![expired_challenge()],
+        vec![Err(transient()), Err(transient())],
+    );
+    let keeper = Keeper::new(chain, &config());
+
+    let report = keeper.tick().unwrap();
+    assert_eq!(report.sent, 2);
+    assert_eq!(report.failed, 0);
+    assert_eq!(keeper.chain().sent.borrow().len(), 4);
+}
+
+#[test]
+fn retries_are_bounded() {
+    let chain = ScriptedChain::new(
+        vec![expired_challenge()],
+        vec![Err(transient()), Err(transient()), Err(transient())],
+    );
+    let keeper = Keeper::new(chain, &config());
+
+    let report = keeper.tick().unwrap();
+    assert_eq!(report.sent, 0);
+    assert_eq!(report.failed, 1);
+    // One attempt plus two retries, and the dependent cancel is never sent
+    assert_eq!(keeper.chain().sent.borrow().len(), 3);
+}
+
+#[test]
+fn program_errors_are_not_retried() {
+    let chain = ScriptedChain::new(
+        vec![expired_challenge(), expired_challenge()],
+        vec![Err(program_error())],
+    );
+    let keeper = Keeper::new(chain, &config());
+
+    let report = keeper.tick().unwrap();
+    assert_eq!(report.failed, 1);
+    // The first challenge stops after its failure, the second still gets both
+    assert_eq!(report.sent, 2);
+    assert_eq!(keeper.chain().sent.borrow().len(), 3);
+}
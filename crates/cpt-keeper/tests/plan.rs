@@ -0,0 +1,322 @@
+use coinpetitive_client::{Challenge, JudgingMode, VotingMode};
+use cpt_keeper::plan::{plan, Action};
+use solana_sdk::pubkey::Pubkey;
+
+const SUBMISSION_END: i64 = 1_000;
+const VOTING_END: i64 = 2_000;
+const DISPUTE_END: i64 = 3_000;
+
+fn active_challenge() -> Challenge {
+    Challenge {
+        is_active: true,
+        reward: 1_000,
+        challenge_treasury: 1_000,
+        submission_end: SUBMISSION_END,
+        voting_end: VOTING_END,
+        ..Default::default()
+    }
+}
+
+fn with_submission(challenge: &mut Challenge, submitter: Pubkey, votes: u64) -> Pubkey {
+    let submission = Pubkey::new_unique();
+    challenge.submission_votes.push((submission, votes));
+    challenge.submitters.push((submission, submitter));
+    for _ in 0..votes {
+        challenge.voters.push((Pubkey::new_unique(), submission));
+    }
+    submission
+}
+
+#[test]
+fn nothing_is_due_before_the_submission_deadline() {
+    assert!(plan(&active_challenge(), SUBMISSION_END - 1).is_empty());
+}
+
+#[test]
+fn submissions_close_once_the_deadline_passes() {
+    let mut challenge = active_challenge();
+    assert_eq!(
+        plan(&challenge, SUBMISSION_END),
+        vec![Action::CloseSubmissions]
+    );
+
+    challenge.submissions_closed = true;
+    assert!(plan(&challenge, SUBMISSION_END + 1).is_empty());
+}
+
+#[test]
+fn challenges_without_votes_are_cancelled_after_voting() {
+    let mut challenge = active_challenge();
+    with_submission(&mut challenge, Pubkey::new_unique(), 0);
+
+    assert_eq!(
+        plan(&challenge, VOTING_END),
+        vec![Action::CloseSubmissions, Action::CancelForQuorum]
+    );
+}
+
+fn proposed(challenge: &mut Challenge, dispute_end: i64) {
+    challenge.results_proposed = true;
+    challenge.dispute_end = dispute_end;
+}
+
+#[test]
+fn settlement_pays_the_submitters_after_the_dispute_window() {
+    let (winner, runner_up) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut challenge = active_challenge();
+    challenge.submissions_closed = true;
+    with_submission(&mut challenge, runner_up, 1);
+    with_submission(&mut challenge, winner, 3);
+
+    assert!(plan(&challenge, VOTING_END - 1).is_empty());
+    assert_eq!(plan(&challenge, VOTING_END), vec![Action::ProposeResults]);
+
+    proposed(&mut challenge, DISPUTE_END);
+    assert!(plan(&challenge, DISPUTE_END - 1).is_empty());
+    assert_eq!(
+        plan(&challenge, DISPUTE_END),
+        vec![Action::Settle { winner, runner_up }]
+    );
+}
+
+#[test]
+fn open_disputes_hold_settlement_and_disqualified_winners_lose_their_place() {
+    let (winner, runner_up, third) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let mut challenge = active_challenge();
+    challenge.submissions_closed = true;
+    let disputed = with_submission(&mut challenge, winner, 3);
+    with_submission(&mut challenge, runner_up, 2);
+    with_submission(&mut challenge, third, 1);
+    proposed(&mut challenge, DISPUTE_END);
+
+    challenge.open_disputes = 1;
+    assert!(plan(&challenge, DISPUTE_END).is_empty());
+
+    challenge.open_disputes = 0;
+    challenge.disqualify(&disputed).unwrap();
+    assert_eq!(
+        plan(&challenge, DISPUTE_END),
+        vec![Action::Settle {
+            winner: runner_up,
+            runner_up: third
+        }]
+    );
+}
+
+#[test]
+fn lone_submission_pays_the_winner_twice_over() {
+    let winner = Pubkey::new_unique();
+    let mut challenge = active_challenge();
+    challenge.submissions_closed = true;
+    with_submission(&mut challenge, winner, 2);
+    proposed(&mut challenge, VOTING_END);
+
+    assert_eq!(
+        plan(&challenge, VOTING_END),
+        vec![Action::Settle {
+            winner,
+            runner_up: winner
+        }]
+    );
+}
+
+#[test]
+fn underfunded_challenges_are_not_finalized() {
+    let mut challenge = active_challenge();
+    challenge.submissions_closed = true;
+    challenge.challenge_treasury = 0;
+    with_submission(&mut challenge, Pubkey::new_unique(), 1);
+
+    assert!(plan(&challenge, VOTING_END).is_empty());
+}
+
+#[test]
+fn cancelled_challenges_refund_everyone_then_close() {
+    let (participant, voter) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut challenge = active_challenge();
+    challenge.is_active = false;
+    challenge.is_cancelled = true;
+    challenge.participants = vec![participant];
+    challenge.voters = vec![
+        (voter, Pubkey::new_unique()),
+        (participant, Pubkey::new_unique()),
+        (voter, Pubkey::new_unique()),
+    ];
+
+    assert_eq!(
+        plan(&challenge, 0),
+        vec![
+            Action::Refund {
+                wallet: participant
+            },
+            Action::Refund { wallet: voter },
+        ]
+    );
+
+    challenge.participants.clear();
+    challenge.voters.clear();
+    assert_eq!(plan(&challenge, 0), vec![Action::Close]);
+}
+
+#[test]
+fn cancelled_challenges_refund_sponsors_before_closing() {
+    let (participant, sponsor) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut challenge = active_challenge();
+    challenge.is_active = false;
+    challenge.is_cancelled = true;
+    challenge.participants = vec![participant];
+    challenge.sponsors = vec![sponsor];
+    challenge.sponsored = 500;
+
+    assert_eq!(
+        plan(&challenge, 0),
+        vec![
+            Action::Refund {
+                wallet: participant
+            },
+            Action::RefundSponsor { sponsor },
+        ]
+    );
+
+    challenge.participants.clear();
+    assert_eq!(plan(&challenge, 0), vec![Action::RefundSponsor { sponsor }]);
+
+    challenge.sponsors.clear();
+    assert_eq!(plan(&challenge, 0), vec![Action::Close]);
+}
+
+#[test]
+fn finalized_challenges_close_once_voters_are_paid() {
+    let mut challenge = active_challenge();
+    let winner = with_submission(&mut challenge, Pubkey::new_unique(), 2);
+    challenge.is_active = false;
+    challenge.winner = Some(winner);
+    challenge.voting_reward_pool = 100;
+    challenge.voting_treasury = 100;
+
+    assert!(plan(&challenge, VOTING_END).is_empty());
+
+    challenge.voting_treasury = 0;
+    assert_eq!(plan(&challenge, VOTING_END), vec![Action::Close]);
+}
+
+#[test]
+fn frozen_challenges_are_left_alone() {
+    let mut challenge = active_challenge();
+    challenge.is_frozen = true;
+    assert!(plan(&challenge, VOTING_END).is_empty());
+}
+
+#[test]
+fn too_few_participants_cancel_at_the_submission_deadline() {
+    let mut challenge = active_challenge();
+    challenge.min_participants = 2;
+    challenge.participants.push(Pubkey::new_unique());
+
+    assert!(plan(&challenge, SUBMISSION_END - 1).is_empty());
+    assert_eq!(
+        plan(&challenge, SUBMISSION_END),
+        vec![Action::CloseSubmissions, Action::CancelForQuorum]
+    );
+}
+
+#[test]
+fn too_few_voters_cancel_instead_of_finalizing() {
+    let mut challenge = active_challenge();
+    challenge.submissions_closed = true;
+    challenge.min_voters = 3;
+    with_submission(&mut challenge, Pubkey::new_unique(), 2);
+
+    assert!(plan(&challenge, VOTING_END - 1).is_empty());
+    assert_eq!(plan(&challenge, VOTING_END), vec![Action::CancelForQuorum]);
+
+    with_submission(&mut challenge, Pubkey::new_unique(), 1);
+    assert_eq!(plan(&challenge, VOTING_END), vec![Action::ProposeResults]);
+}
+
+#[test]
+fn refunds_cover_submitters_who_were_not_participants() {
+    let mut challenge = active_challenge();
+    challenge.is_active = false;
+    challenge.is_cancelled = true;
+    let submitter = Pubkey::new_unique();
+    challenge.submitters.push((Pubkey::new_unique(), submitter));
+
+    assert_eq!(
+        plan(&challenge, VOTING_END),
+        vec![Action::Refund { wallet: submitter }]
+    );
+}
+
+#[test]
+fn commit_reveal_challenges_wait_for_the_reveal_window() {
+    let reveal_end = VOTING_END + 500;
+    let winner = Pubkey::new_unique();
+    let mut challenge = active_challenge();
+    challenge.submissions_closed = true;
+    challenge.commit_reveal = true;
+    challenge.reveal_end = reveal_end;
+    with_submission(&mut challenge, winner, 1);
+
+    assert!(plan(&challenge, VOTING_END).is_empty());
+    assert_eq!(plan(&challenge, reveal_end), vec![Action::ProposeResults]);
+
+    proposed(&mut challenge, reveal_end);
+    assert_eq!(
+        plan(&challenge, reveal_end),
+        vec![Action::Settle {
+            winner,
+            runner_up: winner
+        }]
+    );
+}
+
+#[test]
+fn staked_challenges_close_once_stakes_are_withdrawn() {
+    let mut challenge = active_challenge();
+    challenge.voting_mode = VotingMode::StakeWeighted;
+    let winner = with_submission(&mut challenge, Pubkey::new_unique(), 1);
+    challenge.vote_weights.push(300);
+    challenge.is_active = false;
+    challenge.winner = Some(winner);
+    challenge.winning_weight = 300;
+    challenge.voting_reward_pool = 100;
+    challenge.voting_treasury = 100;
+    challenge.locked_stake = 300;
+
+    assert!(plan(&challenge, VOTING_END).is_empty());
+
+    // The voter was rewarded but hasn't taken their stake back yet
+    challenge.vote_weights[0] = 0;
+    challenge.voting_treasury = 0;
+    assert!(plan(&challenge, VOTING_END).is_empty());
+
+    challenge.locked_stake = 0;
+    assert_eq!(plan(&challenge, VOTING_END), vec![Action::Close]);
+}
+
+#[test]
+fn judges_who_scored_are_paid_before_closing() {
+    let (scored, idle) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut challenge = active_challenge();
+    challenge.judging_mode = JudgingMode::Mean;
+    challenge.judge_weight_bps = 10_000;
+    challenge.judges = vec![idle, scored];
+    let winner = with_submission(&mut challenge, Pubkey::new_unique(), 0);
+    challenge.set_score(1, 0, 80);
+    challenge.is_active = false;
+    challenge.winner = Some(winner);
+    challenge.judge_fee_pool = 50;
+
+    assert_eq!(
+        plan(&challenge, VOTING_END),
+        vec![Action::PayJudge { judge: scored }]
+    );
+
+    challenge.judges_paid = 0b10;
+    assert_eq!(plan(&challenge, VOTING_END), vec![Action::Close]);
+}
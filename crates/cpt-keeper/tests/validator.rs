@@ -0,0 +1,34 @@
+//! Runs against a live `solana-test-validator` with the program deployed:
+//!
+//! ```text
+//! solana-test-validator --reset \
+//!     --bpf-program 5NLxDYs6Br5H8D3F3eq4JjGa8wX292onmvwKbLC3wEbU target/deploy/coinpetitive.so
+//! cargo test -p cpt-keeper --test validator -- --ignored
+//! ```
+
+use clap::Parser;
+use cpt_keeper::chain::{Chain, RpcChain};
+use cpt_keeper::config::Config;
+use cpt_keeper::keeper::Keeper;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[test]
+#[ignore = "needs solana-test-validator with the program deployed"]
+fn scans_a_local_validator() {
+    let config = Config::parse_from(["cpt-keeper", "--once"]);
+    let rpc = RpcClient::new_with_commitment(config.url.clone(), CommitmentConfig::confirmed());
+
+    let signer = Keypair::new();
+    let signature = rpc
+        .request_airdrop(&signer.pubkey(), 1_000_000_000)
+        .expect("airdrop");
+    rpc.poll_for_signature(&signature).expect("airdrop landed");
+
+    let keeper = Keeper::new(RpcChain::new(rpc, signer), &config);
+    assert!(keeper.chain().now().unwrap() > 0);
+
+    let report = keeper.tick().expect("scan");
+    assert_eq!(report.failed, 0);
+}
@@ -0,0 +1,383 @@
+//! Command line definition.
+
+use clap::{Parser, Subcommand, ValueEnum};
+use solana_sdk::pubkey::Pubkey;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "cptctl",
+    version,
+    about = "Operate and inspect Coinpetitive challenges"
+)]
+pub struct Cli {
+    /// RPC endpoint, defaults to a local validator
+    #[arg(
+        long,
+        short = 'u',
+        global = true,
+        env = "CPT_RPC_URL",
+        default_value = "http://127.0.0.1:8899"
+    )]
+    pub url: String,
+
+    /// Keypair that signs and pays, defaults to the Solana CLI keypair
+    #[arg(long, short = 'k', global = true, env = "CPT_KEYPAIR")]
+    pub keypair: Option<PathBuf>,
+
+    #[arg(long, short = 'o', global = true, value_enum, default_value_t = OutputFormat::Human)]
+    pub output: OutputFormat,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Human,
+    Json,
+}
+
+/// Why a submission is disqualified. Ineligible and duplicate entries get
+/// their submission fee back, the others forfeit it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Reason {
+    Spam,
+    Abusive,
+    Plagiarism,
+    Ineligible,
+    Duplicate,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Create the FeeTracker and ChallengeTracker PDAs, skipping existing ones
+    InitTrackers,
+
+    /// List every challenge owned by the program
+    Challenges,
+
+    /// Show a challenge
+    Challenge { challenge: Pubkey },
+
+    /// List a challenge's participants
+    Participants { challenge: Pubkey },
+
+    /// List a challenge's votes as (voter, submission, weight)
+    Votes { challenge: Pubkey },
+
+    /// List a challenge's submissions ranked by votes
+    Submissions { challenge: Pubkey },
+
+    /// Compare a challenge's ledger with its treasury balances
+    Audit { challenge: Pubkey },
+
+    /// Show what finalization and voter distribution would pay right now
+    Preview { challenge: Pubkey },
+
+    /// Propose a challenge's results, opening its dispute window
+    ProposeResults { challenge: Pubkey },
+
+    /// Post the dispute bond against a submission during the dispute window
+    Dispute {
+        challenge: Pubkey,
+
+        /// Submission being disputed
+        #[arg(long)]
+        submission: Pubkey,
+    },
+
+    /// Resolve a dispute as the admin or a judge, slashing the bond unless upheld
+    ResolveDispute {
+        challenge: Pubkey,
+
+        /// Submission the dispute is against
+        #[arg(long)]
+        submission: Pubkey,
+
+        /// Disqualify the submission and return the bond
+        #[arg(long)]
+        upheld: bool,
+    },
+
+    /// Return the bond of a dispute left unresolved past the resolution period
+    ExpireDispute {
+        challenge: Pubkey,
+
+        /// Submission the dispute is against
+        #[arg(long)]
+        submission: Pubkey,
+    },
+
+    /// Take a submission out of the ranking as the admin or a judge
+    Disqualify {
+        challenge: Pubkey,
+
+        /// Submission being disqualified
+        #[arg(long)]
+        submission: Pubkey,
+
+        #[arg(long, value_enum)]
+        reason: Reason,
+    },
+
+    /// Settle proposed results, paying the winner, runner-up and platform fee
+    #[command(alias = "finalize")]
+    Settle {
+        challenge: Pubkey,
+
+        /// Prize account of the winning submission's owner: a token account of
+        /// the prize mint, or the wallet itself for SOL prizes
+        #[arg(long)]
+        winner_token_account: Pubkey,
+
+        /// Prize account of the runner-up, defaults to the winner's
+        #[arg(long)]
+        runner_up_token_account: Option<Pubkey>,
+
+        /// Prize account receiving the platform fee, defaults to the platform
+        /// admin's
+        #[arg(long)]
+        platform_token_account: Option<Pubkey>,
+
+        /// Only print the preview, don't send anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Escrow a challenge's reward in SOL or another token instead of paying
+    /// it out of CPT fees, before anyone joins
+    FundPrize {
+        challenge: Pubkey,
+
+        /// SPL or Token-2022 mint to pay the prize in, SOL when omitted
+        #[arg(long)]
+        mint: Option<Pubkey>,
+    },
+
+    /// Add CPT to a challenge's reward while it still takes entries
+    Sponsor {
+        challenge: Pubkey,
+
+        /// CPT added to the reward, in base units
+        #[arg(long)]
+        amount: u64,
+
+        /// Branding shown alongside the challenge, replaces any earlier one
+        #[arg(long)]
+        metadata_uri: Option<String>,
+    },
+
+    /// Cancel a challenge so fees are refunded instead of paid out
+    Cancel { challenge: Pubkey },
+
+    /// Refund a cancelled challenge's fees and sponsorships, one wallet or
+    /// everyone still owed
+    Refund {
+        challenge: Pubkey,
+
+        /// Only refund this wallet
+        #[arg(long)]
+        wallet: Option<Pubkey>,
+    },
+
+    /// Return a voter's stake from a settled stake-weighted challenge
+    WithdrawStake {
+        challenge: Pubkey,
+
+        /// Voter whose stake is returned
+        #[arg(long)]
+        voter: Pubkey,
+    },
+
+    /// Mint the trophy NFTs a settled challenge's placed winners have not
+    /// received yet
+    MintTrophies { challenge: Pubkey },
+
+    /// Mint the soulbound badges a settled challenge's participants, and
+    /// voters if it awards them, have not claimed yet
+    MintBadges { challenge: Pubkey },
+
+    /// Close a settled challenge and its treasuries, returning rent to the creator
+    Close { challenge: Pubkey },
+
+    /// Dump the FeeTracker
+    FeeTracker,
+
+    /// Dump the ChallengeTracker
+    ChallengeTracker,
+
+    /// Dump the CrankConfig
+    CrankConfig,
+
+    /// Show a wallet's lifetime statistics
+    Profile { wallet: Pubkey },
+
+    /// Show a season's leaderboard and what settling it would pay
+    Season { id: u64 },
+
+    /// Open the next season (admin only)
+    CreateSeason {
+        /// Season number, one after the latest
+        id: u64,
+
+        /// Unix time the season starts
+        #[arg(long)]
+        start: i64,
+
+        /// Unix time the season ends
+        #[arg(long)]
+        end: i64,
+
+        /// Basis points of each CPT platform fee paid into the prize pool
+        #[arg(long, default_value_t = 0)]
+        pool_share_bps: u16,
+
+        /// Leaderboard places paid when the season is settled
+        #[arg(long, default_value_t = 3)]
+        payout_places: u8,
+    },
+
+    /// Pay out an ended season's prize pool to its leaderboard
+    SettleSeason { id: u64 },
+
+    /// Show a team's members, their shares and who has paid
+    Team { challenge: Pubkey, captain: Pubkey },
+
+    /// Register a team captained by the signer for a challenge
+    CreateTeam {
+        challenge: Pubkey,
+
+        /// Another member's wallet, in order (repeatable)
+        #[arg(long = "member", required = true)]
+        members: Vec<Pubkey>,
+
+        /// Basis points of the fee and payouts per member, the captain's first
+        /// (repeatable, adding up to 10000)
+        #[arg(long = "share", required = true)]
+        shares: Vec<u16>,
+    },
+
+    /// Pay the signer's part of a team's participation fee
+    PayTeamFee {
+        challenge: Pubkey,
+        captain: Pubkey,
+
+        /// Pay every part still owed (captain only)
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Refund a member's part of the fee of a team that can no longer enter
+    RefundTeamFee {
+        challenge: Pubkey,
+        captain: Pubkey,
+
+        /// Member to refund, the signer by default
+        #[arg(long)]
+        member: Option<Pubkey>,
+    },
+
+    /// Split a team's prize, or its refund from a cancelled challenge, by shares
+    SplitTeamReward { challenge: Pubkey, captain: Pubkey },
+
+    /// Turn the signer's fresh challenge into a duel, staking CPT for the
+    /// winner to take
+    OpenDuel {
+        challenge: Pubkey,
+
+        /// CPT staked, in base units; the opponent matches it
+        #[arg(long)]
+        stake: u64,
+
+        /// Only this wallet may accept; anyone when left out
+        #[arg(long)]
+        opponent: Option<Pubkey>,
+
+        /// Unix time the stake must be matched by, before submissions close
+        #[arg(long)]
+        accept_by: i64,
+    },
+
+    /// Match a duel's stake as its opponent
+    AcceptDuel { challenge: Pubkey },
+
+    /// Show a tournament's rounds and who qualified for the current one
+    Tournament { address: Pubkey },
+
+    /// Start a tournament, escrowing its prize from the signer's CPT
+    CreateTournament {
+        /// Tournament number, unique among the signer's tournaments
+        id: u64,
+
+        /// Rounds played, the last being the final
+        #[arg(long, default_value_t = 2)]
+        rounds: u8,
+
+        /// Top submitters of each round going through to the next
+        #[arg(long, default_value_t = 3)]
+        advance: u8,
+
+        /// CPT escrowed for the final's winner
+        #[arg(long, default_value_t = 0)]
+        prize: u64,
+
+        /// Add the rounds' leftover fees to the prize instead of paying them to the creator
+        #[arg(long)]
+        roll_fees: bool,
+    },
+
+    /// Link one of the signer's fresh challenges as the tournament's next round
+    AddTournamentRound {
+        tournament: Pubkey,
+        challenge: Pubkey,
+    },
+
+    /// Qualify the current round's top submitters once it has settled
+    AdvanceTournament { tournament: Pubkey },
+
+    /// Pay the prize to the final's winner, or refund it after a cancelled round
+    FinishTournament { tournament: Pubkey },
+
+    /// Set the share of the platform fee paid to whoever finalizes (admin only)
+    SetCrankBounty {
+        /// Basis points of the platform fee, at most 10000
+        bps: u16,
+    },
+
+    /// Create the Metaplex collection trophies are minted into (admin only, once)
+    InitTrophyCollection {
+        /// Symbol of the collection and every trophy
+        #[arg(long, default_value = "CPTW")]
+        symbol: String,
+
+        /// Trophy metadata is served from <BASE_URI>/<challenge>/<rank>.json
+        #[arg(long)]
+        base_uri: String,
+    },
+
+    /// Dump the DisputeConfig
+    DisputeConfig,
+
+    /// Set the dispute bond and window length (admin only)
+    SetDisputeConfig {
+        /// CPT a participant posts to dispute a submission
+        #[arg(long)]
+        bond: u64,
+
+        /// Seconds between proposing and settling results
+        #[arg(long)]
+        window: i64,
+    },
+
+    /// Set the reputation a creator needs for large challenges (admin only)
+    SetReputationConfig {
+        /// Rewards at or above this count as large
+        #[arg(long)]
+        large_reward: u64,
+
+        /// Reputation needed to create a large challenge; zero turns it off
+        #[arg(long)]
+        min_creator_reputation: u64,
+    },
+}
@@ -0,0 +1,1119 @@
+//! One function per subcommand. Each returns the JSON document to print.
+
+use crate::cli::{Cli, Command, Reason};
+use crate::output;
+use anyhow::{Context as _, Result};
+use coinpetitive_client::{
+    instructions::{self, Prize},
+    payout, pda, rpc, AuditReport, Challenge, ChallengeTracker, CrankConfig, Dispute,
+    DisputeConfig, DisqualificationReason, FeeTracker, FinalizationPreview, PrizeKind, Season,
+    Team, Tournament, UserProfile, VoterRewardPreview,
+};
+use serde_json::{json, Value};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::system_program;
+use std::path::PathBuf;
+
+pub struct Context {
+    pub rpc: RpcClient,
+    keypair: PathBuf,
+}
+
+impl Context {
+    pub fn new(cli: &Cli) -> Self {
+        Self {
+            rpc: RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed()),
+            keypair: cli.keypair.clone().unwrap_or_else(default_keypair_path),
+        }
+    }
+
+    /// Only commands that sign or simulate need the keypair, so it is read lazily.
+    pub fn signer(&self) -> Result<Keypair> {
+        read_keypair_file(&self.keypair)
+            .map_err(|e| anyhow::anyhow!("{e}"))
+            .with_context(|| format!("reading keypair {}", self.keypair.display()))
+    }
+
+    fn challenge(&self, address: &Pubkey) -> Result<Challenge> {
+        rpc::fetch(&self.rpc, address).with_context(|| format!("fetching challenge {address}"))
+    }
+}
+
+pub fn default_keypair_path() -> PathBuf {
+    let home = std::env::var_os("HOME").unwrap_or_default();
+    PathBuf::from(home).join(".config/solana/id.json")
+}
+
+pub fn run(cli: &Cli) -> Result<Value> {
+    let ctx = Context::new(cli);
+
+    match &cli.command {
+        Command::InitTrackers => init_trackers(&ctx),
+        Command::Challenges => challenges(&ctx),
+        Command::Challenge { challenge } => {
+            Ok(output::challenge(challenge, &ctx.challenge(challenge)?))
+        }
+        Command::Participants { challenge } => Ok(output::participants(&ctx.challenge(challenge)?)),
+        Command::Votes { challenge } => Ok(output::votes(&ctx.challenge(challenge)?)),
+        Command::Submissions { challenge } => Ok(output::submissions(&ctx.challenge(challenge)?)),
+        Command::Audit { challenge } => audit(&ctx, challenge),
+        Command::Preview { challenge } => preview(&ctx, challenge),
+        Command::ProposeResults { challenge } => propose_results(&ctx, challenge),
+        Command::Dispute {
+            challenge,
+            submission,
+        } => raise_dispute(&ctx, challenge, submission),
+        Command::ResolveDispute {
+            challenge,
+            submission,
+            upheld,
+        } => resolve_dispute(&ctx, challenge, submission, *upheld),
+        Command::ExpireDispute {
+            challenge,
+            submission,
+        } => expire_dispute(&ctx, challenge, submission),
+        Command::Disqualify {
+            challenge,
+            submission,
+            reason,
+        } => disqualify(&ctx, challenge, submission, *reason),
+        Command::Settle {
+            challenge,
+            winner_token_account,
+            runner_up_token_account,
+            platform_token_account,
+            dry_run,
+        } => settle(
+            &ctx,
+            challenge,
+            winner_token_account,
+            runner_up_token_account.as_ref(),
+            platform_token_account.as_ref(),
+            *dry_run,
+        ),
+        Command::FundPrize { challenge, mint } => fund_prize(&ctx, challenge, mint.as_ref()),
+        Command::Sponsor {
+            challenge,
+            amount,
+            metadata_uri,
+        } => sponsor(&ctx, challenge, *amount, metadata_uri.clone()),
+        Command::Cancel { challenge } => cancel(&ctx, challenge),
+        Command::Refund { challenge, wallet } => refund(&ctx, challenge, wallet.as_ref()),
+        Command::WithdrawStake { challenge, voter } => withdraw_stake(&ctx, challenge, voter),
+        Command::MintTrophies { challenge } => mint_trophies(&ctx, challenge),
+        Command::MintBadges { challenge } => mint_badges(&ctx, challenge),
+        Command::Close { challenge } => close(&ctx, challenge),
+        Command::FeeTracker => {
+            let address = pda::fee_tracker().0;
+            let tracker: FeeTracker =
+                rpc::fetch(&ctx.rpc, &address).context("fetching fee tracker")?;
+            Ok(output::fee_tracker(&address, &tracker))
+        }
+        Command::ChallengeTracker => {
+            let address = pda::challenge_tracker().0;
+            let tracker: ChallengeTracker =
+                rpc::fetch(&ctx.rpc, &address).context("fetching challenge tracker")?;
+            Ok(output::challenge_tracker(&address, &tracker))
+        }
+        Command::CrankConfig => {
+            let address = pda::crank_config().0;
+            let config: CrankConfig =
+                rpc::fetch(&ctx.rpc, &address).context("fetching crank config")?;
+            Ok(output::crank_config(&address, &config))
+        }
+        Command::Profile { wallet } => {
+            let address = pda::user_profile(wallet).0;
+            let profile: UserProfile =
+                rpc::fetch(&ctx.rpc, &address).context("fetching user profile")?;
+            Ok(output::user_profile(&address, &profile))
+        }
+        Command::Season { id } => {
+            let address = pda::season(*id).0;
+            let season: Season = rpc::fetch(&ctx.rpc, &address).context("fetching season")?;
+            Ok(output::season(&address, &season))
+        }
+        Command::CreateSeason {
+            id,
+            start,
+            end,
+            pool_share_bps,
+            payout_places,
+        } => create_season(&ctx, *id, *start, *end, *pool_share_bps, *payout_places),
+        Command::SettleSeason { id } => settle_season(&ctx, *id),
+        Command::Team { challenge, captain } => {
+            let address = pda::team(challenge, captain).0;
+            let team: Team = rpc::fetch(&ctx.rpc, &address).context("fetching team")?;
+            Ok(output::team(&address, &team))
+        }
+        Command::CreateTeam {
+            challenge,
+            members,
+            shares,
+        } => create_team(&ctx, challenge, members, shares),
+        Command::PayTeamFee {
+            challenge,
+            captain,
+            all,
+        } => pay_team_fee(&ctx, challenge, captain, *all),
+        Command::RefundTeamFee {
+            challenge,
+            captain,
+            member,
+        } => refund_team_fee(&ctx, challenge, captain, member.as_ref()),
+        Command::SplitTeamReward { challenge, captain } => {
+            split_team_reward(&ctx, challenge, captain)
+        }
+        Command::OpenDuel {
+            challenge,
+            stake,
+            opponent,
+            accept_by,
+        } => open_duel(&ctx, challenge, *stake, *opponent, *accept_by),
+        Command::AcceptDuel { challenge } => accept_duel(&ctx, challenge),
+        Command::Tournament { address } => {
+            let tournament: Tournament =
+                rpc::fetch(&ctx.rpc, address).context("fetching tournament")?;
+            Ok(output::tournament(address, &tournament))
+        }
+        Command::CreateTournament {
+            id,
+            rounds,
+            advance,
+            prize,
+            roll_fees,
+        } => create_tournament(&ctx, *id, *rounds, *advance, *prize, *roll_fees),
+        Command::AddTournamentRound {
+            tournament,
+            challenge,
+        } => add_tournament_round(&ctx, tournament, challenge),
+        Command::AdvanceTournament { tournament } => advance_tournament(&ctx, tournament),
+        Command::FinishTournament { tournament } => finish_tournament(&ctx, tournament),
+        Command::SetCrankBounty { bps } => set_crank_bounty(&ctx, *bps),
+        Command::InitTrophyCollection { symbol, base_uri } => {
+            init_trophy_collection(&ctx, symbol, base_uri)
+        }
+        Command::DisputeConfig => {
+            let address = pda::dispute_config().0;
+            let config: DisputeConfig =
+                rpc::fetch(&ctx.rpc, &address).context("fetching dispute config")?;
+            Ok(output::dispute_config(&address, &config))
+        }
+        Command::SetDisputeConfig { bond, window } => set_dispute_config(&ctx, *bond, *window),
+        Command::SetReputationConfig {
+            large_reward,
+            min_creator_reputation,
+        } => set_reputation_config(&ctx, *large_reward, *min_creator_reputation),
+    }
+}
+
+fn init_trackers(ctx: &Context) -> Result<Value> {
+    let signer = ctx.signer()?;
+    let mut instructions = Vec::new();
+    let mut created = Vec::new();
+
+    let trackers = [
+        (
+            "fee_tracker",
+            pda::fee_tracker().0,
+            instructions::initialize_fee_tracker(&signer.pubkey()),
+        ),
+        (
+            "challenge_tracker",
+            pda::challenge_tracker().0,
+            instructions::initialize_challenge_tracker(&signer.pubkey()),
+        ),
+    ];
+    for (name, address, instruction) in trackers {
+        let exists = ctx
+            .rpc
+            .get_account_with_commitment(&address, ctx.rpc.commitment())?
+            .value
+            .is_some();
+        if exists {
+            continue;
+        }
+        instructions.push(instruction);
+        created.push(json!({ "tracker": name, "address": address.to_string() }));
+    }
+
+    if instructions.is_empty() {
+        return Ok(json!({ "created": created, "signature": null }));
+    }
+
+    let signature = rpc::send(&ctx.rpc, &instructions, &signer, &[])?;
+    Ok(json!({ "created": created, "signature": signature.to_string() }))
+}
+
+fn challenges(ctx: &Context) -> Result<Value> {
+    let mut challenges = rpc::fetch_challenges(&ctx.rpc)?;
+    challenges.sort_by_key(|(address, _)| *address);
+    Ok(challenges
+        .iter()
+        .map(|(address, challenge)| output::challenge_summary(address, challenge))
+        .collect())
+}
+
+fn audit(ctx: &Context, challenge: &Pubkey) -> Result<Value> {
+    let mint = ctx.challenge(challenge)?.reward_token_mint;
+    let payer = ctx.signer()?.pubkey();
+    let report: AuditReport = rpc::simulate_view(
+        &ctx.rpc,
+        &payer,
+        instructions::audit_challenge(challenge, &mint),
+    )?;
+    Ok(output::audit(&report))
+}
+
+fn preview(ctx: &Context, challenge: &Pubkey) -> Result<Value> {
+    let payer = ctx.signer()?.pubkey();
+
+    // Either half can legitimately fail, e.g. before anyone has voted
+    let finalization = rpc::simulate_view::<FinalizationPreview>(
+        &ctx.rpc,
+        &payer,
+        instructions::preview_finalization(challenge),
+    )
+    .map(|p| output::finalization_preview(&p));
+    let voter_reward = rpc::simulate_view::<VoterRewardPreview>(
+        &ctx.rpc,
+        &payer,
+        instructions::preview_voter_reward(challenge),
+    )
+    .map(|p| output::voter_reward_preview(&p));
+
+    let or_error =
+        |result: rpc::Result<Value>| result.unwrap_or_else(|e| json!({ "error": e.to_string() }));
+    Ok(json!({
+        "finalization": or_error(finalization),
+        "voter_reward": or_error(voter_reward),
+    }))
+}
+
+fn propose_results(ctx: &Context, challenge: &Pubkey) -> Result<Value> {
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::propose_results(&signer.pubkey(), challenge)],
+        &signer,
+        &[],
+    )?;
+    let state = ctx.challenge(challenge)?;
+    Ok(json!({
+        "challenge": challenge.to_string(),
+        "dispute_end": state.dispute_end,
+        "signature": signature.to_string(),
+    }))
+}
+
+fn raise_dispute(ctx: &Context, challenge: &Pubkey, submission: &Pubkey) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::raise_dispute(
+            &signer.pubkey(),
+            challenge,
+            &state.reward_token_mint,
+            submission,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({
+        "submission": submission.to_string(),
+        "bond": state.dispute_bond,
+        "signature": signature.to_string(),
+    }))
+}
+
+fn resolve_dispute(
+    ctx: &Context,
+    challenge: &Pubkey,
+    submission: &Pubkey,
+    upheld: bool,
+) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let address = pda::dispute(challenge, submission).0;
+    let dispute: Dispute = rpc::fetch(&ctx.rpc, &address).context("fetching dispute")?;
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::resolve_dispute(
+            &signer.pubkey(),
+            challenge,
+            &state.reward_token_mint,
+            submission,
+            &dispute.disputer,
+            &state
+                .submitter_of(submission)
+                .with_context(|| format!("no submitter for {submission}"))?,
+            upheld,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({
+        "submission": submission.to_string(),
+        "disputer": dispute.disputer.to_string(),
+        "upheld": upheld,
+        "signature": signature.to_string(),
+    }))
+}
+
+fn expire_dispute(ctx: &Context, challenge: &Pubkey, submission: &Pubkey) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let address = pda::dispute(challenge, submission).0;
+    let dispute: Dispute = rpc::fetch(&ctx.rpc, &address).context("fetching dispute")?;
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::expire_dispute(
+            &signer.pubkey(),
+            challenge,
+            &state.reward_token_mint,
+            submission,
+            &dispute.disputer,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({
+        "submission": submission.to_string(),
+        "disputer": dispute.disputer.to_string(),
+        "bond": dispute.bond,
+        "signature": signature.to_string(),
+    }))
+}
+
+fn disqualify(
+    ctx: &Context,
+    challenge: &Pubkey,
+    submission: &Pubkey,
+    reason: Reason,
+) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let submitter = state
+        .submitter_of(submission)
+        .with_context(|| format!("{submission} was not entered in {challenge}"))?;
+    let reason = match reason {
+        Reason::Spam => DisqualificationReason::Spam,
+        Reason::Abusive => DisqualificationReason::Abusive,
+        Reason::Plagiarism => DisqualificationReason::Plagiarism,
+        Reason::Ineligible => DisqualificationReason::Ineligible,
+        Reason::Duplicate => DisqualificationReason::Duplicate,
+    };
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::disqualify_submission(
+            &signer.pubkey(),
+            challenge,
+            &state.reward_token_mint,
+            submission,
+            &submitter,
+            reason,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({
+        "submission": submission.to_string(),
+        "submitter": submitter.to_string(),
+        "fee_refunded": reason.refunds_fee(),
+        "signature": signature.to_string(),
+    }))
+}
+
+fn settle(
+    ctx: &Context,
+    challenge: &Pubkey,
+    winner_token_account: &Pubkey,
+    runner_up_token_account: Option<&Pubkey>,
+    platform_token_account: Option<&Pubkey>,
+    dry_run: bool,
+) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+
+    let preview: FinalizationPreview = rpc::simulate_view(
+        &ctx.rpc,
+        &signer.pubkey(),
+        instructions::preview_finalization(challenge),
+    )
+    .context("previewing finalization")?;
+    if dry_run {
+        return Ok(json!({ "preview": output::finalization_preview(&preview), "signature": null }));
+    }
+
+    let prize = Prize::of(&state);
+    let platform_token_account = match platform_token_account {
+        Some(account) => *account,
+        None => {
+            let tracker: ChallengeTracker = rpc::fetch(&ctx.rpc, &pda::challenge_tracker().0)
+                .context("fetching challenge tracker")?;
+            prize.account(&tracker.authority)
+        }
+    };
+    let place = |i: usize| {
+        preview
+            .places
+            .get(i)
+            .and_then(|place| state.submitter_of(&place.submission))
+    };
+    let winner = place(0).context("no winning submitter")?;
+    let runner_up = place(1);
+    let keys = instructions::SettleResultsAccounts {
+        authority: signer.pubkey(),
+        challenge: *challenge,
+        creator: state.creator,
+        mint: state.reward_token_mint,
+        prize,
+        winner,
+        runner_up,
+        winner_has_profile: rpc::has_profile(&ctx.rpc, &winner)?,
+        runner_up_has_profile: match runner_up {
+            Some(wallet) => rpc::has_profile(&ctx.rpc, &wallet)?,
+            None => false,
+        },
+        season: rpc::running_season(&ctx.rpc, rpc::cluster_time(&ctx.rpc)?)?,
+        tournament: state.fees_to_tournament.then_some(state.tournament),
+        winner_token_account: *winner_token_account,
+        runnerup_token_account: *runner_up_token_account.unwrap_or(winner_token_account),
+        platform_treasury_token_account: platform_token_account,
+    };
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::settle_results(&keys)],
+        &signer,
+        &[],
+    )?;
+
+    Ok(json!({
+        "preview": output::finalization_preview(&preview),
+        "signature": signature.to_string(),
+    }))
+}
+
+fn set_crank_bounty(ctx: &Context, bps: u16) -> Result<Value> {
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::set_crank_bounty(&signer.pubkey(), bps)],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({ "finalize_bounty_bps": bps, "signature": signature.to_string() }))
+}
+
+fn set_dispute_config(ctx: &Context, bond: u64, window: i64) -> Result<Value> {
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::set_dispute_config(
+            &signer.pubkey(),
+            bond,
+            window,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({ "bond": bond, "window": window, "signature": signature.to_string() }))
+}
+
+fn set_reputation_config(
+    ctx: &Context,
+    large_reward: u64,
+    min_creator_reputation: u64,
+) -> Result<Value> {
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::set_reputation_config(
+            &signer.pubkey(),
+            large_reward,
+            min_creator_reputation,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({
+        "large_reward": large_reward,
+        "min_creator_reputation": min_creator_reputation,
+        "signature": signature.to_string(),
+    }))
+}
+
+fn init_trophy_collection(ctx: &Context, symbol: &str, base_uri: &str) -> Result<Value> {
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::initialize_trophy_collection(
+            &signer.pubkey(),
+            symbol.to_string(),
+            base_uri.to_string(),
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({
+        "collection": pda::trophy_collection().0.to_string(),
+        "mint": pda::trophy_collection_mint().0.to_string(),
+        "signature": signature.to_string(),
+    }))
+}
+
+fn fund_prize(ctx: &Context, challenge: &Pubkey, mint: Option<&Pubkey>) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+    let prize = match mint {
+        // The mint's owner tells SPL Token and Token-2022 mints apart
+        Some(mint) => Prize {
+            kind: PrizeKind::Token,
+            mint: *mint,
+            token_program: ctx
+                .rpc
+                .get_account(mint)
+                .with_context(|| format!("fetching mint {mint}"))?
+                .owner,
+        },
+        None => Prize {
+            kind: PrizeKind::Sol,
+            mint: Pubkey::default(),
+            token_program: system_program::ID,
+        },
+    };
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::fund_prize(
+            &signer.pubkey(),
+            challenge,
+            &prize,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({
+        "challenge": challenge.to_string(),
+        "prize": output::prize_kind(prize.kind),
+        "mint": mint.map(|m| m.to_string()),
+        "amount": state.reward,
+        "signature": signature.to_string(),
+    }))
+}
+
+fn sponsor(
+    ctx: &Context,
+    challenge: &Pubkey,
+    amount: u64,
+    metadata_uri: Option<String>,
+) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::sponsor_challenge(
+            &signer.pubkey(),
+            challenge,
+            &state.reward_token_mint,
+            amount,
+            metadata_uri,
+        )],
+        &signer,
+        &[],
+    )?;
+    let state = ctx.challenge(challenge)?;
+    Ok(json!({
+        "sponsor": signer.pubkey().to_string(),
+        "amount": amount,
+        "reward": state.reward,
+        "signature": signature.to_string(),
+    }))
+}
+
+fn cancel(ctx: &Context, challenge: &Pubkey) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::cancel_challenge(
+            &signer.pubkey(),
+            challenge,
+            &state.creator,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({ "challenge": challenge.to_string(), "signature": signature.to_string() }))
+}
+
+fn refund(ctx: &Context, challenge: &Pubkey, wallet: Option<&Pubkey>) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+
+    let (wallets, sponsors) = match wallet {
+        // Sponsors only have fees to refund if they also entered or voted
+        Some(wallet) if state.sponsors.contains(wallet) => {
+            let owed = state.refundable_wallets();
+            (
+                owed.into_iter().filter(|w| w == wallet).collect(),
+                vec![*wallet],
+            )
+        }
+        Some(wallet) => (vec![*wallet], Vec::new()),
+        None => (state.refundable_wallets(), state.sponsors.clone()),
+    };
+
+    let mint = &state.reward_token_mint;
+    let payer = signer.pubkey();
+    let fees = wallets.iter().map(|wallet| {
+        let ix = instructions::claim_refund(&payer, challenge, mint, wallet);
+        ("fees", wallet, ix)
+    });
+    let sponsorships = sponsors.iter().map(|sponsor| {
+        let ix = instructions::claim_sponsor_refund(&payer, challenge, mint, sponsor);
+        ("sponsorship", sponsor, ix)
+    });
+
+    // One transaction per refund so a missing token account only skips that one
+    let results: Vec<Value> = fees
+        .chain(sponsorships)
+        .map(
+            |(refund, wallet, ix)| match rpc::send(&ctx.rpc, &[ix], &signer, &[]) {
+                Ok(signature) => json!({
+                    "wallet": wallet.to_string(),
+                    "refund": refund,
+                    "signature": signature.to_string(),
+                }),
+                Err(e) => json!({
+                    "wallet": wallet.to_string(),
+                    "refund": refund,
+                    "error": e.to_string(),
+                }),
+            },
+        )
+        .collect();
+
+    Ok(Value::Array(results))
+}
+
+fn mint_trophies(ctx: &Context, challenge: &Pubkey) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+    let places = payout::trophy_places(&state).context("ranking submissions")?;
+
+    // One transaction per place, skipping trophies that were already minted
+    let mut results = Vec::new();
+    for (rank, (submission, _)) in (1..).zip(places) {
+        let trophy = pda::trophy(challenge, rank).0;
+        let minted = ctx
+            .rpc
+            .get_account_with_commitment(&trophy, ctx.rpc.commitment())?
+            .value
+            .is_some();
+        if minted {
+            continue;
+        }
+        let winner = state
+            .submitter_of(&submission)
+            .with_context(|| format!("no submitter for {submission}"))?;
+        let ix = instructions::mint_trophy(&signer.pubkey(), challenge, &winner, rank);
+        results.push(match rpc::send(&ctx.rpc, &[ix], &signer, &[]) {
+            Ok(signature) => json!({
+                "rank": rank,
+                "winner": winner.to_string(),
+                "mint": pda::trophy_mint(challenge, rank).0.to_string(),
+                "signature": signature.to_string(),
+            }),
+            Err(e) => json!({
+                "rank": rank,
+                "winner": winner.to_string(),
+                "error": e.to_string(),
+            }),
+        });
+    }
+
+    Ok(Value::Array(results))
+}
+
+fn create_season(
+    ctx: &Context,
+    id: u64,
+    start: i64,
+    end: i64,
+    pool_share_bps: u16,
+    payout_places: u8,
+) -> Result<Value> {
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::create_season(
+            &signer.pubkey(),
+            id,
+            start,
+            end,
+            pool_share_bps,
+            payout_places,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({
+        "season": pda::season(id).0.to_string(),
+        "signature": signature.to_string(),
+    }))
+}
+
+fn settle_season(ctx: &Context, id: u64) -> Result<Value> {
+    let season: Season = rpc::fetch(&ctx.rpc, &pda::season(id).0).context("fetching season")?;
+    let tracker: ChallengeTracker =
+        rpc::fetch(&ctx.rpc, &pda::challenge_tracker().0).context("fetching challenge tracker")?;
+    let signer = ctx.signer()?;
+
+    let prizes = payout::plan_season_prizes(&season);
+    let placed: Vec<Pubkey> = prizes.iter().map(|prize| prize.wallet).collect();
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::settle_season(
+            &signer.pubkey(),
+            id,
+            &placed,
+            &pda::token_account(&tracker.authority, &coinpetitive_client::cpt_mint()),
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({
+        "prizes": output::season_prizes(&prizes),
+        "signature": signature.to_string(),
+    }))
+}
+
+fn create_team(
+    ctx: &Context,
+    challenge: &Pubkey,
+    members: &[Pubkey],
+    shares: &[u16],
+) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+    let captain = signer.pubkey();
+    let team = pda::team(challenge, &captain).0;
+
+    let mut wallets = vec![captain];
+    wallets.extend_from_slice(members);
+    let mut ixs = vec![instructions::create_team(
+        &captain, challenge, &wallets, shares,
+    )];
+    // CPT is covered by create_team; a Token prize needs the team's account
+    let prize = Prize::of(&state);
+    if prize.kind == PrizeKind::Token {
+        ixs.push(instructions::create_prize_account(&captain, &team, &prize));
+    }
+    let signature = rpc::send(&ctx.rpc, &ixs, &signer, &[])?;
+    Ok(json!({
+        "team": team.to_string(),
+        "signature": signature.to_string(),
+    }))
+}
+
+fn pay_team_fee(ctx: &Context, challenge: &Pubkey, captain: &Pubkey, all: bool) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::pay_team_fee(
+            &signer.pubkey(),
+            challenge,
+            captain,
+            &state.reward_token_mint,
+            all,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({ "signature": signature.to_string() }))
+}
+
+fn refund_team_fee(
+    ctx: &Context,
+    challenge: &Pubkey,
+    captain: &Pubkey,
+    member: Option<&Pubkey>,
+) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+    let member = member.copied().unwrap_or_else(|| signer.pubkey());
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::refund_team_fee(
+            &signer.pubkey(),
+            challenge,
+            captain,
+            &state.reward_token_mint,
+            &member,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({
+        "member": member.to_string(),
+        "signature": signature.to_string(),
+    }))
+}
+
+fn split_team_reward(ctx: &Context, challenge: &Pubkey, captain: &Pubkey) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let team: Team =
+        rpc::fetch(&ctx.rpc, &pda::team(challenge, captain).0).context("fetching team")?;
+    // Refunds come back in CPT, prizes in their own currency
+    let currency = if state.is_cancelled {
+        Prize::cpt(&state.reward_token_mint)
+    } else {
+        Prize::of(&state)
+    };
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::split_team_reward(
+            &signer.pubkey(),
+            challenge,
+            captain,
+            &team.members,
+            &currency,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({ "signature": signature.to_string() }))
+}
+
+fn open_duel(
+    ctx: &Context,
+    challenge: &Pubkey,
+    stake: u64,
+    opponent: Option<Pubkey>,
+    accept_by: i64,
+) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::open_duel(
+            &signer.pubkey(),
+            challenge,
+            &state.reward_token_mint,
+            stake,
+            opponent,
+            accept_by,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({
+        "stake": stake,
+        "opponent": opponent.map(|o| o.to_string()),
+        "accept_by": accept_by,
+        "signature": signature.to_string(),
+    }))
+}
+
+fn accept_duel(ctx: &Context, challenge: &Pubkey) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::accept_duel(
+            &signer.pubkey(),
+            challenge,
+            &state.reward_token_mint,
+        )],
+        &signer,
+        &[],
+    )?;
+    let state = ctx.challenge(challenge)?;
+    Ok(json!({
+        "opponent": signer.pubkey().to_string(),
+        "pot": state.reward,
+        "signature": signature.to_string(),
+    }))
+}
+
+fn create_tournament(
+    ctx: &Context,
+    id: u64,
+    rounds: u8,
+    advance: u8,
+    prize: u64,
+    roll_fees: bool,
+) -> Result<Value> {
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::create_tournament(
+            &signer.pubkey(),
+            id,
+            rounds,
+            advance,
+            prize,
+            roll_fees,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({
+        "tournament": pda::tournament(&signer.pubkey(), id).0.to_string(),
+        "signature": signature.to_string(),
+    }))
+}
+
+fn add_tournament_round(ctx: &Context, tournament: &Pubkey, challenge: &Pubkey) -> Result<Value> {
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::add_tournament_round(
+            &signer.pubkey(),
+            tournament,
+            challenge,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({ "signature": signature.to_string() }))
+}
+
+/// The tournament and the challenge of the round it is on.
+fn current_round(ctx: &Context, tournament: &Pubkey) -> Result<(Tournament, Pubkey, Challenge)> {
+    let state: Tournament = rpc::fetch(&ctx.rpc, tournament).context("fetching tournament")?;
+    let round = state
+        .current_round_challenge()
+        .context("the current round has not been added")?;
+    let challenge = ctx.challenge(&round)?;
+    Ok((state, round, challenge))
+}
+
+fn advance_tournament(ctx: &Context, tournament: &Pubkey) -> Result<Value> {
+    let (_, round, _) = current_round(ctx, tournament)?;
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::advance_tournament(
+            &signer.pubkey(),
+            tournament,
+            &round,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({ "round": round.to_string(), "signature": signature.to_string() }))
+}
+
+fn finish_tournament(ctx: &Context, tournament: &Pubkey) -> Result<Value> {
+    let (state, round, challenge) = current_round(ctx, tournament)?;
+    let recipient = if challenge.is_cancelled {
+        state.creator
+    } else {
+        challenge
+            .winner
+            .and_then(|winner| challenge.submitter_of(&winner))
+            .context("the final has no winner yet")?
+    };
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::finish_tournament(
+            &signer.pubkey(),
+            tournament,
+            &round,
+            &recipient,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({
+        "recipient": recipient.to_string(),
+        "signature": signature.to_string(),
+    }))
+}
+
+fn mint_badges(ctx: &Context, challenge: &Pubkey) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+
+    let mut wallets = state.participants.clone();
+    if state.voter_badges {
+        for (voter, _) in &state.voters {
+            if !wallets.contains(voter) {
+                wallets.push(*voter);
+            }
+        }
+    }
+
+    // One transaction per wallet, skipping badges that were already claimed
+    let mut results = Vec::new();
+    for wallet in wallets {
+        let badge = pda::badge(challenge, &wallet).0;
+        let minted = ctx
+            .rpc
+            .get_account_with_commitment(&badge, ctx.rpc.commitment())?
+            .value
+            .is_some();
+        if minted {
+            continue;
+        }
+        let ix = instructions::claim_badge(&signer.pubkey(), challenge, &wallet);
+        results.push(match rpc::send(&ctx.rpc, &[ix], &signer, &[]) {
+            Ok(signature) => json!({
+                "wallet": wallet.to_string(),
+                "signature": signature.to_string(),
+            }),
+            Err(e) => json!({
+                "wallet": wallet.to_string(),
+                "error": e.to_string(),
+            }),
+        });
+    }
+
+    Ok(Value::Array(results))
+}
+
+fn withdraw_stake(ctx: &Context, challenge: &Pubkey, voter: &Pubkey) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::withdraw_stake(
+            &signer.pubkey(),
+            challenge,
+            &state.reward_token_mint,
+            voter,
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({ "voter": voter.to_string(), "signature": signature.to_string() }))
+}
+
+fn close(ctx: &Context, challenge: &Pubkey) -> Result<Value> {
+    let state = ctx.challenge(challenge)?;
+    let signer = ctx.signer()?;
+    let signature = rpc::send(
+        &ctx.rpc,
+        &[instructions::close_challenge(
+            &signer.pubkey(),
+            challenge,
+            &state.creator,
+            &state.reward_token_mint,
+            &Prize::of(&state),
+        )],
+        &signer,
+        &[],
+    )?;
+    Ok(json!({
+        "challenge": challenge.to_string(),
+        "creator": state.creator.to_string(),
+        "signature": signature.to_string(),
+    }))
+}
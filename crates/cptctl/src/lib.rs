@@ -0,0 +1,12 @@
+//! Operator CLI for the Coinpetitive program.
+//!
+//! Inspects challenges and trackers and drives the admin flows (finalize,
+//! cancel, refund, close) against any RPC endpoint, a local validator by
+//! default. Output is human-readable or JSON for scripting.
+
+// The challenge view is one large `json!` literal
+#![recursion_limit = "256"]
+
+pub mod cli;
+pub mod commands;
+pub mod output;
@@ -0,0 +1,15 @@
+use clap::Parser;
+use cptctl::cli::Cli;
+use cptctl::{commands, output};
+
+fn main() {
+    let cli = Cli::parse();
+
+    match commands::run(&cli) {
+        Ok(value) => println!("{}", output::render(cli.output, &value)),
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,473 @@
+//! JSON views of program state and the human renderer built on top of them.
+//!
+//! Every command produces a `serde_json::Value`; `--output json` prints it as
+//! is and `--output human` flattens it into aligned `key: value` lines.
+
+use crate::cli::OutputFormat;
+use coinpetitive_client::{
+    payout, pda, AuditReport, Challenge, ChallengeTracker, CrankConfig, DisputeConfig, FeeTracker,
+    FinalizationPreview, JudgingMode, PrizeKind, Season, SeasonPrize, Team, Tournament,
+    UserProfile, VoterRewardPreview, VotingMode,
+};
+use serde_json::{json, Map, Value};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn challenge_summary(address: &Pubkey, challenge: &Challenge) -> Value {
+    json!({
+        "address": address.to_string(),
+        "creator": challenge.creator.to_string(),
+        "status": status(challenge),
+        "reward": challenge.reward,
+        "participants": challenge.participants.len(),
+        "max_participants": challenge.max_participants,
+        "submissions": challenge.submission_votes.len(),
+        "total_votes": challenge.total_votes,
+    })
+}
+
+pub fn challenge(address: &Pubkey, challenge: &Challenge) -> Value {
+    json!({
+        "address": address.to_string(),
+        "creator": challenge.creator.to_string(),
+        "status": status(challenge),
+        "is_active": challenge.is_active,
+        "is_cancelled": challenge.is_cancelled,
+        "is_frozen": challenge.is_frozen,
+        "submissions_closed": challenge.submissions_closed,
+        "submission_end": challenge.submission_end,
+        "voting_end": challenge.voting_end,
+        "commit_reveal": challenge.commit_reveal,
+        "reveal_end": challenge.reveal_end,
+        "vote_commitments": challenge.vote_commitments,
+        "voting_mode": voting_mode(challenge.voting_mode),
+        "reward": challenge.reward,
+        "participation_fee": challenge.participation_fee,
+        "voting_fee": challenge.voting_fee,
+        "challenge_treasury": challenge.challenge_treasury,
+        "voting_treasury": challenge.voting_treasury,
+        "voting_reward_pool": challenge.voting_reward_pool,
+        "winner": challenge.winner.map(|w| w.to_string()),
+        "winning_votes": challenge.winning_votes,
+        "winning_weight": challenge.winning_weight,
+        "total_votes": challenge.total_votes,
+        "locked_stake": challenge.locked_stake,
+        "judging": judging(challenge),
+        "disputes": disputes(challenge),
+        "sponsorship": sponsorship(challenge),
+        "prize": prize(challenge),
+        "badges": badges(address, challenge),
+        "trophies": trophies(address, challenge),
+        "reputation": reputation(challenge),
+        "tournament": tournament_round(challenge),
+        "duel": duel(challenge),
+        "participants": challenge.participants.len(),
+        "max_participants": challenge.max_participants,
+        "min_participants": challenge.min_participants,
+        "submissions": challenge.submission_votes.len(),
+        "voters": challenge.unique_voters(),
+        "min_voters": challenge.min_voters,
+        "max_voters": challenge.max_voters,
+        "has_quorum": challenge.has_quorum(),
+        "reward_token_mint": challenge.reward_token_mint.to_string(),
+        "treasury": challenge.treasury.to_string(),
+        "voting_treasury_pda": challenge.voting_treasury_pda.to_string(),
+    })
+}
+
+// The judge panel and dispute state are nested, which also keeps
+// `challenge` under json!'s recursion limit
+fn judging(challenge: &Challenge) -> Value {
+    json!({
+        "judging_mode": judging_mode(challenge.judging_mode),
+        "judges": challenge.judges.iter().map(|j| j.to_string()).collect::<Vec<_>>(),
+        "judge_weight_bps": challenge.judge_weight_bps,
+        "judge_fee_bps": challenge.judge_fee_bps,
+        "judge_fee_pool": challenge.judge_fee_pool,
+        "judge_fees_owed": challenge.judge_fees_owed(),
+    })
+}
+
+fn disputes(challenge: &Challenge) -> Value {
+    json!({
+        "results_proposed": challenge.results_proposed,
+        "dispute_end": challenge.dispute_end,
+        "dispute_bond": challenge.dispute_bond,
+        "open_disputes": challenge.open_disputes,
+        "locked_bonds": challenge.locked_bonds,
+    })
+}
+
+fn sponsorship(challenge: &Challenge) -> Value {
+    json!({
+        "sponsored": challenge.sponsored,
+        "sponsors": challenge.sponsors.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+    })
+}
+
+fn prize(challenge: &Challenge) -> Value {
+    json!({
+        "kind": prize_kind(challenge.prize_kind),
+        "mint": (challenge.prize_kind == PrizeKind::Token).then(|| challenge.prize_mint.to_string()),
+        "escrowed": challenge.prize_pool,
+    })
+}
+
+fn reputation(challenge: &Challenge) -> Value {
+    json!({
+        "min_participant": challenge.min_participant_reputation,
+        "min_voter": challenge.min_voter_reputation,
+    })
+}
+
+fn tournament_round(challenge: &Challenge) -> Value {
+    if !challenge.is_tournament_round() {
+        return Value::Null;
+    }
+    json!({
+        "tournament": challenge.tournament.to_string(),
+        "round": challenge.tournament_round,
+        "fees_to_tournament": challenge.fees_to_tournament,
+    })
+}
+
+fn duel(challenge: &Challenge) -> Value {
+    if !challenge.is_duel {
+        return Value::Null;
+    }
+    json!({
+        "stake": challenge.participation_fee,
+        "opponent": (challenge.duel_opponent != Pubkey::default())
+            .then(|| challenge.duel_opponent.to_string()),
+        "accept_by": challenge.duel_accept_by,
+        "matched": challenge.duel_matched(),
+    })
+}
+
+fn badges(address: &Pubkey, challenge: &Challenge) -> Value {
+    json!({
+        "voter_badges": challenge.voter_badges,
+        "mint": pda::badge_mint(address).0.to_string(),
+    })
+}
+
+// The places a settled challenge mints trophies for, and their mints
+fn trophies(address: &Pubkey, challenge: &Challenge) -> Value {
+    if challenge.is_active || challenge.is_cancelled {
+        return Value::Null;
+    }
+    let places = payout::trophy_places(challenge).unwrap_or_default();
+    (1..)
+        .zip(places)
+        .map(|(rank, (submission, _))| {
+            json!({
+                "rank": rank,
+                "winner": challenge.submitter_of(&submission).map(|w| w.to_string()),
+                "mint": pda::trophy_mint(address, rank).0.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub fn status(challenge: &Challenge) -> &'static str {
+    if challenge.is_frozen {
+        "frozen"
+    } else if challenge.is_cancelled {
+        "cancelled"
+    } else if challenge.is_active {
+        "active"
+    } else {
+        "finalized"
+    }
+}
+
+pub fn voting_mode(mode: VotingMode) -> &'static str {
+    match mode {
+        VotingMode::Flat => "flat",
+        VotingMode::StakeWeighted => "stake_weighted",
+        VotingMode::Quadratic => "quadratic",
+    }
+}
+
+pub fn prize_kind(kind: PrizeKind) -> &'static str {
+    match kind {
+        PrizeKind::Cpt => "cpt",
+        PrizeKind::Token => "token",
+        PrizeKind::Sol => "sol",
+    }
+}
+
+pub fn judging_mode(mode: JudgingMode) -> &'static str {
+    match mode {
+        JudgingMode::PublicVote => "public_vote",
+        JudgingMode::Mean => "mean",
+        JudgingMode::Median => "median",
+    }
+}
+
+pub fn participants(challenge: &Challenge) -> Value {
+    challenge
+        .participants
+        .iter()
+        .map(|p| Value::String(p.to_string()))
+        .collect()
+}
+
+pub fn votes(challenge: &Challenge) -> Value {
+    challenge
+        .voters
+        .iter()
+        .enumerate()
+        .map(|(i, (voter, submission))| {
+            json!({
+                "voter": voter.to_string(),
+                "submission": submission.to_string(),
+                "weight": challenge.vote_weight(i),
+            })
+        })
+        .collect()
+}
+
+pub fn submissions(challenge: &Challenge) -> Value {
+    let mut ranked = challenge.submission_votes.clone();
+    ranked.sort_by_key(|(_, votes)| std::cmp::Reverse(*votes));
+    ranked
+        .iter()
+        .enumerate()
+        .map(|(i, (submission, votes))| {
+            json!({
+                "rank": i + 1,
+                "submission": submission.to_string(),
+                "submitter": challenge.submitter_of(submission).map(|s| s.to_string()),
+                "votes": votes,
+                "scores": challenge.scores_for(submission),
+                "disqualified": challenge.is_disqualified(submission),
+                "is_winner": challenge.winner == Some(*submission),
+            })
+        })
+        .collect()
+}
+
+pub fn audit(report: &AuditReport) -> Value {
+    json!({
+        "challenge": report.challenge.to_string(),
+        "clean": report.is_clean(),
+        "is_frozen": report.is_frozen,
+        "ledger_treasury": report.ledger_treasury,
+        "actual_treasury": report.actual_treasury,
+        "ledger_voting_treasury": report.ledger_voting_treasury,
+        "actual_voting_treasury": report.actual_voting_treasury,
+        "treasury_surplus": report.treasury_surplus,
+        "voting_treasury_surplus": report.voting_treasury_surplus,
+    })
+}
+
+pub fn finalization_preview(preview: &FinalizationPreview) -> Value {
+    let places: Vec<Value> = preview
+        .places
+        .iter()
+        .map(|place| {
+            json!({
+                "submission": place.submission.to_string(),
+                "votes": place.votes,
+                "points": place.points,
+                "amount": place.amount,
+            })
+        })
+        .collect();
+    json!({
+        "total_reward": preview.total_reward,
+        "platform_fee": preview.platform_fee,
+        "crank_bounty": preview.crank_bounty,
+        "places": places,
+        "judge_fee": preview.judge_fee,
+        "creator_remainder": preview.creator_remainder,
+        "voter_reward": voter_reward_preview(&preview.voter_reward),
+    })
+}
+
+pub fn voter_reward_preview(preview: &VoterRewardPreview) -> Value {
+    json!({
+        "winning_submission": preview.winning_submission.to_string(),
+        "voting_pool": preview.voting_pool,
+        "winning_voters": preview.winning_voters,
+        "winning_weight": preview.winning_weight,
+        "reward_per_voter": preview.reward_per_voter,
+    })
+}
+
+pub fn fee_tracker(address: &Pubkey, tracker: &FeeTracker) -> Value {
+    json!({
+        "address": address.to_string(),
+        "authority": tracker.authority.to_string(),
+        "total_participation_fees": tracker.total_participation_fees,
+        "total_voting_fees": tracker.total_voting_fees,
+        "total_challenges": tracker.total_challenges,
+    })
+}
+
+pub fn user_profile(address: &Pubkey, profile: &UserProfile) -> Value {
+    json!({
+        "address": address.to_string(),
+        "wallet": profile.wallet.to_string(),
+        "challenges_created": profile.challenges_created,
+        "challenges_joined": profile.challenges_joined,
+        "challenges_won": profile.challenges_won,
+        "votes_cast": profile.votes_cast,
+        "cpt_earned": profile.cpt_earned,
+        "fees_paid": profile.fees_paid,
+        "reputation": profile.reputation,
+        "created_at": profile.created_at,
+    })
+}
+
+pub fn challenge_tracker(address: &Pubkey, tracker: &ChallengeTracker) -> Value {
+    json!({
+        "address": address.to_string(),
+        "authority": tracker.authority.to_string(),
+        "total_challenges": tracker.total_challenges,
+    })
+}
+
+pub fn crank_config(address: &Pubkey, config: &CrankConfig) -> Value {
+    json!({
+        "address": address.to_string(),
+        "authority": config.authority.to_string(),
+        "finalize_bounty_bps": config.finalize_bounty_bps,
+    })
+}
+
+pub fn season(address: &Pubkey, season: &Season) -> Value {
+    json!({
+        "address": address.to_string(),
+        "id": season.id,
+        "start_time": season.start_time,
+        "end_time": season.end_time,
+        "pool_share_bps": season.pool_share_bps,
+        "payout_places": season.payout_places,
+        "prize_pool": season.prize_pool,
+        "settled": season.settled,
+        "leaderboard": season
+            .leaderboard
+            .iter()
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| json!({ "wallet": entry.wallet.to_string(), "points": entry.points }))
+            .collect::<Vec<_>>(),
+        "prizes": season_prizes(&payout::plan_season_prizes(season)),
+    })
+}
+
+pub fn season_prizes(prizes: &[SeasonPrize]) -> Value {
+    prizes
+        .iter()
+        .map(|prize| {
+            json!({
+                "wallet": prize.wallet.to_string(),
+                "points": prize.points,
+                "amount": prize.amount,
+            })
+        })
+        .collect()
+}
+
+pub fn team(address: &Pubkey, team: &Team) -> Value {
+    json!({
+        "address": address.to_string(),
+        "challenge": team.challenge.to_string(),
+        "captain": team.captain.to_string(),
+        "members": team
+            .members
+            .iter()
+            .zip(&team.shares_bps)
+            .zip(&team.paid)
+            .map(|((member, share_bps), paid)| {
+                json!({ "wallet": member.to_string(), "share_bps": share_bps, "paid": paid })
+            })
+            .collect::<Vec<_>>(),
+        "entered": team.entered,
+        "submission": team.submission.map(|s| s.to_string()),
+        "token_account": pda::token_account(address, &coinpetitive_client::cpt_mint()).to_string(),
+    })
+}
+
+pub fn tournament(address: &Pubkey, tournament: &Tournament) -> Value {
+    json!({
+        "address": address.to_string(),
+        "creator": tournament.creator.to_string(),
+        "id": tournament.id,
+        "round_count": tournament.round_count,
+        "advance_count": tournament.advance_count,
+        "rounds": tournament.rounds.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+        "current_round": tournament.current_round,
+        "qualified": tournament.qualified.iter().map(|w| w.to_string()).collect::<Vec<_>>(),
+        "prize": tournament.prize,
+        "roll_fees": tournament.roll_fees,
+        "escrow": pda::tournament_token_account(address).to_string(),
+        "finished": tournament.finished,
+        "winner": tournament.winner.map(|w| w.to_string()),
+    })
+}
+
+pub fn dispute_config(address: &Pubkey, config: &DisputeConfig) -> Value {
+    json!({
+        "address": address.to_string(),
+        "authority": config.authority.to_string(),
+        "bond": config.bond,
+        "window": config.window,
+    })
+}
+
+pub fn render(format: OutputFormat, value: &Value) -> String {
+    match format {
+        OutputFormat::Json => serde_json::to_string_pretty(value).unwrap_or_default(),
+        OutputFormat::Human => {
+            let mut out = String::new();
+            render_human(value, 0, &mut out);
+            out.trim_end().to_string()
+        }
+    }
+}
+
+fn render_human(value: &Value, indent: usize, out: &mut String) {
+    let pad = "  ".repeat(indent);
+    match value {
+        Value::Object(map) => render_object(map, indent, out),
+        Value::Array(items) if items.is_empty() => out.push_str(&format!("{pad}(none)\n")),
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                match item {
+                    Value::Object(map) => {
+                        out.push_str(&format!("{pad}[{}]\n", i + 1));
+                        render_object(map, indent + 1, out);
+                    }
+                    other => out.push_str(&format!("{pad}{}\n", scalar(other))),
+                }
+            }
+        }
+        other => out.push_str(&format!("{pad}{}\n", scalar(other))),
+    }
+}
+
+fn render_object(map: &Map<String, Value>, indent: usize, out: &mut String) {
+    let pad = "  ".repeat(indent);
+    let width = map.keys().map(|key| key.len() + 1).max().unwrap_or(0);
+    for (key, value) in map {
+        match value {
+            Value::Object(_) | Value::Array(_) => {
+                out.push_str(&format!("{pad}{key}:\n"));
+                render_human(value, indent + 1, out);
+            }
+            other => {
+                let label = format!("{key}:");
+                out.push_str(&format!("{pad}{label:<width$} {}\n", scalar(other)));
+            }
+        }
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,299 @@
+use clap::{CommandFactory, Parser};
+use coinpetitive_client::{pda, Challenge, PrizeKind, Season, Team, Tournament};
+use cptctl::cli::{Cli, Command, OutputFormat, Reason};
+use cptctl::output;
+use solana_sdk::pubkey::Pubkey;
+
+#[test]
+fn cli_definition_is_valid() {
+    Cli::command().debug_assert();
+}
+
+#[test]
+fn finalize_still_settles_on_a_local_validator() {
+    let challenge = Pubkey::new_unique();
+    let winner = Pubkey::new_unique();
+    let cli = Cli::try_parse_from([
+        "cptctl",
+        "finalize",
+        &challenge.to_string(),
+        "--winner-token-account",
+        &winner.to_string(),
+        "--dry-run",
+        "-o",
+        "json",
+    ])
+    .unwrap();
+
+    assert_eq!(cli.url, "http://127.0.0.1:8899");
+    assert_eq!(cli.output, OutputFormat::Json);
+    match cli.command {
+        Command::Settle {
+            challenge: c,
+            winner_token_account,
+            runner_up_token_account,
+            dry_run,
+            ..
+        } => {
+            assert_eq!(c, challenge);
+            assert_eq!(winner_token_account, winner);
+            assert_eq!(runner_up_token_account, None);
+            assert!(dry_run);
+        }
+        other => panic!("parsed {other:?}"),
+    }
+}
+
+#[test]
+fn disqualify_takes_a_reason() {
+    let (challenge, submission) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let cli = Cli::try_parse_from([
+        "cptctl",
+        "disqualify",
+        &challenge.to_string(),
+        "--submission",
+        &submission.to_string(),
+        "--reason",
+        "plagiarism",
+    ])
+    .unwrap();
+    assert!(matches!(
+        cli.command,
+        Command::Disqualify {
+            reason: Reason::Plagiarism,
+            ..
+        }
+    ));
+
+    assert!(Cli::try_parse_from([
+        "cptctl",
+        "disqualify",
+        &challenge.to_string(),
+        "--submission",
+        &submission.to_string(),
+        "--reason",
+        "boring",
+    ])
+    .is_err());
+}
+
+#[test]
+fn invalid_pubkeys_are_rejected() {
+    assert!(Cli::try_parse_from(["cptctl", "challenge", "not-a-key"]).is_err());
+}
+
+#[test]
+fn refunds_cover_each_owed_wallet_once() {
+    let (a, b, c) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let submission = Pubkey::new_unique();
+    let challenge = Challenge {
+        participants: vec![a, b],
+        voters: vec![(b, submission), (c, submission), (c, Pubkey::new_unique())],
+        ..Default::default()
+    };
+
+    assert_eq!(challenge.refundable_wallets(), vec![a, b, c]);
+}
+
+#[test]
+fn submissions_are_ranked_by_votes() {
+    let (low, high) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let challenge = Challenge {
+        submission_votes: vec![(low, 1), (high, 4)],
+        winner: Some(high),
+        ..Default::default()
+    };
+
+    let ranked = output::submissions(&challenge);
+    assert_eq!(ranked[0]["submission"], high.to_string());
+    assert_eq!(ranked[0]["rank"], 1);
+    assert_eq!(ranked[0]["is_winner"], true);
+    assert_eq!(ranked[1]["votes"], 1);
+}
+
+#[test]
+fn human_output_aligns_keys_in_order() {
+    let address = Pubkey::new_unique();
+    let challenge = Challenge {
+        is_active: true,
+        reward: 1_000,
+        ..Default::default()
+    };
+
+    let rendered = output::render(
+        OutputFormat::Human,
+        &output::challenge_summary(&address, &challenge),
+    );
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines[0], format!("address:          {address}"));
+    assert_eq!(lines[2], "status:           active");
+    assert_eq!(lines[3], "reward:           1000");
+}
+
+#[test]
+fn human_output_marks_empty_lists_and_nulls() {
+    let rendered = output::render(
+        OutputFormat::Human,
+        &serde_json::json!({ "created": [], "signature": null }),
+    );
+    assert_eq!(rendered, "created:\n  (none)\nsignature: -");
+}
+
+#[test]
+fn only_token_prizes_show_their_mint() {
+    let address = Pubkey::new_unique();
+    let mut challenge = Challenge {
+        prize_kind: PrizeKind::Sol,
+        prize_mint: Pubkey::new_unique(),
+        prize_pool: 5_000,
+        ..Default::default()
+    };
+
+    let shown = output::challenge(&address, &challenge);
+    assert_eq!(shown["prize"]["kind"], "sol");
+    assert!(shown["prize"]["mint"].is_null());
+    assert_eq!(shown["prize"]["escrowed"], 5_000);
+
+    challenge.prize_kind = PrizeKind::Token;
+    let shown = output::challenge(&address, &challenge);
+    assert_eq!(shown["prize"]["mint"], challenge.prize_mint.to_string());
+}
+
+#[test]
+fn trophies_go_to_the_placed_wallets() {
+    let address = Pubkey::new_unique();
+    let (top, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (top_wallet, second_wallet) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut challenge = Challenge {
+        submitters: vec![(top, top_wallet), (second, second_wallet)],
+        ..Default::default()
+    };
+    for _ in 0..2 {
+        challenge.add_vote(Pubkey::new_unique(), top).unwrap();
+    }
+    challenge.add_vote(Pubkey::new_unique(), second).unwrap();
+
+    let shown = output::challenge(&address, &challenge);
+    assert_eq!(shown["trophies"].as_array().unwrap().len(), 2);
+    assert_eq!(shown["trophies"][0]["winner"], top_wallet.to_string());
+    assert_eq!(shown["trophies"][1]["winner"], second_wallet.to_string());
+    assert_eq!(
+        shown["trophies"][1]["mint"],
+        pda::trophy_mint(&address, 2).0.to_string()
+    );
+
+    // A duel's loser placed second but gets no trophy
+    challenge.is_duel = true;
+    let shown = output::challenge(&address, &challenge);
+    assert_eq!(shown["trophies"].as_array().unwrap().len(), 1);
+
+    // A cancelled challenge has no places at all
+    challenge.is_cancelled = true;
+    assert!(output::challenge(&address, &challenge)["trophies"].is_null());
+}
+
+#[test]
+fn badges_show_the_challenges_mint() {
+    let address = Pubkey::new_unique();
+    let challenge = Challenge {
+        voter_badges: true,
+        ..Default::default()
+    };
+
+    let shown = output::challenge(&address, &challenge);
+    assert_eq!(
+        shown["badges"]["mint"],
+        pda::badge_mint(&address).0.to_string()
+    );
+    assert_eq!(shown["badges"]["voter_badges"], true);
+}
+
+#[test]
+fn seasons_show_filled_places_and_their_prizes() {
+    let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut season = Season {
+        payout_places: 3,
+        prize_pool: 1_000,
+        ..Default::default()
+    };
+    season.record(second, 1);
+    season.record(first, 2);
+
+    let shown = output::season(&Pubkey::new_unique(), &season);
+    assert_eq!(shown["leaderboard"].as_array().unwrap().len(), 2);
+    assert_eq!(shown["leaderboard"][0]["wallet"], first.to_string());
+
+    // The rounding dust goes to first place
+    assert_eq!(shown["prizes"][0]["amount"], 667);
+    assert_eq!(shown["prizes"][1]["amount"], 333);
+}
+
+#[test]
+fn tournaments_show_their_escrow_and_qualifiers() {
+    let address = Pubkey::new_unique();
+    let qualifier = Pubkey::new_unique();
+    let tournament = Tournament {
+        round_count: 2,
+        current_round: 1,
+        qualified: vec![qualifier],
+        prize: 500,
+        ..Default::default()
+    };
+
+    let shown = output::tournament(&address, &tournament);
+    assert_eq!(
+        shown["escrow"],
+        pda::tournament_token_account(&address).to_string()
+    );
+    assert_eq!(shown["qualified"][0], qualifier.to_string());
+    assert!(shown["winner"].is_null());
+}
+
+#[test]
+fn teams_list_each_member_with_their_share_and_payment() {
+    let (captain, member) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let team = Team {
+        captain,
+        members: vec![captain, member],
+        shares_bps: vec![6_000, 4_000],
+        paid: vec![true, false],
+        ..Default::default()
+    };
+
+    let shown = output::team(&Pubkey::new_unique(), &team);
+    assert_eq!(shown["members"][0]["wallet"], captain.to_string());
+    assert_eq!(shown["members"][0]["paid"], true);
+    assert_eq!(shown["members"][1]["share_bps"], 4_000);
+    assert_eq!(shown["members"][1]["paid"], false);
+    assert_eq!(shown["entered"], false);
+}
+
+#[test]
+fn duels_show_whether_they_are_open_and_matched() {
+    let address = Pubkey::new_unique();
+    let mut challenge = Challenge {
+        is_duel: true,
+        participation_fee: 1_000,
+        participants: vec![Pubkey::new_unique()],
+        duel_accept_by: 1_700_000_000,
+        ..Default::default()
+    };
+
+    let shown = output::challenge(&address, &challenge);
+    assert_eq!(shown["duel"]["stake"], 1_000);
+    assert!(shown["duel"]["opponent"].is_null());
+    assert_eq!(shown["duel"]["matched"], false);
+
+    challenge.participants.push(Pubkey::new_unique());
+    assert_eq!(
+        output::challenge(&address, &challenge)["duel"]["matched"],
+        true
+    );
+
+    challenge.is_duel = false;
+    assert!(output::challenge(&address, &challenge)["duel"].is_null());
+}
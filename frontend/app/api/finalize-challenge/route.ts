@@ -1,9 +1,11 @@
+import { createHash } from "crypto";
 import { NextResponse } from "next/server";
 import {
   Connection,
   Keypair,
   PublicKey,
   Transaction,
+  TransactionInstruction,
   sendAndConfirmTransaction,
   SystemProgram,
   LAMPORTS_PER_SOL,
@@ -23,6 +25,67 @@ const connection = new Connection(process.env.NEXT_PUBLIC_RPC_URL as string, {
   commitment: "confirmed",
 });
 
+// Anchor's instruction discriminator, for instructions without arguments
+const instructionDiscriminator = (name: string) =>
+  createHash("sha256").update(`global:${name}`).digest().subarray(0, 8);
+
+// The submissions settle_results pays, winner first, as the program ranks
+// them. preview_finalization returns a borsh FinalizationPreview: three u64s,
+// then the places as (submission, votes, points, amount)
+const placedSubmissionsOf = async (
+  programId: PublicKey,
+  challenge: PublicKey,
+  payer: Keypair,
+) => {
+  const [crankConfig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("crank_config")],
+    programId,
+  );
+  const previewIx = new TransactionInstruction({
+    programId,
+    keys: [
+      { pubkey: challenge, isSigner: false, isWritable: false },
+      { pubkey: crankConfig, isSigner: false, isWritable: false },
+    ],
+    data: instructionDiscriminator("preview_finalization"),
+  });
+  const tx = new Transaction().add(previewIx);
+  tx.feePayer = payer.publicKey;
+  const { value } = await connection.simulateTransaction(tx, [payer]);
+  if (value.err || !value.returnData) {
+    throw new Error(`preview_finalization failed: ${JSON.stringify(value.err)}`);
+  }
+  const data = Buffer.from(value.returnData.data[0], "base64");
+  const count = data.readUInt32LE(24);
+  return Array.from({ length: count }, (_, i) => {
+    const offset = 28 + i * 56;
+    return new PublicKey(data.subarray(offset, offset + 32));
+  });
+};
+
+// The wallet behind each submission, read from the challenge account's
+// submitters field by walking the fields stored before it
+const submittersOf = (data: Buffer) => {
+  let offset = 8 + 32 + 1 + 8 * 5; // discriminator, creator, is_active, amounts
+  offset += data[offset] === 1 ? 33 : 1; // winner
+  offset += 8 + 8 + 32; // total_votes, winning_votes, reward_token_mint
+  offset += 4 + 32 * data.readUInt32LE(offset); // participants
+  offset += 1; // max_participants
+  offset += 4 + 40 * data.readUInt32LE(offset); // submission_votes
+  offset += 4 + 64 * data.readUInt32LE(offset); // voters
+  offset += 32 + 32 + 8 + 1 + 1 + 8 + 8 + 1; // treasury .. submissions_closed
+  const count = data.readUInt32LE(offset);
+  offset += 4;
+  const submitters = new Map<string, PublicKey>();
+  for (let i = 0; i < count; i++, offset += 64) {
+    submitters.set(
+      new PublicKey(data.subarray(offset, offset + 32)).toBase58(),
+      new PublicKey(data.subarray(offset + 32, offset + 64)),
+    );
+  }
+  return submitters;
+};
+
 export async function POST(req: Request) {
   const supabase = await createClient();
 
@@ -81,11 +144,6 @@ export async function POST(req: Request) {
 
     const challengePubkey = new PublicKey(challengePublicKey);
 
-    const [adminPDA] = await PublicKey.findProgramAddressSync(
-      [Buffer.from("admin_panel")],
-      program.programId,
-    );
-
     const challengeAccountInfo =
       await connection.getAccountInfo(challengePubkey);
     if (!challengeAccountInfo) {
@@ -107,13 +165,49 @@ export async function POST(req: Request) {
       });
     }
 
-    if (challenges?.[0].state !== 'active') {
+    const state = challenges?.[0].state;
+    if (state !== 'active' && state !== 'proposed') {
       return NextResponse.json({
         success: false,
         error: "Challenge is not active. Someone is in finalizing or refunding now.",
       });
     }
 
+    // Finalizing takes two calls: the first proposes the results and opens
+    // the dispute window, the second settles them once the window has passed
+    if (state === 'active') {
+      const [disputeConfigPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("dispute_config")],
+        program.programId,
+      );
+      const proposeIx = new TransactionInstruction({
+        programId: program.programId,
+        keys: [
+          { pubkey: adminKeypair.publicKey, isSigner: true, isWritable: false },
+          { pubkey: challengePubkey, isSigner: false, isWritable: true },
+          { pubkey: disputeConfigPDA, isSigner: false, isWritable: false },
+        ],
+        data: instructionDiscriminator("propose_results"),
+      });
+      const sig = await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(proposeIx),
+        [adminKeypair],
+      );
+      console.log("Proposed results");
+      console.log("sig:", sig);
+
+      const { error } = await supabase
+        .from("challenges")
+        .update({ state: "proposed" })
+        .eq("onchain_id", challengePublicKey);
+      if (error) {
+        console.log(error);
+      }
+
+      return NextResponse.json({ success: true, state: "proposed" });
+    }
+
     // Update Supabase challenge status
     const { error: pendingError } = await supabase
       .from("challenges")
@@ -218,12 +312,6 @@ export async function POST(req: Request) {
 
     console.log("topVoterIds:", topVoterIds);
 
-    const topSubmissions = submissions.filter((s) =>
-      topSubmissionIds.includes(s.id),
-    );
-
-    console.log("topSubmissions:", JSON.stringify(topSubmissions));
-
     const { data: winnerUsers } = await supabase
       .from("users")
       .select("id, username, pubkey, avatar")
@@ -231,140 +319,183 @@ export async function POST(req: Request) {
 
     console.log("winnerUsers:", winnerUsers);
 
-    for (const winnerSubmission of topSubmissions) {
-      if (!winnerSubmission.participant_id) {
-        return NextResponse.json({ success: false, error: "Winner's wallet address not found" });
-      }
-    }
-
-    const participantIds = topSubmissions
-      .map((s) => s.participant_id)
-      .filter((id): id is string => !!id);
+    // settle_results pays both places at once and checks them against its own
+    // ranking, which weighs stakes, judges' scores and revealed votes that
+    // the Supabase counts don't see, so the places come from the program
+    const placedSubmissions = await placedSubmissionsOf(
+      program.programId,
+      challengePubkey,
+      adminKeypair,
+    );
+    const submitters = submittersOf(
+      (await connection.getAccountInfo(challengePubkey))!.data,
+    );
+    const placedWallets = placedSubmissions
+      .map((s) => submitters.get(s.toBase58()))
+      .filter((wallet): wallet is PublicKey => !!wallet);
 
-    if (participantIds.length === 0) {
+    if (placedWallets.length !== placedSubmissions.length || placedWallets.length === 0) {
       return NextResponse.json({
         success: false,
         error: "No winner wallet addresses found",
       });
     }
 
-    const { data: topWinners } = await supabase
-      .from("users")
-      .select("*")
-      .in("id", participantIds);
-
-    const finalizeIx = await program.methods
-      .finalizeChallenge(new BN(topWinners.length))
-      .accounts({
-        authority: adminKeypair.publicKey,
-        challenge: challengePubkey,
-        // @ts-expect-error - adminPanel is valid but not recognized by inferred type
-        adminPanel: adminPDA,
-        tokenMint: CPT_TOKEN_MINT,
-        tokenProgram2022: TOKEN_2022_PROGRAM_ID,
-        treasury: treasuryPubkey,
-        treasuryTokenAccount,
-        platformTreasuryTokenAccount: new PublicKey(
-          // "BEcHbgDvaDukTCpoDtz1fpG4B7X9FvFshzZ3MJPyRDzf", //Mainnet CPT Token Account
-          "J3yCxeNeAiCCcCg8g9yd4DHYQykoQ3PqLrd38rTjWLnC", // Mainnet Update CPT Token
-          // "FKTCHhbcjhLSJoXZA96HJnGJccBtuBJS8kDU3JJWbzg9" //Devnet CPT Token Account
-        ),
-        creatorTokenAccount,
-      })
-      .instruction();
-    const finalizeTx = new Transaction().add(finalizeIx);
-
-    const sig = await sendAndConfirmTransaction(connection, finalizeTx, [
-      adminKeypair,
-    ]);
-    console.log("Finalized challenge");
-    console.log("sig:", sig);
-
-    // await Promise.all(
-    //   topWinners.map(async (topWinner, i) => {
-
-    for (let i = 0; i < topWinners.length; i++) {
-      const topWinner = topWinners[i];
-      console.log(
-        `Processing winner ${i + 1}/${topWinners.length
-        }: (${topWinner.pubkey.toString().substring(0, 8)}...)`,
-      );
-
-      const winnerPubkey = new PublicKey(topWinner.pubkey);
-      const winnerTokenAccount = getAssociatedTokenAddressSync(
+    const tokenAccountOf = (wallet: PublicKey) =>
+      getAssociatedTokenAddressSync(
         CPT_TOKEN_MINT,
-        winnerPubkey,
+        wallet,
         true,
         TOKEN_2022_PROGRAM_ID,
       );
-      const winnerTokenAccountInfo =
-        await connection.getAccountInfo(winnerTokenAccount);
-
-      if (!winnerTokenAccountInfo) {
-        const ix = createAssociatedTokenAccountInstruction(
-          adminKeypair.publicKey,
-          winnerTokenAccount,
-          winnerPubkey,
-          CPT_TOKEN_MINT,
-          TOKEN_2022_PROGRAM_ID,
-        );
-        const tx = new Transaction().add(ix);
-        const sig = await sendAndConfirmTransaction(connection, tx, [
-          adminKeypair,
-        ]);
 
-        console.log("Created token account for winner");
-        console.log("sig:", sig);
+    // Every account settle_results pays into has to exist beforehand
+    for (const [wallet, label] of [
+      ...placedWallets.map((w) => [w, "winner"] as const),
+      [creatorPubkey, "creator"] as const,
+      [adminKeypair.publicKey, "cranker"] as const,
+    ]) {
+      const tokenAccount = tokenAccountOf(wallet);
+      if (await connection.getAccountInfo(tokenAccount)) {
+        continue;
       }
+      const ix = createAssociatedTokenAccountInstruction(
+        adminKeypair.publicKey,
+        tokenAccount,
+        wallet,
+        CPT_TOKEN_MINT,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      const tx = new Transaction().add(ix);
+      const sig = await sendAndConfirmTransaction(connection, tx, [
+        adminKeypair,
+      ]);
+      console.log(`Created token account for ${label}`);
+      console.log("sig:", sig);
+    }
 
-      const creatorTokenAccountInfo =
-        await connection.getAccountInfo(creatorTokenAccount);
+    const platformTreasuryTokenAccount = new PublicKey(
+      // "BEcHbgDvaDukTCpoDtz1fpG4B7X9FvFshzZ3MJPyRDzf", //Mainnet CPT Token Account
+      "J3yCxeNeAiCCcCg8g9yd4DHYQykoQ3PqLrd38rTjWLnC", // Mainnet Update CPT Token
+      // "FKTCHhbcjhLSJoXZA96HJnGJccBtuBJS8kDU3JJWbzg9" //Devnet CPT Token Account
+    );
 
-      console.log(creatorTokenAccountInfo);
+    const [challengeTrackerPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("challenge_tracker")],
+      program.programId,
+    );
+    const [crankConfigPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("crank_config")],
+      program.programId,
+    );
+    const [seasonConfigPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("season_config")],
+      program.programId,
+    );
+    const profileOf = (wallet: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), wallet.toBuffer()],
+        program.programId,
+      )[0];
+
+    // Anchor takes the program id in place of an optional account left out
+    const optional = (pubkey: PublicKey | null) => ({
+      pubkey: pubkey ?? program.programId,
+      isSigner: false,
+      isWritable: !!pubkey,
+    });
 
-      if (!creatorTokenAccountInfo) {
-        const ix = createAssociatedTokenAccountInstruction(
-          adminKeypair.publicKey,
-          creatorTokenAccount,
-          creatorPubkey,
+    // Settlement has to credit the season running at the time, if any
+    let season: PublicKey | null = null;
+    let seasonTokenAccount: PublicKey | null = null;
+    const seasonConfigInfo = await connection.getAccountInfo(seasonConfigPDA);
+    if (seasonConfigInfo) {
+      const data = seasonConfigInfo.data;
+      const currentSeason = data.readBigUInt64LE(40);
+      const now = BigInt(
+        (await connection.getBlockTime(await connection.getSlot())) ?? 0,
+      );
+      if (
+        currentSeason > BigInt(0) &&
+        data.readBigInt64LE(48) <= now &&
+        now < data.readBigInt64LE(56)
+      ) {
+        const id = Buffer.alloc(8);
+        id.writeBigUInt64LE(currentSeason);
+        [season] = PublicKey.findProgramAddressSync(
+          [Buffer.from("season"), id],
+          program.programId,
+        );
+        seasonTokenAccount = getAssociatedTokenAddressSync(
           CPT_TOKEN_MINT,
+          season,
+          true,
           TOKEN_2022_PROGRAM_ID,
         );
-
-        const tx = new Transaction().add(ix);
-        const sig = await sendAndConfirmTransaction(connection, tx, [
-          adminKeypair,
-        ]);
-        console.log("Created token account for creator");
-        console.log("sig:", sig);
       }
+    }
 
-      const distributeRewardIx = await program.methods
-        .distributeReward()
-        .accounts({
-          authority: adminKeypair.publicKey,
-          challenge: challengePubkey,
-          // @ts-expect-error - adminPanel is valid but not recognized by inferred type
-          adminPanel: adminPDA,
-          tokenProgram: TOKEN_2022_PROGRAM_ID,
-          winnerTokenAccount: winnerTokenAccount,
-          treasury: treasuryPubkey,
-          treasuryTokenAccount,
-          tokenMint: CPT_TOKEN_MINT,
-          tokenProgram2022: TOKEN_2022_PROGRAM_ID,
-        })
-        .instruction();
-      const distributeRewardTx = new Transaction().add(distributeRewardIx);
+    const [winnerPubkey, runnerUpPubkey] = placedWallets;
+    const winnerTokenAccount = tokenAccountOf(winnerPubkey);
+    const runnerUpTokenAccount = tokenAccountOf(runnerUpPubkey ?? winnerPubkey);
+    const hasProfile = async (wallet: PublicKey) =>
+      !!(await connection.getAccountInfo(profileOf(wallet)));
+    const winnerProfile = (await hasProfile(winnerPubkey))
+      ? profileOf(winnerPubkey)
+      : null;
+    // A winner who also placed second is credited on one profile
+    const runnerUpProfile =
+      runnerUpPubkey &&
+      !runnerUpPubkey.equals(winnerPubkey) &&
+      (await hasProfile(runnerUpPubkey))
+        ? profileOf(runnerUpPubkey)
+        : null;
+
+    // The bundled IDL predates settle_results, so the instruction is built
+    // by hand in the program's account order
+    const settleIx = new TransactionInstruction({
+      programId: program.programId,
+      keys: [
+        { pubkey: adminKeypair.publicKey, isSigner: true, isWritable: true },
+        { pubkey: challengePubkey, isSigner: false, isWritable: true },
+        { pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: winnerTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: runnerUpTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: treasuryPubkey, isSigner: false, isWritable: true },
+        { pubkey: treasuryTokenAccount, isSigner: false, isWritable: true },
+        {
+          pubkey: platformTreasuryTokenAccount,
+          isSigner: false,
+          isWritable: true,
+        },
+        // A CPT prize is held in the treasury token account itself
+        { pubkey: treasuryTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: creatorTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: creatorPubkey, isSigner: false, isWritable: false },
+        { pubkey: challengeTrackerPDA, isSigner: false, isWritable: true },
+        { pubkey: crankConfigPDA, isSigner: false, isWritable: false },
+        {
+          pubkey: tokenAccountOf(adminKeypair.publicKey),
+          isSigner: false,
+          isWritable: true,
+        },
+        optional(winnerProfile),
+        optional(runnerUpProfile),
+        { pubkey: seasonConfigPDA, isSigner: false, isWritable: false },
+        optional(season),
+        optional(seasonTokenAccount),
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data: instructionDiscriminator("settle_results"),
+    });
+    const settleTx = new Transaction().add(settleIx);
 
-      const sig = await sendAndConfirmTransaction(
-        connection,
-        distributeRewardTx,
-        [adminKeypair],
-      );
-      console.log("Distributed reward for challenge");
-      console.log("sig:", sig);
-    }
-    // );
+    const sig = await sendAndConfirmTransaction(connection, settleTx, [
+      adminKeypair,
+    ]);
+    console.log("Settled challenge");
+    console.log("sig:", sig);
 
     const [votingTreasuryPubkey] = PublicKey.findProgramAddressSync(
       [Buffer.from("voting_treasury"), challengePubkey.toBuffer()],
@@ -6,9 +6,47 @@ pub const FOUNDER_ALLOCATION: u64 = 1_100_000 * 1_000_000_000; // 1.1M tokens
 pub const DEV_ALLOCATION: u64 = 500_000 * 1_000_000_000; // 500K tokens
 pub const AFFILIATOR_ALLOCATION: u64 = 500_000 * 1_000_000_000; // 500K tokens
 pub const MINT_INCREMENT: u64 = 5_000_000 * 1_000_000_000; // 5M tokens for future mints
-pub const BURN_RATE: u8 = 1; // 1% burn rate on liquidity pool transactions
+pub const BURN_RATE: u8 = coinpetitive_math::BURN_RATE; // 1% burn rate on liquidity pool transactions
 pub const MIN_TIME_BETWEEN_MINTS: i64 = 31_536_000; // 60 * 60 * 24 * 365, one year in seconds
-pub const FIXED_SUBMISSION_FEE: u64 = 5_000_000_0;
+pub const FIXED_SUBMISSION_FEE: u64 = 50_000_000;
+pub const MAX_SUBMISSIONS: usize = 20; // Matches the space reserved in create_challenge
+pub const MAX_PARTICIPANTS: u8 = 100; // Keeps create_challenge under the 10 KiB CPI init limit
+pub const DEFAULT_MAX_PARTICIPANTS: u8 = 50; // When a challenge asks for no limit
+pub const MAX_VOTERS: usize = 50; // Matches the space reserved in create_challenge
+pub const MAX_JUDGES: usize = 5; // Matches the space reserved in create_challenge
+pub const MAX_SPONSORS: usize = 10; // Matches the space reserved in create_challenge
+pub const MAX_SPONSOR_URI_LEN: usize = 200;
+pub const MAX_TEAM_MEMBERS: usize = 5; // Matches the space reserved in create_team
+pub const MAX_TOURNAMENT_ROUNDS: usize = 4; // Matches the space reserved in create_tournament
+pub const MAX_TROPHY_RANK: u8 = 2; // Trophies go to the winner and runner-up
+pub const MAX_TROPHY_SYMBOL_LEN: usize = 10; // Metaplex's limit
+pub const MAX_TROPHY_URI_LEN: usize = 140; // Leaves room for /<challenge>/<rank>.json in Metaplex's 200
+pub const TROPHY_COLLECTION_NAME: &str = "Coinpetitive Trophies";
+pub const BADGE_NAME: &str = "Coinpetitive Badge";
+pub const BADGE_SYMBOL: &str = "CPTB";
+pub const TROPHY_NAMES: [&str; MAX_TROPHY_RANK as usize] =
+    ["Coinpetitive Winner", "Coinpetitive Runner-up"];
+// Reputation every profile starts with, and what raises or lowers it
+pub const BASE_REPUTATION: u64 = 100;
+pub const REPUTATION_FOR_WIN: u64 = 50;
+pub const REPUTATION_FOR_ACCURATE_VOTE: u64 = 5; // Backed the winner
+pub const REPUTATION_FOR_COMPLETED_CHALLENGE: u64 = 20; // Creator, once settled
+pub const REPUTATION_FOR_DISQUALIFICATION: u64 = 50; // Bad-faith entries and upheld disputes
+pub const REPUTATION_FOR_CANCELLED_CHALLENGE: u64 = 20; // Creator
+pub const REPUTATION_FOR_LOST_DISPUTE: u64 = 25; // Disputer
+
+// How long the admin and judges have after dispute_end to resolve a dispute
+// before anyone can expire it and return its bond
+pub const DISPUTE_RESOLUTION_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+// Season points and how many wallets a season's leaderboard holds
+pub const SEASON_LEADERBOARD_SIZE: usize = 10;
+pub const SEASON_POINTS_FOR_WIN: u64 = 100;
+pub const SEASON_POINTS_FOR_ACCURATE_VOTE: u64 = 10; // Backed the winner
+
+// Unique-wallet counts and the mint condition each one meets
+pub const WALLET_MILESTONES: [(u64, u8); 3] = [(250_000, 4), (500_000, 5), (1_000_000, 6)];
+pub const MAX_SCORE: u8 = coinpetitive_math::MAX_SCORE; // Judges score submissions from 0 to 100
 
 // Make sure this matches exactly what you use in the client
 pub const TOKEN_MINT_SEED: &[u8] = b"cpt_token_mint";
@@ -17,10 +55,13 @@ pub const TOKEN_MINT_SEED: &[u8] = b"cpt_token_mint";
 pub const CPT_TOKEN_MINT: &str = "mntjJeXswzxFCnCY1Zs2ekEzDvBVaVdyTVFXbBHfmo9";
 pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
 pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+// Trophies are classic SPL Token NFTs, as Metaplex master editions require
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
 
 // Platform fee constants
-pub const PLATFORM_FEE_RATE: u16 = 210; // 2.1% = 210 basis points (210/10000)
-pub const BASIS_POINTS: u16 = 10000; // For percentage calculations
+pub const PLATFORM_FEE_RATE: u16 = coinpetitive_math::PLATFORM_FEE_RATE; // 2.1% = 210 basis points (210/10000)
+pub const BASIS_POINTS: u16 = coinpetitive_math::BASIS_POINTS; // For percentage calculations
 
 // Wallet addresses
 pub const FOUNDER_WALLET: &str = "FuFzoMF5xTwZego84fRoscnart4dPYNkpHho2UBe7NDt";
@@ -94,4 +94,168 @@ pub enum ErrorCode {
     ArithmeticOverflow,
     #[msg("Insufficient funds")]
     InsufficientFunds,
-}
\ No newline at end of file
+    #[msg("Challenge is frozen pending an audit")]
+    ChallengeFrozen,
+    #[msg("Challenge treasury balances cover its ledger")]
+    LedgerMatchesBalances,
+    #[msg("Challenge already has submissions")]
+    ChallengeHasSubmissions,
+    #[msg("Challenge has not been cancelled")]
+    ChallengeNotCancelled,
+    #[msg("Nothing to refund for this wallet")]
+    NothingToRefund,
+    #[msg("Participants, submitters or voters still have to be refunded")]
+    RefundsPending,
+    #[msg("Voter rewards have not been distributed yet")]
+    VoterRewardsPending,
+    #[msg("Deadlines must be in the future with submissions closing before voting")]
+    InvalidSchedule,
+    #[msg("Submissions are closed for this challenge")]
+    SubmissionsClosed,
+    #[msg("Submission period has not ended")]
+    SubmissionPeriodActive,
+    #[msg("Voting is closed for this challenge")]
+    VotingClosed,
+    #[msg("Maximum submissions reached")]
+    MaxSubmissionsReached,
+    #[msg("Challenge has enough votes to be finalized")]
+    QuorumMet,
+    #[msg("Crank bounty cannot exceed the platform fee")]
+    InvalidCrankBounty,
+    #[msg("Invalid runner-up")]
+    InvalidRunnerUp,
+    #[msg("Platform fees must go to the platform authority")]
+    InvalidPlatformTreasury,
+    #[msg("Quorum minimums must fit within the participant and voter limits")]
+    InvalidQuorum,
+    #[msg("Challenge did not reach its quorum")]
+    QuorumNotMet,
+    #[msg("Challenge does not use commit-reveal voting")]
+    CommitRevealDisabled,
+    #[msg("Votes for this challenge must be committed and revealed")]
+    CommitRevealRequired,
+    #[msg("Reveal window has closed")]
+    RevealClosed,
+    #[msg("Revealed vote does not match its commitment")]
+    InvalidReveal,
+    #[msg("Instruction is not available in this challenge's voting mode")]
+    WrongVotingMode,
+    #[msg("Invalid vote weight")]
+    InvalidVoteWeight,
+    #[msg("Voter has already been rewarded")]
+    VoterAlreadyRewarded,
+    #[msg("Voting stakes must be withdrawn first")]
+    StakesLocked,
+    #[msg("Invalid judge panel")]
+    InvalidJudgePanel,
+    #[msg("Challenge is not judged")]
+    JudgingDisabled,
+    #[msg("Signer is not a judge of this challenge")]
+    NotAJudge,
+    #[msg("Score is out of range")]
+    InvalidScore,
+    #[msg("No judge has scored a submission")]
+    NoScores,
+    #[msg("Judge fees must be claimed first")]
+    JudgeFeesPending,
+    #[msg("Results have not been proposed")]
+    ResultsNotProposed,
+    #[msg("Results have already been proposed")]
+    ResultsAlreadyProposed,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+    #[msg("Disputes must be resolved first")]
+    DisputesOpen,
+    #[msg("Submission has been disqualified")]
+    SubmissionDisqualified,
+    #[msg("Dispute window cannot be negative")]
+    InvalidDisputeConfig,
+    #[msg("Only participants can dispute results")]
+    NotAParticipant,
+    #[msg("Sponsorships close with submissions")]
+    SponsorshipClosed,
+    #[msg("Sponsorship must be more than zero")]
+    InvalidSponsorship,
+    #[msg("Sponsor metadata URI is too long")]
+    SponsorUriTooLong,
+    #[msg("Maximum sponsors reached")]
+    MaxSponsorsReached,
+    #[msg("Prize can only be funded once, before anyone joins or sponsors")]
+    PrizeLocked,
+    #[msg("Prize must be SOL or a token other than CPT")]
+    InvalidPrize,
+    #[msg("Account does not hold or receive this challenge's prize")]
+    InvalidPrizeAccount,
+    #[msg("Only challenges with a CPT prize can be sponsored")]
+    CptPrizeOnly,
+    #[msg("Trophies only go to placed submissions of a settled challenge")]
+    InvalidTrophyRank,
+    #[msg("Trophy symbol or base URI is too long")]
+    InvalidTrophyMetadata,
+    #[msg("Only participants, and voters where the creator opted in, earn a badge")]
+    NotEligibleForBadge,
+    #[msg("Wallet's reputation is below what this requires")]
+    InsufficientReputation,
+    #[msg("Profile does not belong to the expected wallet")]
+    InvalidProfile,
+    #[msg("Season account does not match the season expected")]
+    InvalidSeason,
+    #[msg("Season times, pool share or payout places are out of range")]
+    InvalidSeasonConfig,
+    #[msg("Season has not ended yet")]
+    SeasonNotOver,
+    #[msg("Season has already been settled")]
+    SeasonAlreadySettled,
+    #[msg("Tournament account does not match the challenge's tournament")]
+    InvalidTournament,
+    #[msg("Tournament round count or advancing places are out of range")]
+    InvalidTournamentConfig,
+    #[msg("Challenge can't be added as the tournament's next round")]
+    InvalidTournamentRound,
+    #[msg("This round is not the tournament's current round")]
+    TournamentRoundNotOpen,
+    #[msg("Wallet did not qualify for this tournament round")]
+    NotQualifiedForRound,
+    #[msg("Tournament has already finished")]
+    TournamentFinished,
+    #[msg("Team account does not belong to this challenge")]
+    InvalidTeam,
+    #[msg("Team needs 2 to 5 distinct members with shares adding up to 10,000 basis points")]
+    InvalidTeamConfig,
+    #[msg("Wallet is not a member of this team")]
+    NotTeamMember,
+    #[msg("This part of the team's participation fee is already paid")]
+    TeamFeeAlreadyPaid,
+    #[msg("Team has not paid its whole participation fee")]
+    TeamNotEntered,
+    #[msg("Team has already submitted its video")]
+    TeamAlreadySubmitted,
+    #[msg("Team account holds nothing to split")]
+    NothingToSplit,
+    #[msg("Duel needs a stake and an acceptance deadline before submissions close")]
+    InvalidDuel,
+    #[msg("A duel can only be opened on a fresh CPT challenge nobody has joined or funded")]
+    DuelLocked,
+    #[msg("Duel is matched or its acceptance deadline has passed")]
+    DuelClosed,
+    #[msg("Wallet is not the opponent this duel names")]
+    NotDuelOpponent,
+    #[msg("Duels only hold the two duelists' stakes")]
+    DuelStakesOnly,
+    #[msg("Each duelist submits one video, once the duel is matched")]
+    DuelVideoLimit,
+    #[msg("Challenge was cancelled and only pays refunds")]
+    ChallengeCancelled,
+    #[msg("Challenge has not been settled")]
+    ChallengeNotSettled,
+    #[msg("Disputes can still be resolved by the admin or a judge")]
+    DisputeResolutionOpen,
+    #[msg("Only the admin, or a missed quorum, can cancel a tournament round")]
+    TournamentRoundCancel,
+    #[msg("Team can still enter the challenge")]
+    TeamCanStillEnter,
+    #[msg("Members of teams that never entered have fees to be refunded")]
+    TeamFeesPending,
+}
@@ -0,0 +1,480 @@
+use crate::state::{DisqualificationReason, JudgingMode, PrizeKind, VotingMode};
+use anchor_lang::prelude::*;
+
+// Who a payout went to, for indexers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PayoutKind {
+    Winner,
+    RunnerUp,
+    Platform,
+    Creator,
+    Voter,
+    Judge,
+}
+
+// Emitted when a challenge is created
+#[event]
+pub struct ChallengeCreated {
+    pub challenge: Pubkey,
+    pub creator: Pubkey,
+    pub reward_token_mint: Pubkey,
+    pub reward: u64,
+    pub participation_fee: u64,
+    pub voting_fee: u64,
+    pub max_participants: u8,
+    pub submission_end: i64,
+    pub voting_end: i64,
+    pub min_participants: u8,
+    pub min_voters: u8,
+    pub max_voters: u8,
+    pub commit_reveal: bool,
+    pub reveal_end: i64,
+    pub voting_mode: VotingMode,
+    pub judging_mode: JudgingMode,
+    pub judges: Vec<Pubkey>,
+    pub judge_weight_bps: u16,
+    pub judge_fee_bps: u16,
+    pub voter_badges: bool,
+    pub min_participant_reputation: u64,
+    pub min_voter_reputation: u64,
+}
+
+// Emitted when the creator escrows a SOL or non-CPT token prize
+#[event]
+pub struct PrizeFunded {
+    pub challenge: Pubkey,
+    pub kind: PrizeKind,
+    pub mint: Pubkey, // Unset for SOL
+    pub amount: u64,
+}
+
+// Emitted when a wallet pays the participation fee
+#[event]
+pub struct ParticipantJoined {
+    pub challenge: Pubkey,
+    pub participant: Pubkey,
+    pub fee: u64,
+}
+
+// Emitted when a wallet adds CPT to a challenge's reward
+#[event]
+pub struct ChallengeSponsored {
+    pub challenge: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+    pub total_sponsored: u64, // Everything this sponsor has put in
+    pub reward: u64,          // The challenge's reward after the deposit
+    pub metadata_uri: Option<String>,
+}
+
+// Emitted with the new totals whenever the fee tracker changes
+#[event]
+pub struct FeeTrackerUpdated {
+    pub total_participation_fees: u64,
+    pub total_voting_fees: u64,
+    pub total_challenges: u64,
+}
+
+// Emitted when a participant submits a video
+#[event]
+pub struct VideoSubmitted {
+    pub challenge: Pubkey,
+    pub submission: Pubkey,
+    pub participant: Pubkey,
+    pub fee: u64,
+}
+
+// Emitted for every vote cast, or revealed in a commit-reveal challenge
+#[event]
+pub struct VoteCast {
+    pub challenge: Pubkey,
+    pub voter: Pubkey,
+    pub submission: Pubkey,
+    pub fee: u64,
+    pub weight: u64,
+    pub stake: u64, // Locked on top of the fee in stake-weighted challenges
+}
+
+// Emitted when a sealed vote is committed in a commit-reveal challenge
+#[event]
+pub struct VoteCommitted {
+    pub challenge: Pubkey,
+    pub voter: Pubkey,
+    pub fee: u64,
+}
+
+// Emitted when a judge scores a submission, again if they change the score
+#[event]
+pub struct SubmissionScored {
+    pub challenge: Pubkey,
+    pub judge: Pubkey,
+    pub submission: Pubkey,
+    pub score: u8,
+}
+
+// Emitted when results are proposed, and again when an upheld dispute
+// re-ranks them
+#[event]
+pub struct ResultsProposed {
+    pub challenge: Pubkey,
+    pub winning_submission: Pubkey,
+    pub runner_up: Option<Pubkey>,
+    pub dispute_end: i64,
+    pub proposed_by: Pubkey,
+}
+
+// Emitted when a participant bonds a dispute against a submission
+#[event]
+pub struct DisputeRaised {
+    pub challenge: Pubkey,
+    pub submission: Pubkey,
+    pub disputer: Pubkey,
+    pub bond: u64,
+}
+
+// Emitted when a dispute is resolved: upheld disqualifies the submission and
+// returns the bond, otherwise the bond is slashed into the challenge treasury
+#[event]
+pub struct DisputeResolved {
+    pub challenge: Pubkey,
+    pub submission: Pubkey,
+    pub disputer: Pubkey,
+    pub upheld: bool,
+    pub resolved_by: Pubkey,
+}
+
+// Emitted when a dispute nobody resolved in time is expired and its bond
+// returned, without striking the submission
+#[event]
+pub struct DisputeExpired {
+    pub challenge: Pubkey,
+    pub submission: Pubkey,
+    pub disputer: Pubkey,
+    pub bond: u64,
+}
+
+// Emitted when the admin changes the dispute bond or window
+#[event]
+pub struct DisputeConfigChanged {
+    pub authority: Pubkey,
+    pub bond: u64,
+    pub window: i64,
+}
+
+// Emitted when the admin changes the reputation needed for large challenges
+#[event]
+pub struct ReputationConfigChanged {
+    pub authority: Pubkey,
+    pub large_reward: u64,
+    pub min_creator_reputation: u64,
+}
+
+// Emitted whenever a wallet's reputation moves, with the change and the result
+#[event]
+pub struct ReputationChanged {
+    pub wallet: Pubkey,
+    pub delta: i64,
+    pub reputation: u64,
+}
+
+// Emitted when a moderator takes a submission out of the ranking
+#[event]
+pub struct SubmissionDisqualified {
+    pub challenge: Pubkey,
+    pub submission: Pubkey,
+    pub submitter: Pubkey,
+    pub moderator: Pubkey,
+    pub reason: DisqualificationReason,
+    pub fee_refunded: u64, // 0 when the fee was slashed
+}
+
+// Emitted once a challenge's winner is settled, before its payouts
+#[event]
+pub struct ChallengeFinalized {
+    pub challenge: Pubkey,
+    pub winning_submission: Pubkey,
+    pub winning_votes: u64,
+    pub finalized_by: Pubkey,
+}
+
+// Emitted for every reward paid out of a challenge's treasuries
+#[event]
+pub struct PayoutIssued {
+    pub challenge: Pubkey,
+    pub kind: PayoutKind,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+// Emitted when a challenge's treasury token balances fall short of its ledger
+#[event]
+pub struct ChallengeAuditDiscrepancy {
+    pub challenge: Pubkey,
+    pub ledger_treasury: u64,
+    pub actual_treasury: u64,
+    pub ledger_voting_treasury: u64,
+    pub actual_voting_treasury: u64,
+}
+
+// Emitted when the admin freezes or unfreezes a challenge
+#[event]
+pub struct ChallengeFrozenChanged {
+    pub challenge: Pubkey,
+    pub authority: Pubkey,
+    pub is_frozen: bool,
+}
+
+// Emitted when the submission deadline has passed and entries are closed
+#[event]
+pub struct SubmissionsClosed {
+    pub challenge: Pubkey,
+    pub submissions: u32,
+    pub participants: u32,
+}
+
+// Emitted when the admin changes the finalization bounty
+#[event]
+pub struct CrankBountyChanged {
+    pub authority: Pubkey,
+    pub finalize_bounty_bps: u16,
+}
+
+// Emitted when whoever finalized a challenge is paid their bounty
+#[event]
+pub struct CrankBountyPaid {
+    pub challenge: Pubkey,
+    pub cranker: Pubkey,
+    pub amount: u64,
+}
+
+// Emitted when the creator or admin cancels a challenge, or anyone cancels one
+// that missed its quorum
+#[event]
+pub struct ChallengeCancelled {
+    pub challenge: Pubkey,
+    pub authority: Pubkey,
+}
+
+// Emitted for every refund paid out of a cancelled challenge
+#[event]
+pub struct RefundIssued {
+    pub challenge: Pubkey,
+    pub wallet: Pubkey,
+    pub participation_refund: u64,
+    pub submission_refund: u64,
+    pub voting_refund: u64,
+}
+
+// Emitted when a cancelled challenge returns a sponsor's deposit
+#[event]
+pub struct SponsorRefunded {
+    pub challenge: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+}
+
+// Emitted when a voter takes back their stake once a challenge is settled
+#[event]
+pub struct StakeWithdrawn {
+    pub challenge: Pubkey,
+    pub voter: Pubkey,
+    pub amount: u64,
+}
+
+// Emitted when a participant or voter is minted a challenge's soulbound badge
+#[event]
+pub struct BadgeMinted {
+    pub challenge: Pubkey,
+    pub wallet: Pubkey,
+    pub mint: Pubkey,
+    pub participant: bool,
+    pub voter: bool,
+}
+
+// Emitted when a wallet's profile is created, counting it as a new unique
+// wallet; the count is zero until the admin has created the token state
+#[event]
+pub struct UserProfileCreated {
+    pub wallet: Pubkey,
+    pub unique_wallets: u64,
+}
+
+// Emitted when the unique-wallet count meets one of the mint conditions
+#[event]
+pub struct WalletMilestoneReached {
+    pub unique_wallets: u64,
+    pub condition: u8,
+}
+
+// Emitted once, when the admin creates the trophy collection
+#[event]
+pub struct TrophyCollectionCreated {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub symbol: String,
+    pub base_uri: String,
+}
+
+// Emitted when a placed winner is minted their trophy NFT
+#[event]
+pub struct TrophyMinted {
+    pub challenge: Pubkey,
+    pub submission: Pubkey,
+    pub winner: Pubkey,
+    pub mint: Pubkey,
+    pub rank: u8,
+    pub votes: u64,
+}
+
+// Emitted when a settled challenge and its treasuries are closed
+#[event]
+pub struct ChallengeClosed {
+    pub challenge: Pubkey,
+    pub creator: Pubkey,
+    pub swept_treasury: u64,
+    pub swept_voting_treasury: u64,
+}
+
+// Emitted when the admin opens a season
+#[event]
+pub struct SeasonCreated {
+    pub season: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub pool_share_bps: u16,
+    pub payout_places: u8,
+}
+
+// Emitted when a winner or accurate voter scores season points
+#[event]
+pub struct SeasonPointsAwarded {
+    pub season: u64,
+    pub wallet: Pubkey,
+    pub points: u64,
+    pub total: u64,
+}
+
+// Emitted when a settled challenge pays part of its platform fee into the
+// season's prize pool
+#[event]
+pub struct SeasonPoolFunded {
+    pub season: u64,
+    pub challenge: Pubkey,
+    pub amount: u64,
+    pub prize_pool: u64,
+}
+
+// Emitted for each leaderboard place paid when a season is settled
+#[event]
+pub struct SeasonPrizePaid {
+    pub season: u64,
+    pub wallet: Pubkey,
+    pub rank: u8,
+    pub points: u64,
+    pub amount: u64,
+}
+
+// Emitted once a season's prize pool has been paid out
+#[event]
+pub struct SeasonSettled {
+    pub season: u64,
+    pub prize_pool: u64,
+    pub places_paid: u8,
+}
+
+// Emitted when a creator opens a tournament and escrows its prize
+#[event]
+pub struct TournamentCreated {
+    pub tournament: Pubkey,
+    pub creator: Pubkey,
+    pub round_count: u8,
+    pub advance_count: u8,
+    pub prize: u64,
+    pub roll_fees: bool,
+}
+
+// Emitted when a challenge is linked as a tournament's next round
+#[event]
+pub struct TournamentRoundAdded {
+    pub tournament: Pubkey,
+    pub challenge: Pubkey,
+    pub round: u8,
+}
+
+// Emitted when a settled round's top submitters qualify for the next round
+#[event]
+pub struct TournamentAdvanced {
+    pub tournament: Pubkey,
+    pub round: u8,
+    pub qualified: Vec<Pubkey>,
+}
+
+// Emitted when the tournament prize is released: to the final's winner, or
+// back to the creator when a round was cancelled
+#[event]
+pub struct TournamentFinished {
+    pub tournament: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub refunded: bool,
+}
+
+// Emitted when a captain registers a team for a challenge
+#[event]
+pub struct TeamCreated {
+    pub team: Pubkey,
+    pub challenge: Pubkey,
+    pub captain: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub shares_bps: Vec<u16>,
+}
+
+// Emitted for each payment towards a team's participation fee; `members` is
+// how many members' parts it covered
+#[event]
+pub struct TeamFeePaid {
+    pub team: Pubkey,
+    pub payer: Pubkey,
+    pub members: u8,
+    pub amount: u64,
+    pub entered: bool,
+}
+
+// Emitted when a member of a team that never entered takes back their part
+// of its participation fee
+#[event]
+pub struct TeamFeeRefunded {
+    pub team: Pubkey,
+    pub challenge: Pubkey,
+    pub member: Pubkey,
+    pub amount: u64,
+}
+
+// Emitted when a creator stakes a challenge as a duel; `opponent` is unset
+// for a duel open to anyone
+#[event]
+pub struct DuelOpened {
+    pub challenge: Pubkey,
+    pub creator: Pubkey,
+    pub opponent: Pubkey,
+    pub stake: u64,
+    pub accept_by: i64,
+}
+
+// Emitted when the opponent matches the stake; `pot` is both stakes
+#[event]
+pub struct DuelAccepted {
+    pub challenge: Pubkey,
+    pub opponent: Pubkey,
+    pub pot: u64,
+}
+
+// Emitted for each member's cut when a team's balance is split by shares
+#[event]
+pub struct TeamSharePaid {
+    pub team: Pubkey,
+    pub challenge: Pubkey,
+    pub member: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
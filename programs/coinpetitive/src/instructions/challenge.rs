@@ -1,17 +1,101 @@
+#![allow(ambiguous_glob_reexports)]
+
+pub mod accept_duel;
+pub mod add_tournament_round;
+pub mod advance_tournament;
+pub mod audit_challenge;
+pub mod cancel_challenge;
 pub mod challenge_tracking;
+pub mod claim_badge;
 pub mod claim_creator_reward;
+pub mod claim_judge_fee;
+pub mod claim_refund;
+pub mod claim_sponsor_refund;
+pub mod close_challenge;
+pub mod close_submissions;
+pub mod commit_vote;
 pub mod create_challenge;
+pub mod create_season;
+pub mod create_team;
+pub mod create_tournament;
+pub mod disqualify_submission;
 pub mod distribute_voting_treasury;
-pub mod finalize_challenge;
+pub mod expire_dispute;
+pub mod finish_tournament;
+pub mod fund_prize;
+pub mod initialize_trophy_collection;
+pub mod mint_trophy;
+pub mod open_duel;
 pub mod pay_participation_fee;
+pub mod pay_team_fee;
+pub mod preview_finalization;
+pub mod preview_voter_reward;
+pub mod profile;
+pub mod propose_results;
+pub mod raise_dispute;
+pub mod refund_team_fee;
+pub mod resolve_dispute;
+pub mod reveal_vote;
+pub mod score_submission;
+pub mod set_challenge_frozen;
+pub mod set_crank_bounty;
+pub mod set_dispute_config;
+pub mod set_reputation_config;
+pub mod settle_results;
+pub mod settle_season;
+pub mod split_team_reward;
+pub mod sponsor_challenge;
+pub mod stake_vote;
 pub mod submit_video;
-// pub mod vote_for_submission;
+pub mod treasury;
+pub mod vote_for_submission;
+pub mod withdraw_stake;
 
+pub use accept_duel::*;
+pub use add_tournament_round::*;
+pub use advance_tournament::*;
+pub use audit_challenge::*;
+pub use cancel_challenge::*;
 pub use challenge_tracking::*;
+pub use claim_badge::*;
 pub use claim_creator_reward::*;
+pub use claim_judge_fee::*;
+pub use claim_refund::*;
+pub use claim_sponsor_refund::*;
+pub use close_challenge::*;
+pub use close_submissions::*;
+pub use commit_vote::*;
 pub use create_challenge::*;
+pub use create_season::*;
+pub use create_team::*;
+pub use create_tournament::*;
+pub use disqualify_submission::*;
 pub use distribute_voting_treasury::*;
-pub use finalize_challenge::*;
+pub use expire_dispute::*;
+pub use finish_tournament::*;
+pub use fund_prize::*;
+pub use initialize_trophy_collection::*;
+pub use mint_trophy::*;
+pub use open_duel::*;
 pub use pay_participation_fee::*;
+pub use pay_team_fee::*;
+pub use preview_finalization::*;
+pub use preview_voter_reward::*;
+pub use propose_results::*;
+pub use raise_dispute::*;
+pub use refund_team_fee::*;
+pub use resolve_dispute::*;
+pub use reveal_vote::*;
+pub use score_submission::*;
+pub use set_challenge_frozen::*;
+pub use set_crank_bounty::*;
+pub use set_dispute_config::*;
+pub use set_reputation_config::*;
+pub use settle_results::*;
+pub use settle_season::*;
+pub use split_team_reward::*;
+pub use sponsor_challenge::*;
+pub use stake_vote::*;
 pub use submit_video::*;
-// pub use vote_for_submission::*;
+pub use vote_for_submission::*;
+pub use withdraw_stake::*;
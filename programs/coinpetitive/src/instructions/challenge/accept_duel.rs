@@ -0,0 +1,124 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::{DuelAccepted, ParticipantJoined};
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::profile::{open_profile, require_reputation};
+use crate::instructions::challenge::treasury::transfer_from_signer;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptDuel<'info> {
+    #[account(mut)]
+    pub opponent: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+        constraint = challenge.is_duel @ ErrorCode::InvalidDuel,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    // Created by the wallet's first challenge action
+    #[account(
+        init_if_needed,
+        payer = opponent,
+        space = UserProfile::SPACE,
+        seeds = [b"user_profile", opponent.key().as_ref()],
+        bump,
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    /// CHECK: Token state PDA, only counted in once the admin has created it
+    #[account(mut, seeds = [b"token_state"], bump)]
+    pub token_state: UncheckedAccount<'info>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Opponent's token account
+    #[account(mut)]
+    pub opponent_token_account: AccountInfo<'info>,
+
+    /// CHECK: Main treasury PDA - verified in handler
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account - verified in handler
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<AcceptDuel>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let opponent = ctx.accounts.opponent.key();
+
+    // Past the deadline the duel can only be cancelled and its creator refunded
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        !challenge.duel_matched() && now < challenge.duel_accept_by,
+        ErrorCode::DuelClosed
+    );
+    require!(
+        challenge.can_accept_duel(&opponent),
+        ErrorCode::NotDuelOpponent
+    );
+
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+    token_account_balance(
+        &ctx.accounts.treasury_token_account,
+        &challenge.treasury,
+        &challenge.reward_token_mint,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidTreasury))?;
+
+    let profile = &mut ctx.accounts.user_profile;
+    open_profile(profile, opponent, &ctx.accounts.token_state)?;
+    require_reputation(profile, challenge.min_participant_reputation)?;
+    profile.challenges_joined = profile
+        .challenges_joined
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Matching the stake doubles the pot the winner takes
+    let stake = challenge.participation_fee;
+    transfer_from_signer(
+        &ctx.accounts.token_program,
+        &ctx.accounts.opponent_token_account,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.opponent.to_account_info(),
+        stake,
+    )?;
+    challenge.credit_treasury(stake)?;
+    challenge.reward = challenge
+        .reward
+        .checked_add(stake)
+        .ok_or(ErrorCode::MathOverflow)?;
+    challenge.duel_opponent = opponent;
+    challenge.participants.push(opponent);
+
+    emit!(ParticipantJoined {
+        challenge: challenge.key(),
+        participant: opponent,
+        fee: stake,
+    });
+    emit!(DuelAccepted {
+        challenge: challenge.key(),
+        opponent,
+        pot: challenge.reward,
+    });
+
+    msg!(
+        "{} matched the duel, the pot is now {}",
+        opponent,
+        challenge.reward
+    );
+
+    Ok(())
+}
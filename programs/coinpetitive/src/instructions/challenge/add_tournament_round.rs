@@ -0,0 +1,51 @@
+use crate::errors::ErrorCode;
+use crate::events::TournamentRoundAdded;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AddTournamentRound<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = creator @ ErrorCode::Unauthorized,
+        constraint = !tournament.finished @ ErrorCode::TournamentFinished,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    // A fresh challenge by the same creator, before anyone has joined it
+    #[account(
+        mut,
+        constraint = challenge.creator == creator.key() @ ErrorCode::Unauthorized,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = challenge.participants.is_empty() @ ErrorCode::InvalidTournamentRound,
+        constraint = !challenge.is_tournament_round() @ ErrorCode::InvalidTournamentRound,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+}
+
+pub fn handle(ctx: Context<AddTournamentRound>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    require!(
+        tournament.rounds.len() < tournament.round_count as usize,
+        ErrorCode::InvalidTournamentRound
+    );
+
+    let round = tournament.rounds.len() as u8;
+    let challenge = &mut ctx.accounts.challenge;
+    challenge.tournament = tournament.key();
+    challenge.tournament_round = round;
+    challenge.fees_to_tournament = tournament.roll_fees;
+    tournament.rounds.push(challenge.key());
+
+    emit!(TournamentRoundAdded {
+        tournament: tournament.key(),
+        challenge: challenge.key(),
+        round,
+    });
+
+    msg!("Challenge {} is round {}", challenge.key(), round);
+
+    Ok(())
+}
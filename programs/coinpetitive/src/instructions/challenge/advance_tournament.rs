@@ -0,0 +1,65 @@
+use crate::errors::ErrorCode;
+use crate::events::TournamentAdvanced;
+use crate::payout;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AdvanceTournament<'info> {
+    // Anyone may advance a tournament once its current round has settled
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !tournament.finished @ ErrorCode::TournamentFinished,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(
+        constraint = Some(round.key()) == tournament.current_round_challenge()
+            @ ErrorCode::TournamentRoundNotOpen,
+        constraint = !round.is_active && round.winner.is_some() @ ErrorCode::ChallengeStillActive,
+    )]
+    pub round: Box<Account<'info, Challenge>>,
+}
+
+pub fn handle(ctx: Context<AdvanceTournament>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let round = &ctx.accounts.round;
+
+    // The final pays out through finish_tournament instead
+    require!(
+        !tournament.is_final_round(),
+        ErrorCode::InvalidTournamentRound
+    );
+
+    // The round's ranking, best first, one place per wallet
+    let mut qualified: Vec<Pubkey> = Vec::new();
+    for (submission, _) in payout::rank_submissions(round)? {
+        if qualified.len() == tournament.advance_count as usize {
+            break;
+        }
+        if let Some(wallet) = round.submitter_of(&submission) {
+            if !qualified.contains(&wallet) {
+                qualified.push(wallet);
+            }
+        }
+    }
+
+    tournament.current_round += 1;
+    tournament.qualified = qualified;
+
+    emit!(TournamentAdvanced {
+        tournament: tournament.key(),
+        round: tournament.current_round,
+        qualified: tournament.qualified.clone(),
+    });
+
+    msg!(
+        "{} wallets qualified for round {}",
+        tournament.qualified.len(),
+        tournament.current_round
+    );
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use crate::errors::ErrorCode;
+use crate::events::ChallengeAuditDiscrepancy;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::state::Account as Token2022Account;
+
+#[derive(Accounts)]
+pub struct AuditChallenge<'info> {
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    /// CHECK: Treasury's token account - verified in handler
+    pub treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Voting Treasury's token account - verified in handler
+    pub voting_treasury_token_account: AccountInfo<'info>,
+}
+
+// Ledger vs actual balances, returned to the caller through return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuditReport {
+    pub challenge: Pubkey,
+    pub ledger_treasury: u64,
+    pub actual_treasury: u64,
+    pub ledger_voting_treasury: u64,
+    pub actual_voting_treasury: u64,
+    pub is_frozen: bool,
+    pub treasury_surplus: u64, // Held beyond the ledger, e.g. sent in directly
+    pub voting_treasury_surplus: u64,
+}
+
+impl AuditReport {
+    // Anyone can send tokens to a treasury, so only a shortfall means the
+    // ledger promises more than the challenge holds
+    pub fn treasury_short(&self) -> bool {
+        self.actual_treasury < self.ledger_treasury
+    }
+
+    pub fn voting_treasury_short(&self) -> bool {
+        self.actual_voting_treasury < self.ledger_voting_treasury
+    }
+
+    pub fn is_clean(&self) -> bool {
+        !self.treasury_short() && !self.voting_treasury_short()
+    }
+}
+
+// Read the balance of a Token-2022 account, checking it belongs to the expected
+// owner and mint instead of trusting raw byte offsets
+pub fn token_account_balance(
+    token_account: &AccountInfo,
+    expected_owner: &Pubkey,
+    expected_mint: &Pubkey,
+) -> Result<u64> {
+    token_account_balance_in(
+        token_account,
+        &spl_token_2022::ID,
+        expected_owner,
+        expected_mint,
+    )
+}
+
+// The same for an account of either token program; SPL Token accounts share
+// Token-2022's base layout
+pub fn token_account_balance_in(
+    token_account: &AccountInfo,
+    token_program: &Pubkey,
+    expected_owner: &Pubkey,
+    expected_mint: &Pubkey,
+) -> Result<u64> {
+    require!(
+        token_account.owner == token_program,
+        ErrorCode::InvalidTokenProgram
+    );
+
+    let data = token_account.try_borrow_data()?;
+    let state = StateWithExtensions::<Token2022Account>::unpack(&data)?;
+
+    require!(
+        state.base.owner == *expected_owner,
+        ErrorCode::InvalidTokenAccount
+    );
+    require!(
+        state.base.mint == *expected_mint,
+        ErrorCode::InvalidTokenMint
+    );
+
+    Ok(state.base.amount)
+}
+
+// Compare the challenge ledger against both treasury token accounts
+pub fn build_report(
+    challenge: &Account<Challenge>,
+    treasury_token_account: &AccountInfo,
+    voting_treasury_token_account: &AccountInfo,
+) -> Result<AuditReport> {
+    let actual_treasury = token_account_balance(
+        treasury_token_account,
+        &challenge.treasury,
+        &challenge.reward_token_mint,
+    )?;
+    let actual_voting_treasury = token_account_balance(
+        voting_treasury_token_account,
+        &challenge.voting_treasury_pda,
+        &challenge.reward_token_mint,
+    )?;
+
+    let ledger_treasury = challenge
+        .challenge_treasury
+        .saturating_add(challenge.locked_bonds)
+        .saturating_add(challenge.team_fees_held);
+    let ledger_voting_treasury = challenge
+        .voting_treasury
+        .saturating_add(challenge.locked_stake);
+
+    Ok(AuditReport {
+        challenge: challenge.key(),
+        ledger_treasury,
+        actual_treasury,
+        ledger_voting_treasury,
+        actual_voting_treasury,
+        is_frozen: challenge.is_frozen,
+        treasury_surplus: actual_treasury.saturating_sub(ledger_treasury),
+        voting_treasury_surplus: actual_voting_treasury.saturating_sub(ledger_voting_treasury),
+    })
+}
+
+pub fn handle(ctx: Context<AuditChallenge>) -> Result<AuditReport> {
+    let report = build_report(
+        &ctx.accounts.challenge,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.voting_treasury_token_account,
+    )?;
+
+    msg!(
+        "Treasury ledger {} / actual {}",
+        report.ledger_treasury,
+        report.actual_treasury
+    );
+    msg!(
+        "Voting treasury ledger {} / actual {}",
+        report.ledger_voting_treasury,
+        report.actual_voting_treasury
+    );
+
+    if report.treasury_surplus > 0 || report.voting_treasury_surplus > 0 {
+        msg!(
+            "Surplus over the ledger: treasury {} / voting treasury {}",
+            report.treasury_surplus,
+            report.voting_treasury_surplus
+        );
+    }
+
+    if !report.is_clean() {
+        msg!("Treasury balances fall short of the ledger");
+        emit!(ChallengeAuditDiscrepancy {
+            challenge: report.challenge,
+            ledger_treasury: report.ledger_treasury,
+            actual_treasury: report.actual_treasury,
+            ledger_voting_treasury: report.ledger_voting_treasury,
+            actual_voting_treasury: report.actual_voting_treasury,
+        });
+    }
+
+    // Anchor hands the report back to the caller via set_return_data
+    Ok(report)
+}
@@ -0,0 +1,85 @@
+use crate::constraints::REPUTATION_FOR_CANCELLED_CHALLENGE;
+use crate::errors::ErrorCode;
+use crate::events::ChallengeCancelled;
+use crate::instructions::challenge::profile::lower_reputation;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelChallenge<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    #[account(
+        seeds = [b"challenge_tracker"],
+        bump,
+    )]
+    pub challenge_tracker: Box<Account<'info, ChallengeTracker>>,
+
+    // Creator's profile, created along with the challenge
+    #[account(
+        mut,
+        seeds = [b"user_profile", challenge.creator.as_ref()],
+        bump,
+    )]
+    pub creator_profile: Box<Account<'info, UserProfile>>,
+}
+
+pub fn handle(ctx: Context<CancelChallenge>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let authority = ctx.accounts.authority.key();
+
+    // Bonds are only returned or slashed by resolving their dispute
+    require!(challenge.open_disputes == 0, ErrorCode::DisputesOpen);
+
+    // The admin can cancel at any time, the creator only before anyone submits,
+    // and anyone once a deadline has passed without the quorum it needed. A
+    // tournament's creator could otherwise cancel a round and pull back the
+    // escrow it rolls its fees into, so rounds are left to the first two
+    let is_admin = authority == ctx.accounts.challenge_tracker.authority;
+    let is_creator = authority == challenge.creator;
+    let now = Clock::get()?.unix_timestamp;
+    let voting_ended = now >= challenge.results_at();
+    let quorum_missed = challenge.quorum_missed(now);
+    if !is_admin && !quorum_missed {
+        if is_creator {
+            require!(
+                !challenge.is_tournament_round(),
+                ErrorCode::TournamentRoundCancel
+            );
+            require!(
+                challenge.submission_votes.is_empty() && challenge.vote_commitments == 0,
+                ErrorCode::ChallengeHasSubmissions
+            );
+        } else {
+            require!(voting_ended, ErrorCode::VotingPeriodActive);
+            return err!(ErrorCode::QuorumMet);
+        }
+    }
+
+    challenge.is_active = false;
+    challenge.is_cancelled = true;
+    lower_reputation(
+        &mut ctx.accounts.creator_profile,
+        REPUTATION_FOR_CANCELLED_CHALLENGE,
+    );
+
+    emit!(ChallengeCancelled {
+        challenge: challenge.key(),
+        authority,
+    });
+
+    msg!(
+        "Challenge cancelled, {} participants and {} votes to refund",
+        challenge.participants.len(),
+        challenge.voters.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::BadgeMinted;
+use crate::instructions::challenge::treasury::create_associated_token_account;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{self, system_instruction};
+use spl_token_2022::extension::{metadata_pointer, ExtensionType};
+use spl_token_metadata_interface::state::{Field, TokenMetadata};
+
+#[derive(Accounts)]
+pub struct ClaimBadge<'info> {
+    // Anyone may mint a settled challenge's badges, paying their rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = !challenge.is_active @ ErrorCode::ChallengeStillActive,
+        constraint = !challenge.is_cancelled @ ErrorCode::NotEligibleForBadge,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    /// CHECK: Participant or voter receiving the badge - verified in handler
+    pub wallet: AccountInfo<'info>,
+
+    // One badge per wallet, however many ways it took part
+    #[account(
+        init,
+        payer = payer,
+        space = Badge::SPACE,
+        seeds = [b"badge", challenge.key().as_ref(), wallet.key().as_ref()],
+        bump,
+    )]
+    pub badge: Box<Account<'info, Badge>>,
+
+    /// CHECK: The challenge's non-transferable badge mint, created by the first claim
+    #[account(mut, seeds = [b"badge_mint", challenge.key().as_ref()], bump)]
+    pub badge_mint: AccountInfo<'info>,
+
+    /// CHECK: Wallet's badge token account, created here if needed
+    #[account(mut)]
+    pub wallet_badge_account: AccountInfo<'info>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Associated Token Program
+    #[account(address = ASSOCIATED_TOKEN_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub associated_token_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<ClaimBadge>) -> Result<()> {
+    let challenge = &ctx.accounts.challenge;
+    let wallet = ctx.accounts.wallet.key();
+
+    let participant = challenge.participants.contains(&wallet);
+    let voter = challenge.voter_badges && challenge.voters.iter().any(|(v, _)| *v == wallet);
+    require!(participant || voter, ErrorCode::NotEligibleForBadge);
+
+    let challenge_pubkey = challenge.key();
+    let mint_seeds: &[&[u8]] = &[
+        b"badge_mint",
+        challenge_pubkey.as_ref(),
+        &[ctx.bumps.badge_mint],
+    ];
+
+    if ctx.accounts.badge_mint.data_is_empty() {
+        create_badge_mint(&ctx, mint_seeds)?;
+    }
+
+    create_associated_token_account(
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.wallet_badge_account,
+        &ctx.accounts.wallet,
+        &ctx.accounts.badge_mint,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+
+    solana_program::program::invoke_signed(
+        &spl_token_2022::instruction::mint_to(
+            ctx.accounts.token_program.key,
+            ctx.accounts.badge_mint.key,
+            ctx.accounts.wallet_badge_account.key,
+            ctx.accounts.badge_mint.key,
+            &[],
+            1,
+        )?,
+        &[
+            ctx.accounts.badge_mint.clone(),
+            ctx.accounts.wallet_badge_account.clone(),
+        ],
+        &[mint_seeds],
+    )?;
+
+    let badge = &mut ctx.accounts.badge;
+    badge.challenge = challenge_pubkey;
+    badge.wallet = wallet;
+    badge.participant = participant;
+    badge.voter = voter;
+    badge.minted_at = Clock::get()?.unix_timestamp;
+
+    emit!(BadgeMinted {
+        challenge: challenge_pubkey,
+        wallet,
+        mint: ctx.accounts.badge_mint.key(),
+        participant,
+        voter,
+    });
+
+    msg!("Minted a badge to {}", wallet);
+
+    Ok(())
+}
+
+// The mint is its own authority and carries its metadata itself. Being
+// non-transferable, its tokens can never leave the wallet they were minted to
+fn create_badge_mint(ctx: &Context<ClaimBadge>, mint_seeds: &[&[u8]]) -> Result<()> {
+    let mint = &ctx.accounts.badge_mint;
+    let token_program = ctx.accounts.token_program.key;
+
+    let metadata = TokenMetadata {
+        name: BADGE_NAME.to_string(),
+        symbol: BADGE_SYMBOL.to_string(),
+        additional_metadata: vec![(
+            "challenge".to_string(),
+            ctx.accounts.challenge.key().to_string(),
+        )],
+        ..Default::default()
+    };
+    let mint_len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+        ExtensionType::NonTransferable,
+        ExtensionType::MetadataPointer,
+    ])?;
+    // Token-2022 grows the account for the metadata itself, so the rent for it
+    // is paid up front
+    let lamports = Rent::get()?.minimum_balance(mint_len + metadata.tlv_size_of()?);
+
+    solana_program::program::invoke_signed(
+        &system_instruction::create_account(
+            ctx.accounts.payer.key,
+            mint.key,
+            lamports,
+            mint_len as u64,
+            token_program,
+        ),
+        &[
+            ctx.accounts.payer.to_account_info(),
+            mint.clone(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[mint_seeds],
+    )?;
+
+    solana_program::program::invoke(
+        &spl_token_2022::instruction::initialize_non_transferable_mint(token_program, mint.key)?,
+        std::slice::from_ref(mint),
+    )?;
+    solana_program::program::invoke(
+        &metadata_pointer::instruction::initialize(
+            token_program,
+            mint.key,
+            Some(*mint.key),
+            Some(*mint.key),
+        )?,
+        std::slice::from_ref(mint),
+    )?;
+    solana_program::program::invoke(
+        &spl_token_2022::instruction::initialize_mint2(token_program, mint.key, mint.key, None, 0)?,
+        std::slice::from_ref(mint),
+    )?;
+
+    solana_program::program::invoke_signed(
+        &spl_token_metadata_interface::instruction::initialize(
+            token_program,
+            mint.key,
+            mint.key,
+            mint.key,
+            mint.key,
+            metadata.name,
+            metadata.symbol,
+            metadata.uri,
+        ),
+        std::slice::from_ref(mint),
+        &[mint_seeds],
+    )?;
+    for (key, value) in metadata.additional_metadata {
+        solana_program::program::invoke_signed(
+            &spl_token_metadata_interface::instruction::update_field(
+                token_program,
+                mint.key,
+                mint.key,
+                Field::Key(key),
+                value,
+            ),
+            std::slice::from_ref(mint),
+            &[mint_seeds],
+        )?;
+    }
+
+    Ok(())
+}
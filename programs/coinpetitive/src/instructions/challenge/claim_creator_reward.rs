@@ -1,5 +1,7 @@
 use crate::constraints::*;
 use crate::errors::ErrorCode;
+use crate::events::{PayoutIssued, PayoutKind};
+use crate::instructions::challenge::audit_challenge::token_account_balance;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
@@ -10,11 +12,14 @@ pub struct ClaimCreatorReward<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
 
-    // The challenge account - verify it's finalized and creator is correct
+    // The challenge account - verify it's settled and creator is correct
     #[account(
         mut,
         constraint = !challenge.is_active @ ErrorCode::ChallengeStillActive,
-        constraint = challenge.creator == creator.key() @ ErrorCode::InvalidCreator
+        constraint = !challenge.is_cancelled @ ErrorCode::ChallengeCancelled,
+        constraint = challenge.is_settled() @ ErrorCode::ChallengeNotSettled,
+        constraint = challenge.creator == creator.key() @ ErrorCode::InvalidCreator,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen
     )]
     pub challenge: Box<Account<'info, Challenge>>,
 
@@ -55,18 +60,18 @@ pub fn handle(ctx: Context<ClaimCreatorReward>) -> Result<()> {
     let token_program = ctx.accounts.token_program.to_account_info();
 
     // Get the actual treasury token balance
-    let treasury_token_account_data = treasury_token_account.try_borrow_data()?;
-
-    // The amount is at bytes 64-71 in token account data (spl_token layout)
-    let mut amount_bytes = [0u8; 8];
-    amount_bytes.copy_from_slice(&treasury_token_account_data[64..72]);
-    let actual_treasury_balance = u64::from_le_bytes(amount_bytes);
-
-    // Important: Drop the borrow before proceeding
-    drop(treasury_token_account_data);
+    let actual_treasury_balance = token_account_balance(
+        &treasury_token_account,
+        &challenge.treasury,
+        &challenge.reward_token_mint,
+    )?;
 
     msg!("Actual treasury token balance: {}", actual_treasury_balance);
 
+    // Judge fees stay in the treasury until every judge has claimed theirs
+    let judge_fees_owed = challenge.judge_fees_owed();
+    let actual_treasury_balance = actual_treasury_balance.saturating_sub(judge_fees_owed);
+
     // Only continue if there's actually a balance to claim
     if actual_treasury_balance == 0 {
         msg!("No tokens to claim");
@@ -95,7 +100,7 @@ pub fn handle(ctx: Context<ClaimCreatorReward>) -> Result<()> {
         ],
         data: [3]
             .into_iter() // Token instruction 3 = Transfer
-            .chain(actual_treasury_balance.to_le_bytes().into_iter())
+            .chain(actual_treasury_balance.to_le_bytes())
             .collect(),
     };
 
@@ -106,6 +111,18 @@ pub fn handle(ctx: Context<ClaimCreatorReward>) -> Result<()> {
         &[treasury_seeds],
     )?;
 
+    // The ATA has been drained down to the judge fees, and the ledger never
+    // exceeds the ATA balance
+    let challenge = &mut ctx.accounts.challenge;
+    challenge.challenge_treasury = challenge.challenge_treasury.min(judge_fees_owed);
+
+    emit!(PayoutIssued {
+        challenge: challenge_pubkey,
+        kind: PayoutKind::Creator,
+        recipient: ctx.accounts.creator.key(),
+        amount: actual_treasury_balance,
+    });
+
     msg!("Transferred {} tokens to creator", actual_treasury_balance);
 
     Ok(())
@@ -0,0 +1,80 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::{PayoutIssued, PayoutKind};
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::treasury::transfer_signed;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClaimJudgeFee<'info> {
+    // Anyone can crank a judge's fee; it only ever goes to the judge's own ATA
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !challenge.is_active @ ErrorCode::ChallengeStillActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Main treasury PDA - verified in handler
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Judge's token account - owner verified in handler
+    #[account(mut)]
+    pub judge_token_account: AccountInfo<'info>,
+}
+
+pub fn handle(ctx: Context<ClaimJudgeFee>, judge: Pubkey) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+    token_account_balance(
+        &ctx.accounts.judge_token_account,
+        &judge,
+        &challenge.reward_token_mint,
+    )?;
+
+    // Only judges who scored share the pool, each of them once
+    let judge_bit = 1u8 << challenge.judge_index(&judge).ok_or(ErrorCode::NotAJudge)?;
+    let owed = challenge.scoring_judges() & !challenge.judges_paid & judge_bit != 0;
+    let amount = challenge.judge_fee_share();
+    require!(owed && amount > 0, ErrorCode::NoRewardToDistribute);
+
+    let challenge_pubkey = challenge.key();
+    let (_, bump) =
+        Pubkey::find_program_address(&[b"treasury", challenge_pubkey.as_ref()], ctx.program_id);
+    transfer_signed(
+        &ctx.accounts.token_program,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.judge_token_account,
+        &ctx.accounts.treasury,
+        &[b"treasury", challenge_pubkey.as_ref(), &[bump]],
+        amount,
+    )?;
+    challenge.debit_treasury(amount)?;
+    challenge.judges_paid |= judge_bit;
+
+    emit!(PayoutIssued {
+        challenge: challenge_pubkey,
+        kind: PayoutKind::Judge,
+        recipient: judge,
+        amount,
+    });
+
+    msg!("Paid {} token judge fee to {}", amount, judge);
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::RefundIssued;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::treasury::transfer_signed;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    // Anyone can crank a refund; funds only ever go to the wallet's own ATA
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_cancelled @ ErrorCode::ChallengeNotCancelled,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Main treasury PDA - verified in handler
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Voting Treasury PDA - verified in handler
+    pub voting_treasury: AccountInfo<'info>,
+
+    /// CHECK: Voting Treasury's token account
+    #[account(mut)]
+    pub voting_treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Refunded wallet's token account - owner verified in handler
+    #[account(mut)]
+    pub wallet_token_account: AccountInfo<'info>,
+}
+
+pub fn handle(ctx: Context<ClaimRefund>, wallet: Pubkey) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+    require!(
+        ctx.accounts.voting_treasury.key() == challenge.voting_treasury_pda,
+        ErrorCode::InvalidVotingTreasury
+    );
+
+    // Refunds can only land in the wallet's own account
+    token_account_balance(
+        &ctx.accounts.wallet_token_account,
+        &wallet,
+        &challenge.reward_token_mint,
+    )?;
+
+    let Refund {
+        participation: participation_refund,
+        submission: submission_refund,
+        voting: voting_refund,
+    } = challenge.take_refund(&wallet)?;
+
+    require!(
+        participation_refund > 0 || submission_refund > 0 || voting_refund > 0,
+        ErrorCode::NothingToRefund
+    );
+
+    let challenge_pubkey = challenge.key();
+
+    // Participation and submission fees both sit in the main treasury
+    let treasury_refund = participation_refund
+        .checked_add(submission_refund)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if treasury_refund > 0 {
+        let (_, bump) =
+            Pubkey::find_program_address(&[b"treasury", challenge_pubkey.as_ref()], ctx.program_id);
+        transfer_signed(
+            &ctx.accounts.token_program,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.wallet_token_account,
+            &ctx.accounts.treasury,
+            &[b"treasury", challenge_pubkey.as_ref(), &[bump]],
+            treasury_refund,
+        )?;
+        challenge.debit_treasury(treasury_refund)?;
+    }
+
+    if voting_refund > 0 {
+        let (_, bump) = Pubkey::find_program_address(
+            &[b"voting_treasury", challenge_pubkey.as_ref()],
+            ctx.program_id,
+        );
+        transfer_signed(
+            &ctx.accounts.token_program,
+            &ctx.accounts.voting_treasury_token_account,
+            &ctx.accounts.wallet_token_account,
+            &ctx.accounts.voting_treasury,
+            &[b"voting_treasury", challenge_pubkey.as_ref(), &[bump]],
+            voting_refund,
+        )?;
+        challenge.debit_voting_treasury(voting_refund)?;
+    }
+
+    emit!(RefundIssued {
+        challenge: challenge_pubkey,
+        wallet,
+        participation_refund,
+        submission_refund,
+        voting_refund,
+    });
+
+    msg!(
+        "Refunded {} participation, {} submission and {} voting tokens to {}",
+        participation_refund,
+        submission_refund,
+        voting_refund,
+        wallet
+    );
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::SponsorRefunded;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::treasury::transfer_signed;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClaimSponsorRefund<'info> {
+    // Anyone can crank a refund; funds only ever go to the sponsor's own ATA
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_cancelled @ ErrorCode::ChallengeNotCancelled,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    #[account(
+        mut,
+        close = sponsor,
+        seeds = [b"sponsor", challenge.key().as_ref(), sponsor_record.sponsor.as_ref()],
+        bump,
+    )]
+    pub sponsor_record: Box<Account<'info, SponsorRecord>>,
+
+    /// CHECK: The sponsor, receives the record's rent
+    #[account(mut, address = sponsor_record.sponsor)]
+    pub sponsor: AccountInfo<'info>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Main treasury PDA - verified in handler
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Sponsor's token account - owner verified in handler
+    #[account(mut)]
+    pub sponsor_token_account: AccountInfo<'info>,
+}
+
+pub fn handle(ctx: Context<ClaimSponsorRefund>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let sponsor = ctx.accounts.sponsor_record.sponsor;
+    let deposited = ctx.accounts.sponsor_record.amount;
+
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+    token_account_balance(
+        &ctx.accounts.sponsor_token_account,
+        &sponsor,
+        &challenge.reward_token_mint,
+    )?;
+
+    let refund = challenge.sponsor_refund(deposited);
+    let challenge_pubkey = challenge.key();
+    if refund > 0 {
+        let (_, bump) =
+            Pubkey::find_program_address(&[b"treasury", challenge_pubkey.as_ref()], ctx.program_id);
+        transfer_signed(
+            &ctx.accounts.token_program,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.sponsor_token_account,
+            &ctx.accounts.treasury,
+            &[b"treasury", challenge_pubkey.as_ref(), &[bump]],
+            refund,
+        )?;
+        challenge.debit_treasury(refund)?;
+    }
+
+    // The whole deposit is settled even if the share was rounded down
+    challenge.sponsored = challenge.sponsored.saturating_sub(deposited);
+    challenge.sponsors.retain(|s| *s != sponsor);
+
+    emit!(SponsorRefunded {
+        challenge: challenge_pubkey,
+        sponsor,
+        amount: refund,
+    });
+
+    msg!(
+        "Refunded {} of {} sponsored tokens to {}",
+        refund,
+        deposited,
+        sponsor
+    );
+
+    Ok(())
+}
@@ -0,0 +1,258 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::ChallengeClosed;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::audit_challenge::token_account_balance_in;
+use crate::instructions::challenge::profile::raise_reputation;
+use crate::instructions::challenge::settle_results::require_prize_escrow;
+use crate::instructions::challenge::treasury::{
+    close_token_account_signed, drain_lamports, transfer_checked_signed, transfer_signed,
+};
+use crate::payout;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseChallenge<'info> {
+    // Anyone can close a settled challenge; everything left goes to the creator
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        constraint = !challenge.is_active @ ErrorCode::ChallengeStillActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    /// CHECK: The creator of the challenge, receives all rent and leftovers
+    #[account(mut, address = challenge.creator @ ErrorCode::InvalidCreator)]
+    pub creator: AccountInfo<'info>,
+
+    /// CHECK: Creator's token account - owner verified in handler
+    #[account(mut)]
+    pub creator_token_account: AccountInfo<'info>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Main treasury PDA - verified in handler
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account - verified in handler
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Voting Treasury PDA - verified in handler
+    #[account(mut)]
+    pub voting_treasury: AccountInfo<'info>,
+
+    /// CHECK: Voting Treasury's token account - verified in handler
+    #[account(mut)]
+    pub voting_treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Escrow of a Token prize, swept and closed - verified in handler
+    #[account(mut)]
+    pub prize_escrow: AccountInfo<'info>,
+
+    /// CHECK: Token program of the prize mint - verified in handler
+    pub prize_token_program: AccountInfo<'info>,
+
+    /// CHECK: Mint of a Token prize, unused otherwise - verified in handler
+    pub prize_mint: AccountInfo<'info>,
+
+    /// CHECK: Creator's account in the prize mint - verified in handler
+    #[account(mut)]
+    pub creator_prize_account: AccountInfo<'info>,
+
+    // Creator's profile, created along with the challenge
+    #[account(
+        mut,
+        seeds = [b"user_profile", challenge.creator.as_ref()],
+        bump,
+    )]
+    pub creator_profile: Box<Account<'info, UserProfile>>,
+}
+
+pub fn handle(ctx: Context<CloseChallenge>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+    require!(
+        ctx.accounts.voting_treasury.key() == challenge.voting_treasury_pda,
+        ErrorCode::InvalidVotingTreasury
+    );
+
+    if challenge.is_cancelled {
+        // Every participant, submitter, voter and sponsor must have been
+        // refunded first; disqualified entries have nothing left to refund
+        require!(
+            challenge.refundable_wallets().is_empty() && challenge.sponsors.is_empty(),
+            ErrorCode::RefundsPending
+        );
+    } else {
+        // Only rounding dust may be left for the creator, never a voter's share
+        require!(
+            !payout::voter_rewards_pending(challenge),
+            ErrorCode::VoterRewardsPending
+        );
+    }
+
+    // Judge fees sit in the treasury ATA and would otherwise be swept
+    require!(
+        challenge.judge_fees_owed() == 0,
+        ErrorCode::JudgeFeesPending
+    );
+
+    // Stakes sit in the voting treasury ATA and would otherwise be swept
+    require!(challenge.locked_stake == 0, ErrorCode::StakesLocked);
+
+    // So do the parts of fees paid by teams that never entered
+    require!(challenge.team_fees_held == 0, ErrorCode::TeamFeesPending);
+
+    let mint = challenge.reward_token_mint;
+    let treasury_balance = token_account_balance(
+        &ctx.accounts.treasury_token_account,
+        &challenge.treasury,
+        &mint,
+    )?;
+    let voting_balance = token_account_balance(
+        &ctx.accounts.voting_treasury_token_account,
+        &challenge.voting_treasury_pda,
+        &mint,
+    )?;
+    token_account_balance(
+        &ctx.accounts.creator_token_account,
+        &challenge.creator,
+        &mint,
+    )?;
+
+    let challenge_pubkey = challenge.key();
+    let (_, treasury_bump) =
+        Pubkey::find_program_address(&[b"treasury", challenge_pubkey.as_ref()], ctx.program_id);
+    let (_, voting_bump) = Pubkey::find_program_address(
+        &[b"voting_treasury", challenge_pubkey.as_ref()],
+        ctx.program_id,
+    );
+    let treasury_seeds: &[&[u8]] = &[b"treasury", challenge_pubkey.as_ref(), &[treasury_bump]];
+    let voting_seeds: &[&[u8]] = &[
+        b"voting_treasury",
+        challenge_pubkey.as_ref(),
+        &[voting_bump],
+    ];
+
+    // Sweep both treasuries to the creator, then close their token accounts
+    if treasury_balance > 0 {
+        transfer_signed(
+            &ctx.accounts.token_program,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.creator_token_account,
+            &ctx.accounts.treasury,
+            treasury_seeds,
+            treasury_balance,
+        )?;
+    }
+    if voting_balance > 0 {
+        transfer_signed(
+            &ctx.accounts.token_program,
+            &ctx.accounts.voting_treasury_token_account,
+            &ctx.accounts.creator_token_account,
+            &ctx.accounts.voting_treasury,
+            voting_seeds,
+            voting_balance,
+        )?;
+    }
+    close_token_account_signed(
+        &ctx.accounts.token_program,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.creator,
+        &ctx.accounts.treasury,
+        treasury_seeds,
+    )?;
+    close_token_account_signed(
+        &ctx.accounts.token_program,
+        &ctx.accounts.voting_treasury_token_account,
+        &ctx.accounts.creator,
+        &ctx.accounts.voting_treasury,
+        voting_seeds,
+    )?;
+
+    // A Token prize has its own escrow; SOL prizes are drained below
+    let mut swept_prize = 0;
+    if challenge.prize_kind == PrizeKind::Token {
+        require_prize_escrow(
+            challenge,
+            &ctx.accounts.prize_escrow,
+            &ctx.accounts.prize_mint,
+            &ctx.accounts.prize_token_program,
+        )?;
+        swept_prize = token_account_balance_in(
+            &ctx.accounts.prize_escrow,
+            &challenge.prize_token_program,
+            &challenge.treasury,
+            &challenge.prize_mint,
+        )?;
+        token_account_balance_in(
+            &ctx.accounts.creator_prize_account,
+            &challenge.prize_token_program,
+            &challenge.creator,
+            &challenge.prize_mint,
+        )
+        .map_err(|_| error!(ErrorCode::InvalidPrizeAccount))?;
+        if swept_prize > 0 {
+            transfer_checked_signed(
+                &ctx.accounts.prize_token_program,
+                &ctx.accounts.prize_escrow,
+                &ctx.accounts.prize_mint,
+                &ctx.accounts.creator_prize_account,
+                &ctx.accounts.treasury,
+                treasury_seeds,
+                swept_prize,
+            )?;
+        }
+        close_token_account_signed(
+            &ctx.accounts.prize_token_program,
+            &ctx.accounts.prize_escrow,
+            &ctx.accounts.creator,
+            &ctx.accounts.treasury,
+            treasury_seeds,
+        )?;
+    }
+
+    // The treasury PDAs only hold rent, gas SOL and any SOL prize left over
+    drain_lamports(&ctx.accounts.treasury, &ctx.accounts.creator)?;
+    drain_lamports(&ctx.accounts.voting_treasury, &ctx.accounts.creator)?;
+
+    challenge.challenge_treasury = 0;
+    challenge.voting_treasury = 0;
+    challenge.prize_pool = 0;
+
+    // Seeing a challenge through to its results earns the creator standing
+    if !challenge.is_cancelled {
+        raise_reputation(
+            &mut ctx.accounts.creator_profile,
+            REPUTATION_FOR_COMPLETED_CHALLENGE,
+        );
+    }
+
+    emit!(ChallengeClosed {
+        challenge: challenge_pubkey,
+        creator: challenge.creator,
+        swept_treasury: treasury_balance,
+        swept_voting_treasury: voting_balance,
+    });
+
+    msg!(
+        "Challenge closed, swept {} + {} tokens and {} prize to creator",
+        treasury_balance,
+        voting_balance,
+        swept_prize
+    );
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::errors::ErrorCode;
+use crate::events::SubmissionsClosed;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseSubmissions<'info> {
+    // Anyone can close submissions once the deadline has passed
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+        constraint = !challenge.submissions_closed @ ErrorCode::SubmissionsClosed,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+}
+
+pub fn handle(ctx: Context<CloseSubmissions>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= challenge.submission_end,
+        ErrorCode::SubmissionPeriodActive
+    );
+
+    challenge.submissions_closed = true;
+
+    emit!(SubmissionsClosed {
+        challenge: challenge.key(),
+        submissions: challenge.submission_votes.len() as u32,
+        participants: challenge.participants.len() as u32,
+    });
+
+    msg!(
+        "Submissions closed with {} submissions from {} participants",
+        challenge.submission_votes.len(),
+        challenge.participants.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::VoteCommitted;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::profile::{record_vote, require_reputation};
+use crate::instructions::challenge::treasury::transfer_from_signer;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CommitVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+        constraint = challenge.commit_reveal @ ErrorCode::CommitRevealDisabled,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    // One sealed vote per voter and challenge
+    #[account(
+        init,
+        payer = voter,
+        space = VoteCommitment::SPACE,
+        seeds = [b"vote_commitment", challenge.key().as_ref(), voter.key().as_ref()],
+        bump,
+    )]
+    pub vote_commitment: Box<Account<'info, VoteCommitment>>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Voter's token account
+    #[account(mut)]
+    pub voter_token_account: AccountInfo<'info>,
+
+    /// CHECK: Voting Treasury account (PDA) - verified in handler
+    pub voting_treasury: AccountInfo<'info>,
+
+    /// CHECK: Voting Treasury's token account
+    #[account(mut)]
+    pub voting_treasury_token_account: AccountInfo<'info>,
+
+    // Created by the wallet's first challenge action
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = UserProfile::SPACE,
+        seeds = [b"user_profile", voter.key().as_ref()],
+        bump,
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    /// CHECK: Token state PDA, only counted in once the admin has created it
+    #[account(mut, seeds = [b"token_state"], bump)]
+    pub token_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<CommitVote>, commitment: [u8; 32]) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let voter = ctx.accounts.voter.key();
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(challenge.accepts_votes(now), ErrorCode::VotingClosed);
+
+    // Every commitment must be able to become a vote when it is revealed
+    require!(
+        (challenge.vote_commitments as usize) < challenge.max_voters as usize,
+        ErrorCode::MaxVotersReached
+    );
+
+    require!(
+        ctx.accounts.voting_treasury.key() == challenge.voting_treasury_pda,
+        ErrorCode::InvalidVotingTreasury
+    );
+    token_account_balance(
+        &ctx.accounts.voting_treasury_token_account,
+        &challenge.voting_treasury_pda,
+        &challenge.reward_token_mint,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidVotingTreasury))?;
+
+    // The fee is paid up front and stays in the pool if the vote is never revealed
+    let voting_fee = challenge.voting_fee;
+    transfer_from_signer(
+        &ctx.accounts.token_program,
+        &ctx.accounts.voter_token_account,
+        &ctx.accounts.voting_treasury_token_account,
+        &ctx.accounts.voter.to_account_info(),
+        voting_fee,
+    )?;
+    challenge.credit_voting_treasury(voting_fee)?;
+    challenge.vote_commitments = challenge
+        .vote_commitments
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    record_vote(
+        &mut ctx.accounts.user_profile,
+        voter,
+        &ctx.accounts.token_state,
+        voting_fee,
+    )?;
+    require_reputation(&ctx.accounts.user_profile, challenge.min_voter_reputation)?;
+
+    let vote_commitment = &mut ctx.accounts.vote_commitment;
+    vote_commitment.challenge = challenge.key();
+    vote_commitment.voter = voter;
+    vote_commitment.commitment = commitment;
+
+    emit!(VoteCommitted {
+        challenge: challenge.key(),
+        voter,
+        fee: voting_fee,
+    });
+
+    msg!("Vote committed by {}", voter);
+
+    Ok(())
+}
@@ -1,18 +1,14 @@
 use crate::constraints::*;
 use crate::errors::ErrorCode;
+use crate::events::ChallengeCreated;
+use crate::instructions::challenge::profile::{open_profile, require_reputation};
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{self, system_instruction};
 use std::str::FromStr;
 
 #[derive(Accounts)]
-#[instruction(
-    reward: u64,
-    participation_fee: u64,
-    voting_fee: u64,
-    max_participants: u8,
-    challenge_id: u64
-)]
+#[instruction(params: CreateChallengeParams)]
 pub struct CreateChallenge<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -33,12 +29,61 @@ pub struct CreateChallenge<'info> {
                8 +  // total_votes: u64
                8 +  // winning_votes: u64
                32 + // reward_token_mint: Pubkey
-               4 + (32 * max_participants as usize) + // participants vec with length prefix
+               4 + (32 * params.participant_limit().min(MAX_PARTICIPANTS) as usize) + // participants vec with length prefix
                1 +  // max_participants: u8
-               4 + (40 * 20) + // submission_votes: Vec<(Pubkey, u64)> - limit to 20 submissions
-               4 + (64 * 50) + // voters: Vec<(Pubkey, Pubkey)> - limit to 50 voters
+               4 + (40 * MAX_SUBMISSIONS) + // submission_votes: Vec<(Pubkey, u64)>
+               4 + (64 * MAX_VOTERS) + // voters: Vec<(Pubkey, Pubkey)>
                32 + // treasury: Pubkey
-               32   // voting_treasury_pda: Pubkey
+               32 + // voting_treasury_pda: Pubkey
+               8 +  // voting_reward_pool: u64
+               1 +  // is_frozen: bool
+               1 +  // is_cancelled: bool
+               8 +  // submission_end: i64
+               8 +  // voting_end: i64
+               1 +  // submissions_closed: bool
+               4 + (64 * MAX_SUBMISSIONS) + // submitters: Vec<(Pubkey, Pubkey)>
+               1 +  // min_participants: u8
+               1 +  // min_voters: u8
+               1 +  // max_voters: u8
+               1 +  // commit_reveal: bool
+               8 +  // reveal_end: i64
+               2 +  // vote_commitments: u16
+               1 +  // voting_mode: VotingMode
+               4 + (8 * MAX_VOTERS) + // vote_weights: Vec<u64>
+               8 +  // locked_stake: u64
+               8 +  // winning_weight: u64
+               1 +  // judging_mode: JudgingMode
+               4 + (32 * MAX_JUDGES) + // judges: Vec<Pubkey>
+               4 + (3 * MAX_JUDGES * MAX_SUBMISSIONS) + // judge_scores: Vec<(u8, u8, u8)>
+               2 +  // judge_weight_bps: u16
+               2 +  // judge_fee_bps: u16
+               8 +  // judge_fee_pool: u64
+               1 +  // judges_paid: u8
+               1 +  // results_proposed: bool
+               8 +  // dispute_window: i64
+               8 +  // dispute_end: i64
+               8 +  // dispute_bond: u64
+               1 +  // open_disputes: u8
+               8 +  // locked_bonds: u64
+               4 +  // disqualified: u32
+               4 + (32 * MAX_SPONSORS) + // sponsors: Vec<Pubkey>
+               8 +  // sponsored: u64
+               1 +  // prize_kind: PrizeKind
+               32 + // prize_mint: Pubkey
+               32 + // prize_token_program: Pubkey
+               8 +  // prize_pool: u64
+               1 +  // voter_badges: bool
+               8 +  // min_participant_reputation: u64
+               8 +  // min_voter_reputation: u64
+               8 +  // season: u64
+               32 + // tournament: Pubkey
+               1 +  // tournament_round: u8
+               1 +  // fees_to_tournament: bool
+               1 +  // is_duel: bool
+               32 + // duel_opponent: Pubkey
+               8 +  // duel_accept_by: i64
+               8 +  // voters_rewarded: u64
+               8 // team_fees_held: u64
     )]
     pub challenge: Box<Account<'info, Challenge>>,
 
@@ -76,16 +121,158 @@ pub struct CreateChallenge<'info> {
     /// CHECK: Voting Treasury token account
     #[account(mut)]
     pub voting_treasury_token_account: AccountInfo<'info>,
+
+    // Created by the wallet's first challenge action
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserProfile::SPACE,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump,
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    /// CHECK: Token state PDA, only counted in once the admin has created it
+    #[account(mut, seeds = [b"token_state"], bump)]
+    pub token_state: UncheckedAccount<'info>,
+
+    /// CHECK: Reputation config PDA, only read once the admin has created it
+    #[account(seeds = [b"reputation_config"], bump)]
+    pub reputation_config: UncheckedAccount<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CreateChallengeParams {
+    pub reward: u64,
+    pub participation_fee: u64,
+    pub voting_fee: u64,
+    /// Cap on entries, at most `MAX_PARTICIPANTS`; zero means the default.
+    pub max_participants: u8,
+    pub challenge_id: u64,
+    /// Unix time entries and submissions close.
+    pub submission_end: i64,
+    /// Unix time voting closes; must not be before `submission_end`.
+    pub voting_end: i64,
+    /// Entries needed by `submission_end`, otherwise the challenge is refunded.
+    pub min_participants: u8,
+    /// Distinct voters needed by `voting_end`, otherwise the challenge is refunded.
+    pub min_voters: u8,
+    /// Cap on distinct voters; zero means the program maximum.
+    pub max_voters: u8,
+    /// Votes are committed sealed until `voting_end` and revealed until `reveal_end`.
+    pub commit_reveal: bool,
+    /// End of the reveal window; ignored unless `commit_reveal` is set.
+    pub reveal_end: i64,
+    /// How votes are priced and weighted; commit-reveal requires `Flat`.
+    pub voting_mode: VotingMode,
+    /// How submissions are ranked; anything but `PublicVote` needs `judges`.
+    pub judging_mode: JudgingMode,
+    /// Judge panel, at most `MAX_JUDGES` distinct wallets.
+    pub judges: Vec<Pubkey>,
+    /// Share of the ranking decided by the judges; 10000 ranks by score alone.
+    pub judge_weight_bps: u16,
+    /// Share of the creator's leftover fees split between judges who scored.
+    pub judge_fee_bps: u16,
+    /// Voters can claim the challenge's badge too, not just participants.
+    pub voter_badges: bool,
+    /// Reputation a wallet needs to join; zero lets anyone in.
+    pub min_participant_reputation: u64,
+    /// Reputation a wallet needs to vote; zero lets anyone vote.
+    pub min_voter_reputation: u64,
 }
 
-pub fn handle(
-    ctx: Context<CreateChallenge>,
-    reward: u64,
-    participation_fee: u64,
-    voting_fee: u64,
-    max_participants: u8,
-    challenge_id: u64,
-) -> Result<()> {
+impl CreateChallengeParams {
+    // The participant limit the challenge is created with, and sized for
+    pub fn participant_limit(&self) -> u8 {
+        if self.max_participants == 0 {
+            DEFAULT_MAX_PARTICIPANTS
+        } else {
+            self.max_participants
+        }
+    }
+}
+
+pub fn handle(ctx: Context<CreateChallenge>, params: CreateChallengeParams) -> Result<()> {
+    let max_participants = params.participant_limit();
+    let CreateChallengeParams {
+        reward,
+        participation_fee,
+        voting_fee,
+        submission_end,
+        voting_end,
+        min_participants,
+        min_voters,
+        max_voters,
+        commit_reveal,
+        reveal_end,
+        voting_mode,
+        judging_mode,
+        judges,
+        judge_weight_bps,
+        judge_fee_bps,
+        voter_badges,
+        min_participant_reputation,
+        min_voter_reputation,
+        ..
+    } = params;
+
+    // Keepers crank the phase transitions off these deadlines
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now < submission_end && submission_end <= voting_end,
+        ErrorCode::InvalidSchedule
+    );
+
+    // Commit-reveal challenges open a reveal window once voting ends
+    let reveal_end = if commit_reveal {
+        reveal_end
+    } else {
+        voting_end
+    };
+    require!(
+        !commit_reveal || voting_end < reveal_end,
+        ErrorCode::InvalidSchedule
+    );
+
+    // Sealed votes are revealed one per voter, so they can't carry a weight
+    require!(
+        !commit_reveal || voting_mode == VotingMode::Flat,
+        ErrorCode::WrongVotingMode
+    );
+
+    // Public-vote challenges have no panel; judged ones need 1 to MAX_JUDGES
+    // distinct judges who decide at least part of the ranking
+    let valid_panel = match judging_mode {
+        JudgingMode::PublicVote => judges.is_empty() && judge_weight_bps == 0 && judge_fee_bps == 0,
+        JudgingMode::Mean | JudgingMode::Median => {
+            !judges.is_empty()
+                && judges.len() <= MAX_JUDGES
+                && judges
+                    .iter()
+                    .enumerate()
+                    .all(|(i, judge)| !judges[..i].contains(judge))
+                && judge_weight_bps > 0
+                && judge_weight_bps <= BASIS_POINTS
+                && judge_fee_bps <= BASIS_POINTS
+        }
+    };
+    require!(valid_panel, ErrorCode::InvalidJudgePanel);
+
+    // Zero limits fall back to the defaults, the account has to fit the
+    // participants, and the quorum has to be reachable
+    let max_voters = if max_voters == 0 {
+        MAX_VOTERS as u8
+    } else {
+        max_voters
+    };
+    require!(
+        max_participants <= MAX_PARTICIPANTS
+            && min_participants <= max_participants
+            && max_voters as usize <= MAX_VOTERS
+            && min_voters <= max_voters,
+        ErrorCode::InvalidQuorum
+    );
+
     // Create the treasury PDA ourselves rather than relying on the derived account
     let (treasury_pda, treasury_bump) = Pubkey::find_program_address(
         &[b"treasury", ctx.accounts.challenge.key().as_ref()],
@@ -218,9 +405,6 @@ pub fn handle(
         ErrorCode::InvalidTokenProgram
     );
 
-    // Verify token mint is the expected one
-    let token_mint_id = Pubkey::from_str(CPT_TOKEN_MINT).unwrap();
-
     // Create ATA for treasury using proper ATA instruction
     msg!("Creating associated token account for treasury");
 
@@ -323,17 +507,101 @@ pub fn handle(
     challenge.participants = Vec::new();
     challenge.submission_votes = Vec::new();
     challenge.voters = Vec::new();
+    challenge.voting_reward_pool = 0;
+    challenge.is_frozen = false;
+    challenge.is_cancelled = false;
+    challenge.submission_end = submission_end;
+    challenge.voting_end = voting_end;
+    challenge.submissions_closed = false;
+    challenge.submitters = Vec::new();
+    challenge.min_participants = min_participants;
+    challenge.min_voters = min_voters;
+    challenge.max_voters = max_voters;
+    challenge.commit_reveal = commit_reveal;
+    challenge.reveal_end = reveal_end;
+    challenge.vote_commitments = 0;
+    challenge.voting_mode = voting_mode;
+    challenge.vote_weights = Vec::new();
+    challenge.locked_stake = 0;
+    challenge.winning_weight = 0;
+    challenge.judging_mode = judging_mode;
+    challenge.judges = judges.clone();
+    challenge.judge_scores = Vec::new();
+    challenge.judge_weight_bps = judge_weight_bps;
+    challenge.judge_fee_bps = judge_fee_bps;
+    challenge.judge_fee_pool = 0;
+    challenge.judges_paid = 0;
+    challenge.results_proposed = false;
+    challenge.dispute_window = 0;
+    challenge.dispute_end = 0;
+    challenge.dispute_bond = 0;
+    challenge.open_disputes = 0;
+    challenge.locked_bonds = 0;
+    challenge.disqualified = 0;
+    challenge.sponsors = Vec::new();
+    challenge.sponsored = 0;
+    challenge.prize_kind = PrizeKind::Cpt;
+    challenge.prize_mint = ctx.accounts.token_mint.key();
+    challenge.prize_token_program = token_2022_id;
+    challenge.prize_pool = 0;
+    challenge.voter_badges = voter_badges;
+    challenge.min_participant_reputation = min_participant_reputation;
+    challenge.min_voter_reputation = min_voter_reputation;
+    challenge.season = 0;
+    challenge.tournament = Pubkey::default();
+    challenge.tournament_round = 0;
+    challenge.fees_to_tournament = false;
+    challenge.is_duel = false;
+    challenge.duel_opponent = Pubkey::default();
+    challenge.duel_accept_by = 0;
+    challenge.voters_rewarded = 0;
+    challenge.team_fees_held = 0;
 
     // Store the treasury address in the challenge
     challenge.treasury = treasury_pda;
     challenge.voting_treasury_pda = voting_treasury_pda;
 
-    // Set max_participants with a reasonable default if zero
-    challenge.max_participants = if max_participants == 0 {
-        50
-    } else {
-        max_participants
-    };
+    challenge.max_participants = max_participants;
+
+    let profile = &mut ctx.accounts.user_profile;
+    open_profile(profile, challenge.creator, &ctx.accounts.token_state)?;
+    profile.challenges_created = profile
+        .challenges_created
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Large rewards need a creator with some standing
+    let reputation_config = &ctx.accounts.reputation_config;
+    if reputation_config.owner == ctx.program_id {
+        let config =
+            ReputationConfig::try_deserialize(&mut &reputation_config.try_borrow_data()?[..])?;
+        require_reputation(profile, config.required_for(reward))?;
+    }
+
+    emit!(ChallengeCreated {
+        challenge: challenge.key(),
+        creator: challenge.creator,
+        reward_token_mint: challenge.reward_token_mint,
+        reward,
+        participation_fee,
+        voting_fee,
+        max_participants,
+        submission_end,
+        voting_end,
+        min_participants,
+        min_voters,
+        max_voters,
+        commit_reveal,
+        reveal_end,
+        voting_mode,
+        judging_mode,
+        judges,
+        judge_weight_bps,
+        judge_fee_bps,
+        voter_badges,
+        min_participant_reputation,
+        min_voter_reputation,
+    });
 
     Ok(())
 }
@@ -0,0 +1,126 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::SeasonCreated;
+use crate::instructions::challenge::treasury::create_associated_token_account;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(season_id: u64)]
+pub struct CreateSeason<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // The tracker authority is the platform admin
+    #[account(
+        seeds = [b"challenge_tracker"],
+        bump,
+        constraint = challenge_tracker.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub challenge_tracker: Box<Account<'info, ChallengeTracker>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SeasonConfig::SPACE,
+        seeds = [b"season_config"],
+        bump,
+    )]
+    pub season_config: Box<Account<'info, SeasonConfig>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Season::SPACE,
+        seeds = [b"season", season_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub season: Box<Account<'info, Season>>,
+
+    /// CHECK: Season's CPT token account holding its prize pool, created here
+    #[account(mut)]
+    pub season_token_account: AccountInfo<'info>,
+
+    /// CHECK: CPT mint
+    #[account(address = CPT_TOKEN_MINT.parse::<Pubkey>().unwrap())]
+    pub cpt_mint: AccountInfo<'info>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Associated Token Program
+    #[account(address = ASSOCIATED_TOKEN_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub associated_token_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(
+    ctx: Context<CreateSeason>,
+    season_id: u64,
+    start_time: i64,
+    end_time: i64,
+    pool_share_bps: u16,
+    payout_places: u8,
+) -> Result<()> {
+    let config = &mut ctx.accounts.season_config;
+
+    // Seasons are numbered from one and never overlap
+    require!(
+        season_id == config.current_season + 1,
+        ErrorCode::InvalidSeason
+    );
+    require!(
+        start_time < end_time && start_time >= config.current_end,
+        ErrorCode::InvalidSeasonConfig
+    );
+    require!(
+        pool_share_bps <= BASIS_POINTS,
+        ErrorCode::InvalidSeasonConfig
+    );
+    require!(
+        payout_places > 0 && payout_places as usize <= SEASON_LEADERBOARD_SIZE,
+        ErrorCode::InvalidSeasonConfig
+    );
+
+    create_associated_token_account(
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.season_token_account,
+        &ctx.accounts.season.to_account_info(),
+        &ctx.accounts.cpt_mint,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+
+    let season = &mut ctx.accounts.season;
+    season.id = season_id;
+    season.start_time = start_time;
+    season.end_time = end_time;
+    season.pool_share_bps = pool_share_bps;
+    season.payout_places = payout_places;
+
+    config.authority = ctx.accounts.authority.key();
+    config.current_season = season_id;
+    config.current_start = start_time;
+    config.current_end = end_time;
+
+    emit!(SeasonCreated {
+        season: season_id,
+        start_time,
+        end_time,
+        pool_share_bps,
+        payout_places,
+    });
+
+    msg!(
+        "Season {} runs from {} to {}, paying {} places",
+        season_id,
+        start_time,
+        end_time,
+        payout_places
+    );
+
+    Ok(())
+}
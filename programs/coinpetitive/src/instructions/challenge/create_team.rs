@@ -0,0 +1,143 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::TeamCreated;
+use crate::instructions::challenge::profile::{open_profile, require_reputation};
+use crate::instructions::challenge::treasury::create_associated_token_account;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateTeam<'info> {
+    #[account(mut)]
+    pub captain: Signer<'info>,
+
+    #[account(constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen)]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    #[account(
+        init,
+        payer = captain,
+        space = Team::SPACE,
+        seeds = [b"team", challenge.key().as_ref(), captain.key().as_ref()],
+        bump,
+    )]
+    pub team: Box<Account<'info, Team>>,
+
+    // The team's own record, so a win is credited like any wallet's
+    #[account(
+        init,
+        payer = captain,
+        space = UserProfile::SPACE,
+        seeds = [b"user_profile", team.key().as_ref()],
+        bump,
+    )]
+    pub team_profile: Box<Account<'info, UserProfile>>,
+
+    #[account(
+        init_if_needed,
+        payer = captain,
+        space = UserProfile::SPACE,
+        seeds = [b"user_profile", captain.key().as_ref()],
+        bump,
+    )]
+    pub captain_profile: Box<Account<'info, UserProfile>>,
+
+    /// CHECK: Token state PDA, only counted in once the admin has created it
+    #[account(mut, seeds = [b"token_state"], bump)]
+    pub token_state: UncheckedAccount<'info>,
+
+    /// CHECK: Team's CPT account for prizes and refunds, created here
+    #[account(mut)]
+    pub team_token_account: AccountInfo<'info>,
+
+    /// CHECK: CPT mint
+    #[account(address = CPT_TOKEN_MINT.parse::<Pubkey>().unwrap())]
+    pub cpt_mint: AccountInfo<'info>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Associated Token Program
+    #[account(address = ASSOCIATED_TOKEN_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub associated_token_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<CreateTeam>, members: Vec<Pubkey>, shares_bps: Vec<u16>) -> Result<()> {
+    let challenge = &ctx.accounts.challenge;
+    let captain = ctx.accounts.captain.key();
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        challenge.is_active && challenge.accepts_submissions(now),
+        ErrorCode::SubmissionsClosed
+    );
+    // Round entries are checked wallet by wallet
+    require!(
+        !challenge.is_tournament_round(),
+        ErrorCode::NotQualifiedForRound
+    );
+    require!(!challenge.is_duel, ErrorCode::DuelStakesOnly);
+
+    require!(
+        (2..=MAX_TEAM_MEMBERS).contains(&members.len()) && shares_bps.len() == members.len(),
+        ErrorCode::InvalidTeamConfig
+    );
+    require!(members[0] == captain, ErrorCode::InvalidTeamConfig);
+    for (i, member) in members.iter().enumerate() {
+        require!(!members[..i].contains(member), ErrorCode::InvalidTeamConfig);
+    }
+    let total_bps: u32 = shares_bps.iter().map(|&share| share as u32).sum();
+    require!(
+        total_bps == coinpetitive_math::BASIS_POINTS as u32,
+        ErrorCode::InvalidTeamConfig
+    );
+
+    // The captain answers for the team's reputation
+    let captain_profile = &mut ctx.accounts.captain_profile;
+    open_profile(captain_profile, captain, &ctx.accounts.token_state)?;
+    require_reputation(captain_profile, challenge.min_participant_reputation)?;
+
+    // A team is not a wallet, so it doesn't count towards the unique wallets
+    let team_key = ctx.accounts.team.key();
+    let team_profile = &mut ctx.accounts.team_profile;
+    team_profile.wallet = team_key;
+    team_profile.created_at = now;
+    team_profile.reputation = BASE_REPUTATION;
+
+    create_associated_token_account(
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.captain.to_account_info(),
+        &ctx.accounts.team_token_account,
+        &ctx.accounts.team.to_account_info(),
+        &ctx.accounts.cpt_mint,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+
+    let team = &mut ctx.accounts.team;
+    team.challenge = challenge.key();
+    team.captain = captain;
+    team.paid = vec![false; members.len()];
+    team.members = members;
+    team.shares_bps = shares_bps;
+    team.created_at = now;
+
+    emit!(TeamCreated {
+        team: team_key,
+        challenge: team.challenge,
+        captain,
+        members: team.members.clone(),
+        shares_bps: team.shares_bps.clone(),
+    });
+
+    msg!(
+        "Team {} of {} members created",
+        team_key,
+        team.members.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::TournamentCreated;
+use crate::instructions::challenge::treasury::{
+    create_associated_token_account, transfer_from_signer,
+};
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct CreateTournament<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = Tournament::SPACE,
+        seeds = [b"tournament", creator.key().as_ref(), tournament_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    /// CHECK: Tournament's CPT account escrowing the prize, created here
+    #[account(mut)]
+    pub tournament_token_account: AccountInfo<'info>,
+
+    /// CHECK: Creator's CPT account funding the prize - verified by the transfer
+    #[account(mut)]
+    pub creator_token_account: AccountInfo<'info>,
+
+    /// CHECK: CPT mint
+    #[account(address = CPT_TOKEN_MINT.parse::<Pubkey>().unwrap())]
+    pub cpt_mint: AccountInfo<'info>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Associated Token Program
+    #[account(address = ASSOCIATED_TOKEN_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub associated_token_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(
+    ctx: Context<CreateTournament>,
+    tournament_id: u64,
+    round_count: u8,
+    advance_count: u8,
+    prize: u64,
+    roll_fees: bool,
+) -> Result<()> {
+    require!(
+        round_count >= 2 && round_count as usize <= MAX_TOURNAMENT_ROUNDS,
+        ErrorCode::InvalidTournamentConfig
+    );
+    require!(
+        advance_count > 0 && advance_count as usize <= MAX_SUBMISSIONS,
+        ErrorCode::InvalidTournamentConfig
+    );
+
+    create_associated_token_account(
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.tournament_token_account,
+        &ctx.accounts.tournament.to_account_info(),
+        &ctx.accounts.cpt_mint,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+
+    // The prize is escrowed once, up front, and only released by finish_tournament
+    if prize > 0 {
+        transfer_from_signer(
+            &ctx.accounts.token_program,
+            &ctx.accounts.creator_token_account,
+            &ctx.accounts.tournament_token_account,
+            &ctx.accounts.creator.to_account_info(),
+            prize,
+        )?;
+    }
+
+    let tournament = &mut ctx.accounts.tournament;
+    tournament.creator = ctx.accounts.creator.key();
+    tournament.id = tournament_id;
+    tournament.round_count = round_count;
+    tournament.advance_count = advance_count;
+    tournament.prize = prize;
+    tournament.roll_fees = roll_fees;
+    tournament.created_at = Clock::get()?.unix_timestamp;
+
+    emit!(TournamentCreated {
+        tournament: tournament.key(),
+        creator: tournament.creator,
+        round_count,
+        advance_count,
+        prize,
+        roll_fees,
+    });
+
+    msg!(
+        "Tournament of {} rounds created with a prize of {}",
+        round_count,
+        prize
+    );
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::SubmissionDisqualified;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::profile::lower_reputation;
+use crate::instructions::challenge::propose_results::repropose;
+use crate::instructions::challenge::treasury::transfer_signed;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DisqualifySubmission<'info> {
+    // The platform admin or one of the challenge's judges
+    #[account(mut)]
+    pub moderator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    #[account(
+        init,
+        payer = moderator,
+        space = ModerationRecord::SPACE,
+        seeds = [b"moderation", challenge.key().as_ref(), submission_id.key().as_ref()],
+        bump,
+    )]
+    pub moderation_record: Box<Account<'info, ModerationRecord>>,
+
+    /// CHECK: Just storing submission ID for reference
+    pub submission_id: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"challenge_tracker"],
+        bump,
+    )]
+    pub challenge_tracker: Box<Account<'info, ChallengeTracker>>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Main treasury PDA - verified in handler
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Submitter's token account - owner verified in handler when the fee is refunded
+    #[account(mut)]
+    pub submitter_token_account: AccountInfo<'info>,
+
+    // Submitter's profile - wallet verified in handler
+    #[account(mut)]
+    pub submitter_profile: Box<Account<'info, UserProfile>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<DisqualifySubmission>, reason: DisqualificationReason) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let moderator = ctx.accounts.moderator.key();
+    let submission_id = ctx.accounts.submission_id.key();
+
+    require!(
+        moderator == ctx.accounts.challenge_tracker.authority
+            || challenge.judge_index(&moderator).is_some(),
+        ErrorCode::Unauthorized
+    );
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+    let submitter = challenge
+        .submitter_of(&submission_id)
+        .ok_or(ErrorCode::SubmissionNotFound)?;
+    require!(
+        !challenge.is_disqualified(&submission_id),
+        ErrorCode::SubmissionDisqualified
+    );
+    require_keys_eq!(
+        ctx.accounts.submitter_profile.wallet,
+        submitter,
+        ErrorCode::InvalidProfile
+    );
+
+    // Good-faith entries get their fee back, the rest forfeit it to the treasury
+    let challenge_pubkey = challenge.key();
+    let fee = challenge.submission_fee();
+    let (fee_refunded, fee_slashed) = if reason.refunds_fee() {
+        if fee > 0 {
+            token_account_balance(
+                &ctx.accounts.submitter_token_account,
+                &submitter,
+                &challenge.reward_token_mint,
+            )?;
+            let (_, bump) = Pubkey::find_program_address(
+                &[b"treasury", challenge_pubkey.as_ref()],
+                ctx.program_id,
+            );
+            transfer_signed(
+                &ctx.accounts.token_program,
+                &ctx.accounts.treasury_token_account,
+                &ctx.accounts.submitter_token_account,
+                &ctx.accounts.treasury,
+                &[b"treasury", challenge_pubkey.as_ref(), &[bump]],
+                fee,
+            )?;
+            challenge.debit_treasury(fee)?;
+        }
+        (fee, 0)
+    } else {
+        // Only bad-faith entries cost the submitter reputation
+        lower_reputation(
+            &mut ctx.accounts.submitter_profile,
+            REPUTATION_FOR_DISQUALIFICATION,
+        );
+        (0, fee)
+    };
+
+    challenge.disqualify(&submission_id)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let record = &mut ctx.accounts.moderation_record;
+    record.challenge = challenge_pubkey;
+    record.submission = submission_id;
+    record.submitter = submitter;
+    record.moderator = moderator;
+    record.reason = reason;
+    record.fee_refunded = fee_refunded;
+    record.fee_slashed = fee_slashed;
+    record.disqualified_at = now;
+
+    emit!(SubmissionDisqualified {
+        challenge: challenge_pubkey,
+        submission: submission_id,
+        submitter,
+        moderator,
+        reason,
+        fee_refunded,
+    });
+
+    msg!(
+        "Submission {} disqualified ({:?}), {} tokens refunded",
+        submission_id,
+        reason,
+        fee_refunded
+    );
+
+    // Proposed results no longer stand if they ranked this submission
+    repropose(challenge, challenge_pubkey, moderator)
+}
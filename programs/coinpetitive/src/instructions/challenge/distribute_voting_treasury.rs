@@ -1,10 +1,15 @@
 use crate::constraints::*;
 use crate::errors::ErrorCode;
+use crate::events::{PayoutIssued, PayoutKind};
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::profile::claim_accurate_vote;
+use crate::payout;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
 
 #[derive(Accounts)]
+#[instruction(voter: Pubkey)]
 pub struct DistributeVotingTreasury<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -13,6 +18,7 @@ pub struct DistributeVotingTreasury<'info> {
         mut,
         // Remove this constraint to allow anyone to distribute rewards:
         // constraint = challenge.creator == authority.key() @ ErrorCode::Unauthorized,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
     )]
     pub challenge: Box<Account<'info, Challenge>>,
 
@@ -29,9 +35,21 @@ pub struct DistributeVotingTreasury<'info> {
     #[account(mut)]
     pub voting_treasury_token_account: AccountInfo<'info>,
 
-    /// CHECK: Voter's token account to receive reward
+    /// CHECK: Voter's token account to receive reward - owner verified in handler
     #[account(mut)]
     pub voter_token_account: AccountInfo<'info>,
+
+    // Created when the voter voted
+    #[account(
+        mut,
+        seeds = [b"user_profile", voter.as_ref()],
+        bump,
+    )]
+    pub voter_profile: Box<Account<'info, UserProfile>>,
+
+    // Season the challenge settled in, if any - verified in handler
+    #[account(mut)]
+    pub season: Option<Box<Account<'info, Season>>>,
 }
 
 pub fn handle(
@@ -39,7 +57,7 @@ pub fn handle(
     voter: Pubkey,
     winning_voters_count: u64,
 ) -> Result<()> {
-    let challenge = &ctx.accounts.challenge;
+    let challenge = &mut ctx.accounts.challenge;
 
     // Verify voting treasury matches the one stored in the challenge
     require!(
@@ -51,18 +69,36 @@ pub fn handle(
     let winning_submission = challenge.winner.ok_or(ErrorCode::ChallengeStillActive)?;
 
     // Check if this voter voted for the winning submission
-    let voted_for_winner = challenge
-        .voters
-        .iter()
-        .any(|(v, s)| *v == voter && *s == winning_submission);
+    let vote_index = challenge
+        .vote_index(&voter, &winning_submission)
+        .ok_or(ErrorCode::VoterDidNotVoteForWinner)?;
+
+    // Each winning vote is paid once, whoever sends the claim, and so is
+    // the reputation and season points that come with it
+    claim_accurate_vote(
+        challenge,
+        vote_index,
+        &mut ctx.accounts.voter_profile,
+        ctx.accounts.season.as_mut().map(|season| &mut ***season),
+    )?;
 
-    require!(voted_for_winner, ErrorCode::VoterDidNotVoteForWinner);
+    // Flat challenges split the pool by the winning voters count, which has
+    // to match the votes on record
+    let weighted = challenge.voting_mode != VotingMode::Flat;
+    require!(
+        weighted || winning_voters_count == challenge.voters_for(&winning_submission),
+        ErrorCode::InvalidVoteCount
+    );
 
-    // Validate the winning voters count
-    require!(winning_voters_count > 0, ErrorCode::InvalidVoteCount);
+    // Rewards can only land in the voter's own account
+    token_account_balance(
+        &ctx.accounts.voter_token_account,
+        &voter,
+        &challenge.reward_token_mint,
+    )?;
 
-    // Calculate this voter's reward using the count provided from PocketBase
-    let reward_per_voter = challenge.voting_treasury / winning_voters_count;
+    // Calculate this voter's reward from the pool frozen at finalization
+    let reward_per_voter = payout::voter_reward(challenge, &voter, winning_voters_count);
 
     // Make sure there's a reward to distribute
     require!(reward_per_voter > 0, ErrorCode::NoRewardToDistribute);
@@ -83,9 +119,6 @@ pub fn handle(
         voter
     );
 
-    // Verify that the voter_token_account belongs to the voter
-    // In production this would need more verification
-
     let voter_transfer_ix = solana_program::instruction::Instruction {
         program_id: ctx.accounts.token_program.key(),
         accounts: vec![
@@ -104,7 +137,7 @@ pub fn handle(
         ],
         data: [3]
             .into_iter() // Token instruction 3 = Transfer
-            .chain(reward_per_voter.to_le_bytes().into_iter())
+            .chain(reward_per_voter.to_le_bytes())
             .collect(),
     };
 
@@ -118,6 +151,26 @@ pub fn handle(
         &[voting_treasury_seeds],
     )?;
 
+    challenge.debit_voting_treasury(reward_per_voter)?;
+    if weighted {
+        if let Some(weight) = challenge.vote_weights.get_mut(vote_index) {
+            *weight = 0;
+        }
+    }
+
+    let voter_profile = &mut ctx.accounts.voter_profile;
+    voter_profile.cpt_earned = voter_profile
+        .cpt_earned
+        .checked_add(reward_per_voter)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(PayoutIssued {
+        challenge: challenge_pubkey,
+        kind: PayoutKind::Voter,
+        recipient: voter,
+        amount: reward_per_voter,
+    });
+
     msg!(
         "Transferred {} tokens to voter: {}",
         reward_per_voter,
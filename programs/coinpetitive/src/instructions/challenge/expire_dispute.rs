@@ -0,0 +1,98 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::DisputeExpired;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::treasury::transfer_signed;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ExpireDispute<'info> {
+    // Anyone, once the resolution period is over
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    #[account(
+        mut,
+        close = disputer,
+        seeds = [b"dispute", challenge.key().as_ref(), dispute.submission.as_ref()],
+        bump,
+    )]
+    pub dispute: Box<Account<'info, Dispute>>,
+
+    /// CHECK: The wallet that raised the dispute, receives the account's rent
+    #[account(mut, address = dispute.disputer)]
+    pub disputer: AccountInfo<'info>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Main treasury PDA - verified in handler
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Disputer's token account - owner verified in handler
+    #[account(mut)]
+    pub disputer_token_account: AccountInfo<'info>,
+}
+
+// A dispute nobody resolved in time is dropped: the bond goes back to the
+// disputer, the submission keeps its place and nobody's reputation changes
+pub fn handle(ctx: Context<ExpireDispute>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let submission = ctx.accounts.dispute.submission;
+    let disputer = ctx.accounts.dispute.disputer;
+    let bond = ctx.accounts.dispute.bond;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        challenge.disputes_expired(now),
+        ErrorCode::DisputeResolutionOpen
+    );
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+
+    challenge.release_bond(bond)?;
+
+    let challenge_pubkey = challenge.key();
+    if bond > 0 {
+        token_account_balance(
+            &ctx.accounts.disputer_token_account,
+            &disputer,
+            &challenge.reward_token_mint,
+        )?;
+        let (_, bump) =
+            Pubkey::find_program_address(&[b"treasury", challenge_pubkey.as_ref()], ctx.program_id);
+        transfer_signed(
+            &ctx.accounts.token_program,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.disputer_token_account,
+            &ctx.accounts.treasury,
+            &[b"treasury", challenge_pubkey.as_ref(), &[bump]],
+            bond,
+        )?;
+    }
+
+    emit!(DisputeExpired {
+        challenge: challenge_pubkey,
+        submission,
+        disputer,
+        bond,
+    });
+
+    msg!("Dispute against submission {} expired", submission);
+
+    Ok(())
+}
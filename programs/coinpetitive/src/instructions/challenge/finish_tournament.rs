@@ -0,0 +1,115 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::TournamentFinished;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::treasury::transfer_signed;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct FinishTournament<'info> {
+    // Anyone may release the prize once the final settles, or a round is cancelled
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !tournament.finished @ ErrorCode::TournamentFinished,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(
+        constraint = Some(round.key()) == tournament.current_round_challenge()
+            @ ErrorCode::TournamentRoundNotOpen,
+    )]
+    pub round: Box<Account<'info, Challenge>>,
+
+    /// CHECK: Tournament's CPT escrow - verified in handler
+    #[account(mut)]
+    pub tournament_token_account: AccountInfo<'info>,
+
+    /// CHECK: CPT account of the final's winner, or of the creator after a
+    /// cancelled round - verified in handler
+    #[account(mut)]
+    pub recipient_token_account: AccountInfo<'info>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn handle(ctx: Context<FinishTournament>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let round = &ctx.accounts.round;
+
+    // A cancelled round ends the tournament and the creator gets the prize back
+    let refunded = round.is_cancelled;
+    let recipient = if refunded {
+        tournament.creator
+    } else {
+        require!(
+            tournament.is_final_round(),
+            ErrorCode::InvalidTournamentRound
+        );
+        let winning_submission = match (round.is_active, round.winner) {
+            (false, Some(winner)) => winner,
+            _ => return err!(ErrorCode::ChallengeStillActive),
+        };
+        round
+            .submitter_of(&winning_submission)
+            .ok_or(ErrorCode::InvalidWinner)?
+    };
+
+    // Rolled round fees sit in the escrow next to the prize, so all of it is paid
+    let cpt_mint = CPT_TOKEN_MINT.parse::<Pubkey>().unwrap();
+    let tournament_pubkey = tournament.key();
+    let amount = token_account_balance(
+        &ctx.accounts.tournament_token_account,
+        &tournament_pubkey,
+        &cpt_mint,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidTournament))?;
+    token_account_balance(&ctx.accounts.recipient_token_account, &recipient, &cpt_mint)
+        .map_err(|_| error!(ErrorCode::InvalidTokenAccount))?;
+
+    if amount > 0 {
+        let id_bytes = tournament.id.to_le_bytes();
+        let (_, bump) = Pubkey::find_program_address(
+            &[
+                b"tournament",
+                tournament.creator.as_ref(),
+                id_bytes.as_ref(),
+            ],
+            ctx.program_id,
+        );
+        let tournament_seeds: &[&[u8]] = &[
+            b"tournament",
+            tournament.creator.as_ref(),
+            id_bytes.as_ref(),
+            &[bump],
+        ];
+        transfer_signed(
+            &ctx.accounts.token_program,
+            &ctx.accounts.tournament_token_account,
+            &ctx.accounts.recipient_token_account,
+            &tournament.to_account_info(),
+            tournament_seeds,
+            amount,
+        )?;
+    }
+
+    tournament.finished = true;
+    if !refunded {
+        tournament.winner = Some(recipient);
+    }
+
+    emit!(TournamentFinished {
+        tournament: tournament_pubkey,
+        recipient,
+        amount,
+        refunded,
+    });
+
+    msg!("Tournament finished: {} paid to {}", amount, recipient);
+
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::PrizeFunded;
+use crate::instructions::challenge::audit_challenge::token_account_balance_in;
+use crate::instructions::challenge::treasury::{
+    create_associated_token_account, transfer_checked_from_signer,
+};
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program;
+use anchor_lang::solana_program::system_instruction;
+
+#[derive(Accounts)]
+pub struct FundPrize<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+        constraint = challenge.creator == creator.key() @ ErrorCode::InvalidCreator,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    /// CHECK: Mint of a Token prize, owner verified in handler; unused for SOL
+    pub prize_mint: AccountInfo<'info>,
+
+    /// CHECK: SPL Token or Token-2022 program of the prize mint, or the system program for SOL
+    pub prize_token_program: AccountInfo<'info>,
+
+    /// CHECK: Creator's token account for the prize mint; unused for SOL
+    #[account(mut)]
+    pub creator_prize_account: AccountInfo<'info>,
+
+    /// CHECK: Main treasury PDA - verified in handler
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account for the prize mint, created here if needed;
+    /// the treasury PDA itself for SOL
+    #[account(mut)]
+    pub prize_escrow: AccountInfo<'info>,
+
+    /// CHECK: Associated Token Program
+    #[account(address = ASSOCIATED_TOKEN_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub associated_token_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<FundPrize>, kind: PrizeKind) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+
+    // Entrants and sponsors signed up for a CPT prize, so it can only be
+    // swapped before any of them did
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        challenge.accepts_submissions(now),
+        ErrorCode::SubmissionsClosed
+    );
+    require!(
+        challenge.prize_kind == PrizeKind::Cpt
+            && challenge.participants.is_empty()
+            && challenge.submitters.is_empty()
+            && challenge.sponsors.is_empty(),
+        ErrorCode::PrizeLocked
+    );
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+
+    // The whole reward is escrowed up front, in the prize's own units
+    let amount = challenge.reward;
+    require!(amount > 0, ErrorCode::InvalidPrize);
+
+    let prize_token_program = ctx.accounts.prize_token_program.key();
+    let (prize_mint, escrowed) = match kind {
+        PrizeKind::Cpt => return err!(ErrorCode::InvalidPrize),
+        PrizeKind::Token => {
+            require!(
+                prize_token_program == anchor_spl::token::ID
+                    || prize_token_program == spl_token_2022::ID,
+                ErrorCode::InvalidTokenProgram
+            );
+            let mint = &ctx.accounts.prize_mint;
+            require!(
+                mint.owner == &prize_token_program && mint.key() != challenge.reward_token_mint,
+                ErrorCode::InvalidPrize
+            );
+
+            create_associated_token_account(
+                &ctx.accounts.associated_token_program,
+                &ctx.accounts.creator.to_account_info(),
+                &ctx.accounts.prize_escrow,
+                &ctx.accounts.treasury,
+                mint,
+                &ctx.accounts.system_program.to_account_info(),
+                &ctx.accounts.prize_token_program,
+            )?;
+            let escrow_balance = || {
+                token_account_balance_in(
+                    &ctx.accounts.prize_escrow,
+                    &prize_token_program,
+                    &challenge.treasury,
+                    &mint.key(),
+                )
+                .map_err(|_| error!(ErrorCode::InvalidPrizeAccount))
+            };
+            let before = escrow_balance()?;
+
+            transfer_checked_from_signer(
+                &ctx.accounts.prize_token_program,
+                &ctx.accounts.creator_prize_account,
+                mint,
+                &ctx.accounts.prize_escrow,
+                &ctx.accounts.creator.to_account_info(),
+                amount,
+            )?;
+
+            // Transfer fees and the like can land less than was sent
+            let escrowed = escrow_balance()?
+                .checked_sub(before)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(escrowed > 0, ErrorCode::InvalidPrize);
+            (mint.key(), escrowed)
+        }
+        PrizeKind::Sol => {
+            require!(
+                prize_token_program == solana_program::system_program::ID
+                    && ctx.accounts.prize_escrow.key() == challenge.treasury,
+                ErrorCode::InvalidPrizeAccount
+            );
+
+            // Held in the treasury PDA on top of its rent and gas
+            solana_program::program::invoke(
+                &system_instruction::transfer(
+                    &ctx.accounts.creator.key(),
+                    &challenge.treasury,
+                    amount,
+                ),
+                &[
+                    ctx.accounts.creator.to_account_info(),
+                    ctx.accounts.treasury.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+            (Pubkey::default(), amount)
+        }
+    };
+
+    // Nobody has signed up yet, so the reward becomes whatever the escrow
+    // actually received
+    challenge.prize_kind = kind;
+    challenge.prize_mint = prize_mint;
+    challenge.prize_token_program = prize_token_program;
+    challenge.prize_pool = escrowed;
+    challenge.reward = escrowed;
+
+    emit!(PrizeFunded {
+        challenge: challenge.key(),
+        kind,
+        mint: prize_mint,
+        amount: escrowed,
+    });
+
+    msg!("Escrowed a {:?} prize of {}", kind, escrowed);
+
+    Ok(())
+}
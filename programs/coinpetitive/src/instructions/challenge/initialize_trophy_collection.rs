@@ -0,0 +1,165 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::TrophyCollectionCreated;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{
+        create_master_edition_v3, create_metadata_accounts_v3,
+        mpl_token_metadata::types::{CollectionDetails, DataV2},
+        CreateMasterEditionV3, CreateMetadataAccountsV3, Metadata as Metaplex,
+    },
+    token::{mint_to, Mint, MintTo, Token, TokenAccount},
+};
+
+#[derive(Accounts)]
+pub struct InitializeTrophyCollection<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // The tracker authority is the platform admin
+    #[account(
+        seeds = [b"challenge_tracker"],
+        bump,
+        constraint = challenge_tracker.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub challenge_tracker: Box<Account<'info, ChallengeTracker>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TrophyCollection::SPACE,
+        seeds = [b"trophy_collection"],
+        bump,
+    )]
+    pub trophy_collection: Box<Account<'info, TrophyCollection>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"trophy_collection_mint"],
+        bump,
+        mint::decimals = 0,
+        mint::authority = trophy_collection,
+        mint::freeze_authority = trophy_collection,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    // The collection NFT itself stays with the collection PDA
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = collection_mint,
+        associated_token::authority = trophy_collection,
+    )]
+    pub collection_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Collection metadata PDA, created and checked by Metaplex
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Collection master edition PDA, created and checked by Metaplex
+    #[account(mut)]
+    pub collection_edition: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metaplex>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle(
+    ctx: Context<InitializeTrophyCollection>,
+    symbol: String,
+    base_uri: String,
+) -> Result<()> {
+    require!(
+        symbol.len() <= MAX_TROPHY_SYMBOL_LEN && base_uri.len() <= MAX_TROPHY_URI_LEN,
+        ErrorCode::InvalidTrophyMetadata
+    );
+
+    let bump = ctx.bumps.trophy_collection;
+    let seeds: &[&[u8]] = &[b"trophy_collection", &[bump]];
+    let signer = [seeds];
+
+    // A collection NFT is a 1-of-1 like the trophies it groups
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.collection_mint.to_account_info(),
+                to: ctx.accounts.collection_token_account.to_account_info(),
+                authority: ctx.accounts.trophy_collection.to_account_info(),
+            },
+            &signer,
+        ),
+        1,
+    )?;
+
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.collection_metadata.to_account_info(),
+                mint: ctx.accounts.collection_mint.to_account_info(),
+                mint_authority: ctx.accounts.trophy_collection.to_account_info(),
+                payer: ctx.accounts.authority.to_account_info(),
+                update_authority: ctx.accounts.trophy_collection.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            &signer,
+        ),
+        DataV2 {
+            name: TROPHY_COLLECTION_NAME.to_string(),
+            symbol: symbol.clone(),
+            uri: format!("{}/collection.json", base_uri),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        true,
+        true,
+        Some(CollectionDetails::V1 { size: 0 }),
+    )?;
+
+    create_master_edition_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMasterEditionV3 {
+                edition: ctx.accounts.collection_edition.to_account_info(),
+                mint: ctx.accounts.collection_mint.to_account_info(),
+                update_authority: ctx.accounts.trophy_collection.to_account_info(),
+                mint_authority: ctx.accounts.trophy_collection.to_account_info(),
+                payer: ctx.accounts.authority.to_account_info(),
+                metadata: ctx.accounts.collection_metadata.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            &signer,
+        ),
+        Some(0),
+    )?;
+
+    let collection = &mut ctx.accounts.trophy_collection;
+    collection.authority = ctx.accounts.authority.key();
+    collection.mint = ctx.accounts.collection_mint.key();
+    collection.symbol = symbol;
+    collection.base_uri = base_uri;
+    collection.minted = 0;
+
+    emit!(TrophyCollectionCreated {
+        authority: collection.authority,
+        mint: collection.mint,
+        symbol: collection.symbol.clone(),
+        base_uri: collection.base_uri.clone(),
+    });
+
+    msg!("Trophy collection {} created", collection.mint);
+
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::TrophyMinted;
+use crate::payout;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{
+        create_master_edition_v3, create_metadata_accounts_v3,
+        mpl_token_metadata::types::{Collection, DataV2},
+        verify_sized_collection_item, CreateMasterEditionV3, CreateMetadataAccountsV3,
+        Metadata as Metaplex, VerifySizedCollectionItem,
+    },
+    token::{mint_to, Mint, MintTo, Token, TokenAccount},
+};
+
+#[derive(Accounts)]
+#[instruction(rank: u8)]
+pub struct MintTrophy<'info> {
+    // Anyone may mint a settled challenge's trophies, paying their rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = !challenge.is_active @ ErrorCode::ChallengeStillActive,
+        constraint = !challenge.is_cancelled @ ErrorCode::ChallengeCancelled,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    /// CHECK: Wallet behind the placed submission - verified in handler
+    pub winner: AccountInfo<'info>,
+
+    // One trophy per place, so it cannot be minted twice
+    #[account(
+        init,
+        payer = payer,
+        space = Trophy::SPACE,
+        seeds = [b"trophy", challenge.key().as_ref(), &[rank]],
+        bump,
+    )]
+    pub trophy: Box<Account<'info, Trophy>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"trophy_mint", challenge.key().as_ref(), &[rank]],
+        bump,
+        mint::decimals = 0,
+        mint::authority = trophy_collection,
+        mint::freeze_authority = trophy_collection,
+    )]
+    pub trophy_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = trophy_mint,
+        associated_token::authority = winner,
+    )]
+    pub winner_trophy_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Trophy metadata PDA, created and checked by Metaplex
+    #[account(mut)]
+    pub trophy_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Trophy master edition PDA, created and checked by Metaplex
+    #[account(mut)]
+    pub trophy_edition: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"trophy_collection"], bump)]
+    pub trophy_collection: Box<Account<'info, TrophyCollection>>,
+
+    #[account(address = trophy_collection.mint)]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Collection metadata PDA, its size is bumped by Metaplex
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Collection master edition PDA, checked by Metaplex
+    pub collection_edition: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metaplex>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle(ctx: Context<MintTrophy>, rank: u8) -> Result<()> {
+    let challenge = &ctx.accounts.challenge;
+
+    // The places are ranked exactly as settle_results paid them
+    require!(rank >= 1, ErrorCode::InvalidTrophyRank);
+    let places = payout::trophy_places(challenge)?;
+    let (submission, votes) = *places
+        .get(usize::from(rank - 1))
+        .ok_or(ErrorCode::InvalidTrophyRank)?;
+    require!(
+        rank != 1 || challenge.winner == Some(submission),
+        ErrorCode::InvalidWinner
+    );
+    let winner = challenge
+        .submitter_of(&submission)
+        .ok_or(ErrorCode::InvalidWinner)?;
+    require_keys_eq!(ctx.accounts.winner.key(), winner, ErrorCode::InvalidWinner);
+
+    let bump = ctx.bumps.trophy_collection;
+    let seeds: &[&[u8]] = &[b"trophy_collection", &[bump]];
+    let signer = [seeds];
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.trophy_mint.to_account_info(),
+                to: ctx.accounts.winner_trophy_account.to_account_info(),
+                authority: ctx.accounts.trophy_collection.to_account_info(),
+            },
+            &signer,
+        ),
+        1,
+    )?;
+
+    // Only the collection PDA could update the metadata, and no instruction
+    // lets it, so the trophy is fixed once minted
+    let collection_mint = ctx.accounts.collection_mint.key();
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.trophy_metadata.to_account_info(),
+                mint: ctx.accounts.trophy_mint.to_account_info(),
+                mint_authority: ctx.accounts.trophy_collection.to_account_info(),
+                payer: ctx.accounts.payer.to_account_info(),
+                update_authority: ctx.accounts.trophy_collection.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            &signer,
+        ),
+        DataV2 {
+            name: TROPHY_NAMES[usize::from(rank - 1)].to_string(),
+            symbol: ctx.accounts.trophy_collection.symbol.clone(),
+            uri: format!(
+                "{}/{}/{}.json",
+                ctx.accounts.trophy_collection.base_uri,
+                challenge.key(),
+                rank
+            ),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: Some(Collection {
+                verified: false,
+                key: collection_mint,
+            }),
+            uses: None,
+        },
+        true,
+        true,
+        None,
+    )?;
+
+    // A zero-supply master edition makes the trophy a 1-of-1 NFT
+    create_master_edition_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMasterEditionV3 {
+                edition: ctx.accounts.trophy_edition.to_account_info(),
+                mint: ctx.accounts.trophy_mint.to_account_info(),
+                update_authority: ctx.accounts.trophy_collection.to_account_info(),
+                mint_authority: ctx.accounts.trophy_collection.to_account_info(),
+                payer: ctx.accounts.payer.to_account_info(),
+                metadata: ctx.accounts.trophy_metadata.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            &signer,
+        ),
+        Some(0),
+    )?;
+
+    verify_sized_collection_item(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            VerifySizedCollectionItem {
+                payer: ctx.accounts.payer.to_account_info(),
+                metadata: ctx.accounts.trophy_metadata.to_account_info(),
+                collection_authority: ctx.accounts.trophy_collection.to_account_info(),
+                collection_mint: ctx.accounts.collection_mint.to_account_info(),
+                collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+                collection_master_edition: ctx.accounts.collection_edition.to_account_info(),
+            },
+            &signer,
+        ),
+        None,
+    )?;
+
+    let trophy = &mut ctx.accounts.trophy;
+    trophy.challenge = challenge.key();
+    trophy.submission = submission;
+    trophy.winner = winner;
+    trophy.mint = ctx.accounts.trophy_mint.key();
+    trophy.rank = rank;
+    trophy.votes = votes;
+    trophy.minted_at = Clock::get()?.unix_timestamp;
+
+    let collection = &mut ctx.accounts.trophy_collection;
+    collection.minted = collection.minted.saturating_add(1);
+
+    emit!(TrophyMinted {
+        challenge: trophy.challenge,
+        submission,
+        winner,
+        mint: trophy.mint,
+        rank,
+        votes,
+    });
+
+    msg!("Minted trophy #{} to {}", rank, winner);
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::{DuelOpened, ParticipantJoined};
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::treasury::transfer_from_signer;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct OpenDuel<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+        constraint = challenge.creator == creator.key() @ ErrorCode::InvalidCreator,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    // Created with the challenge
+    #[account(
+        mut,
+        seeds = [b"user_profile", creator.key().as_ref()],
+        bump,
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Creator's token account
+    #[account(mut)]
+    pub creator_token_account: AccountInfo<'info>,
+
+    /// CHECK: Main treasury PDA - verified in handler
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account - verified in handler
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+}
+
+pub fn handle(
+    ctx: Context<OpenDuel>,
+    stake: u64,
+    opponent: Option<Pubkey>,
+    accept_by: i64,
+) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let creator = ctx.accounts.creator.key();
+
+    // The stakes become the whole prize, so nobody else can have a claim on it
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        challenge.accepts_submissions(now),
+        ErrorCode::SubmissionsClosed
+    );
+    require!(
+        !challenge.is_duel
+            && challenge.reward == 0
+            && challenge.prize_kind == PrizeKind::Cpt
+            && !challenge.is_tournament_round()
+            && challenge.participants.is_empty()
+            && challenge.submitters.is_empty()
+            && challenge.sponsors.is_empty(),
+        ErrorCode::DuelLocked
+    );
+    require!(
+        stake > 0 && now < accept_by && accept_by <= challenge.submission_end,
+        ErrorCode::InvalidDuel
+    );
+    require!(opponent != Some(creator), ErrorCode::NotDuelOpponent);
+
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+    token_account_balance(
+        &ctx.accounts.treasury_token_account,
+        &challenge.treasury,
+        &challenge.reward_token_mint,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidTreasury))?;
+
+    transfer_from_signer(
+        &ctx.accounts.token_program,
+        &ctx.accounts.creator_token_account,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.creator.to_account_info(),
+        stake,
+    )?;
+    challenge.credit_treasury(stake)?;
+
+    // The stake stands in for the participation fee, so a cancelled duel
+    // refunds it through claim_refund like any other entry
+    challenge.is_duel = true;
+    challenge.duel_opponent = opponent.unwrap_or_default();
+    challenge.duel_accept_by = accept_by;
+    challenge.participation_fee = stake;
+    challenge.reward = stake;
+    challenge.min_participants = 2;
+    challenge.max_participants = 2;
+    challenge.participants.push(creator);
+
+    let profile = &mut ctx.accounts.user_profile;
+    profile.challenges_joined = profile
+        .challenges_joined
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(ParticipantJoined {
+        challenge: challenge.key(),
+        participant: creator,
+        fee: stake,
+    });
+    emit!(DuelOpened {
+        challenge: challenge.key(),
+        creator,
+        opponent: challenge.duel_opponent,
+        stake,
+        accept_by,
+    });
+
+    msg!(
+        "{} opened a duel for {} tokens, to be matched by {}",
+        creator,
+        stake,
+        accept_by
+    );
+
+    Ok(())
+}
@@ -1,5 +1,8 @@
 use crate::constraints::*;
 use crate::errors::ErrorCode;
+use crate::events::{FeeTrackerUpdated, ParticipantJoined};
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::profile::{open_profile, require_reputation};
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
@@ -9,9 +12,15 @@ pub struct PayParticipationFee<'info> {
     #[account(mut)]
     pub participant: Signer<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
     pub challenge: Box<Account<'info, Challenge>>,
 
+    // Required when the challenge is a tournament round
+    pub tournament: Option<Box<Account<'info, Tournament>>>,
+
     /// CHECK: Treasury account (PDA)
     #[account(
         mut,
@@ -40,6 +49,20 @@ pub struct PayParticipationFee<'info> {
     )]
     pub fee_tracker: Account<'info, FeeTracker>,
 
+    // Created by the wallet's first challenge action
+    #[account(
+        init_if_needed,
+        payer = participant,
+        space = UserProfile::SPACE,
+        seeds = [b"user_profile", participant.key().as_ref()],
+        bump,
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    /// CHECK: Token state PDA, only counted in once the admin has created it
+    #[account(mut, seeds = [b"token_state"], bump)]
+    pub token_state: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -52,6 +75,19 @@ pub fn handle(ctx: Context<PayParticipationFee>) -> Result<()> {
         ctx.accounts.treasury.key() == challenge.treasury,
         ErrorCode::InvalidTreasury
     );
+    token_account_balance(
+        &ctx.accounts.treasury_token_account,
+        &challenge.treasury,
+        &challenge.reward_token_mint,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidTreasury))?;
+
+    // Entries close together with submissions
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        challenge.is_active && challenge.accepts_submissions(now),
+        ErrorCode::SubmissionsClosed
+    );
 
     // Check if participant has already paid
     require!(
@@ -59,6 +95,27 @@ pub fn handle(ctx: Context<PayParticipationFee>) -> Result<()> {
         ErrorCode::AlreadyParticipated
     );
 
+    // Duels are joined by matching the creator's stake
+    require!(!challenge.is_duel, ErrorCode::DuelStakesOnly);
+
+    // Past the first round, only wallets that qualified may enter
+    if challenge.is_tournament_round() {
+        let tournament = ctx
+            .accounts
+            .tournament
+            .as_ref()
+            .filter(|t| t.key() == challenge.tournament)
+            .ok_or(ErrorCode::InvalidTournament)?;
+        require!(
+            challenge.tournament_round == tournament.current_round,
+            ErrorCode::TournamentRoundNotOpen
+        );
+        require!(
+            tournament.admits(challenge.tournament_round, &participant_key),
+            ErrorCode::NotQualifiedForRound
+        );
+    }
+
     // Check max participants only if it's set
     if challenge.max_participants > 0 {
         require!(
@@ -95,7 +152,7 @@ pub fn handle(ctx: Context<PayParticipationFee>) -> Result<()> {
         // Token instruction 3 = Transfer, followed by amount as little-endian bytes
         data: [3]
             .into_iter()
-            .chain(challenge.participation_fee.to_le_bytes().into_iter())
+            .chain(challenge.participation_fee.to_le_bytes())
             .collect(),
     };
 
@@ -110,11 +167,25 @@ pub fn handle(ctx: Context<PayParticipationFee>) -> Result<()> {
     )?;
 
     // Update challenge treasury
-    challenge.challenge_treasury += challenge.participation_fee;
+    let participation_fee = challenge.participation_fee;
+    challenge.credit_treasury(participation_fee)?;
 
     // Add participant to the list
     challenge.participants.push(participant_key);
 
+    // Count the entry on the participant's profile
+    let profile = &mut ctx.accounts.user_profile;
+    open_profile(profile, participant_key, &ctx.accounts.token_state)?;
+    require_reputation(profile, challenge.min_participant_reputation)?;
+    profile.challenges_joined = profile
+        .challenges_joined
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    profile.fees_paid = profile
+        .fees_paid
+        .checked_add(participation_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+
     // Update fee tracker with participation fee
     let fee_tracker = &mut ctx.accounts.fee_tracker;
     fee_tracker.total_participation_fees = fee_tracker
@@ -122,6 +193,17 @@ pub fn handle(ctx: Context<PayParticipationFee>) -> Result<()> {
         .checked_add(challenge.participation_fee)
         .ok_or(ProgramError::ArithmeticOverflow)?;
 
+    emit!(ParticipantJoined {
+        challenge: challenge.key(),
+        participant: participant_key,
+        fee: participation_fee,
+    });
+    emit!(FeeTrackerUpdated {
+        total_participation_fees: fee_tracker.total_participation_fees,
+        total_voting_fees: fee_tracker.total_voting_fees,
+        total_challenges: fee_tracker.total_challenges,
+    });
+
     msg!("Participation fee paid successfully");
     msg!("Participant {} added to challenge", participant_key);
     msg!(
@@ -0,0 +1,166 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::{FeeTrackerUpdated, ParticipantJoined, TeamFeePaid};
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::treasury::transfer_from_signer;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct PayTeamFee<'info> {
+    // A member paying their own part, or the captain paying every part left
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !team.entered @ ErrorCode::AlreadyParticipated,
+    )]
+    pub team: Box<Account<'info, Team>>,
+
+    #[account(
+        mut,
+        address = team.challenge @ ErrorCode::InvalidTeam,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    /// CHECK: Treasury account (PDA) - verified in handler
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Payer's token account
+    #[account(mut)]
+    pub payer_token_account: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = FeeTracker::SPACE,
+        seeds = [b"fee_tracker"],
+        bump,
+    )]
+    pub fee_tracker: Account<'info, FeeTracker>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<PayTeamFee>, all: bool) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let team = &mut ctx.accounts.team;
+    let payer = ctx.accounts.payer.key();
+
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+    token_account_balance(
+        &ctx.accounts.treasury_token_account,
+        &challenge.treasury,
+        &challenge.reward_token_mint,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidTreasury))?;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        challenge.is_active && challenge.accepts_submissions(now),
+        ErrorCode::SubmissionsClosed
+    );
+
+    // Whose parts of the one participation fee this payment covers
+    let covered: Vec<usize> = if all {
+        require_keys_eq!(payer, team.captain, ErrorCode::Unauthorized);
+        (0..team.members.len()).filter(|&i| !team.paid[i]).collect()
+    } else {
+        let i = team.member_index(&payer).ok_or(ErrorCode::NotTeamMember)?;
+        require!(!team.paid[i], ErrorCode::TeamFeeAlreadyPaid);
+        vec![i]
+    };
+    require!(!covered.is_empty(), ErrorCode::TeamFeeAlreadyPaid);
+
+    // Parts are held outside the ledger until the whole fee is in, so a team
+    // that never enters can take them back
+    let parts = team.split(challenge.participation_fee);
+    let amount: u64 = covered.iter().map(|&i| parts[i]).sum();
+    if amount > 0 {
+        transfer_from_signer(
+            &ctx.accounts.token_program,
+            &ctx.accounts.payer_token_account,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.payer.to_account_info(),
+            amount,
+        )?;
+        challenge.team_fees_held = challenge
+            .team_fees_held
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    for &i in &covered {
+        team.paid[i] = true;
+    }
+
+    let fee_tracker = &mut ctx.accounts.fee_tracker;
+    fee_tracker.total_participation_fees = fee_tracker
+        .total_participation_fees
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // The last part in enters the team as a single participant
+    let team_key = team.key();
+    if team.is_fully_paid() {
+        require!(
+            !challenge.has_participant(&team_key),
+            ErrorCode::AlreadyParticipated
+        );
+        if challenge.max_participants > 0 {
+            require!(
+                challenge.participants.len() < challenge.max_participants as usize,
+                ErrorCode::MaxParticipantsReached
+            );
+        }
+        challenge.participants.push(team_key);
+        team.entered = true;
+
+        // The parts add up to the fee, which now counts as collected
+        let fee = challenge.participation_fee;
+        challenge.team_fees_held = challenge
+            .team_fees_held
+            .checked_sub(fee)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        challenge.credit_treasury(fee)?;
+
+        emit!(ParticipantJoined {
+            challenge: challenge.key(),
+            participant: team_key,
+            fee: challenge.participation_fee,
+        });
+    }
+
+    emit!(TeamFeePaid {
+        team: team_key,
+        payer,
+        members: covered.len() as u8,
+        amount,
+        entered: team.entered,
+    });
+    emit!(FeeTrackerUpdated {
+        total_participation_fees: fee_tracker.total_participation_fees,
+        total_voting_fees: fee_tracker.total_voting_fees,
+        total_challenges: fee_tracker.total_challenges,
+    });
+
+    msg!(
+        "Paid {} towards team {}'s participation fee",
+        amount,
+        team_key
+    );
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use crate::errors::ErrorCode;
+use crate::payout::{self, FinalizationPreview};
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct PreviewFinalization<'info> {
+    #[account(
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    #[account(
+        seeds = [b"crank_config"],
+        bump,
+    )]
+    pub crank_config: Box<Account<'info, CrankConfig>>,
+}
+
+// Read-only: meant to be run through simulateTransaction before finalizing
+pub fn handle(ctx: Context<PreviewFinalization>) -> Result<FinalizationPreview> {
+    let preview = payout::plan_finalization(
+        &ctx.accounts.challenge,
+        ctx.accounts.crank_config.finalize_bounty_bps,
+    )?;
+
+    msg!(
+        "Platform fee: {} (crank bounty {}), places: {}, creator remainder: {}",
+        preview.platform_fee,
+        preview.crank_bounty,
+        preview.places.len(),
+        preview.creator_remainder
+    );
+
+    Ok(preview)
+}
@@ -0,0 +1,22 @@
+use crate::payout::{self, VoterRewardPreview};
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct PreviewVoterReward<'info> {
+    pub challenge: Box<Account<'info, Challenge>>,
+}
+
+// Read-only: works before finalization (current leader) and after (declared winner)
+pub fn handle(ctx: Context<PreviewVoterReward>) -> Result<VoterRewardPreview> {
+    let preview = payout::plan_voter_reward(&ctx.accounts.challenge)?;
+
+    msg!(
+        "{} winning voters share {}: {} each",
+        preview.winning_voters,
+        preview.voting_pool,
+        preview.reward_per_voter
+    );
+
+    Ok(preview)
+}
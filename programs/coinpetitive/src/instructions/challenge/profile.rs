@@ -0,0 +1,149 @@
+use crate::constraints::{
+    BASE_REPUTATION, REPUTATION_FOR_ACCURATE_VOTE, SEASON_POINTS_FOR_ACCURATE_VOTE,
+};
+use crate::errors::ErrorCode;
+use crate::events::{
+    ReputationChanged, SeasonPointsAwarded, UserProfileCreated, WalletMilestoneReached,
+};
+use crate::state::{Challenge, Season, TokenState, UserProfile};
+use anchor_lang::prelude::*;
+
+// Claim a profile the instruction just created for `wallet`, counting the
+// wallet once towards the unique-wallet mint conditions. Wallets are only
+// counted once the admin has created the token state
+pub fn open_profile(
+    profile: &mut UserProfile,
+    wallet: Pubkey,
+    token_state: &AccountInfo,
+) -> Result<()> {
+    if !profile.is_new() {
+        return Ok(());
+    }
+
+    profile.wallet = wallet;
+    profile.created_at = Clock::get()?.unix_timestamp;
+    profile.reputation = BASE_REPUTATION;
+
+    if token_state.owner != &crate::ID {
+        emit!(UserProfileCreated {
+            wallet,
+            unique_wallets: 0,
+        });
+        return Ok(());
+    }
+    let mut state = TokenState::try_deserialize(&mut &token_state.try_borrow_data()?[..])?;
+    let condition = state.track_new_wallet();
+    state.try_serialize(&mut &mut token_state.try_borrow_mut_data()?[..])?;
+
+    emit!(UserProfileCreated {
+        wallet,
+        unique_wallets: state.unique_wallets,
+    });
+
+    if let Some(condition) = condition {
+        emit!(WalletMilestoneReached {
+            unique_wallets: state.unique_wallets,
+            condition,
+        });
+        msg!(
+            "{} unique wallets: mint condition {} met",
+            state.unique_wallets,
+            condition
+        );
+    }
+
+    Ok(())
+}
+
+// Count a vote and the fee paid for it on the voter's profile
+pub fn record_vote(
+    profile: &mut UserProfile,
+    voter: Pubkey,
+    token_state: &AccountInfo,
+    fee: u64,
+) -> Result<()> {
+    open_profile(profile, voter, token_state)?;
+    profile.votes_cast = profile
+        .votes_cast
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    profile.fees_paid = profile
+        .fees_paid
+        .checked_add(fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
+
+// Gate an action on the wallet's reputation; a zero minimum lets anyone through
+pub fn require_reputation(profile: &UserProfile, min: u64) -> Result<()> {
+    require!(profile.reputation >= min, ErrorCode::InsufficientReputation);
+    Ok(())
+}
+
+pub fn raise_reputation(profile: &mut UserProfile, points: u64) {
+    profile.reputation = profile.reputation.saturating_add(points);
+    emit!(ReputationChanged {
+        wallet: profile.wallet,
+        delta: points as i64,
+        reputation: profile.reputation,
+    });
+}
+
+// Reputation bottoms out at zero, so the change emitted is what was taken
+pub fn lower_reputation(profile: &mut UserProfile, points: u64) {
+    let lowered = profile.reputation.saturating_sub(points);
+    let delta = (profile.reputation - lowered) as i64;
+    profile.reputation = lowered;
+    emit!(ReputationChanged {
+        wallet: profile.wallet,
+        delta: -delta,
+        reputation: lowered,
+    });
+}
+
+// Mark a winning vote as paid and credit the voter what comes with it:
+// reputation, and points in the challenge's season until it is settled.
+// A repeated claim fails before anything is credited
+pub fn claim_accurate_vote(
+    challenge: &mut Challenge,
+    vote_index: usize,
+    profile: &mut UserProfile,
+    season: Option<&mut Season>,
+) -> Result<()> {
+    challenge.record_voter_reward(vote_index)?;
+    raise_reputation(profile, REPUTATION_FOR_ACCURATE_VOTE);
+
+    if challenge.season != 0 {
+        let season = season.ok_or(ErrorCode::InvalidSeason)?;
+        require!(season.id == challenge.season, ErrorCode::InvalidSeason);
+        if !season.settled {
+            award_season_points(profile, season, SEASON_POINTS_FOR_ACCURATE_VOTE);
+        }
+    }
+    Ok(())
+}
+
+// Add to the wallet's points for `season`, starting from zero if it last
+// scored in an earlier one, and rank its new total on the leaderboard. The
+// profile only holds its latest season's total, so a late claim from a
+// season it has moved on from scores nothing rather than reset that total.
+// Returns whether the points were awarded
+pub fn award_season_points(profile: &mut UserProfile, season: &mut Season, points: u64) -> bool {
+    if season.id < profile.season {
+        return false;
+    }
+    if profile.season != season.id {
+        profile.season = season.id;
+        profile.season_points = 0;
+    }
+    profile.season_points = profile.season_points.saturating_add(points);
+    season.record(profile.wallet, profile.season_points);
+
+    emit!(SeasonPointsAwarded {
+        season: season.id,
+        wallet: profile.wallet,
+        points,
+        total: profile.season_points,
+    });
+    true
+}
@@ -0,0 +1,88 @@
+use crate::errors::ErrorCode;
+use crate::events::ResultsProposed;
+use crate::payout;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeResults<'info> {
+    // Anyone may propose once voting has ended
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+        constraint = !challenge.results_proposed @ ErrorCode::ResultsAlreadyProposed,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    #[account(
+        seeds = [b"dispute_config"],
+        bump,
+    )]
+    pub dispute_config: Box<Account<'info, DisputeConfig>>,
+}
+
+pub fn handle(ctx: Context<ProposeResults>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let config = &ctx.accounts.dispute_config;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= challenge.results_at(), ErrorCode::VotingPeriodActive);
+
+    // The same ranking settle_results pays out, unless a dispute changes it
+    let plan = payout::plan_finalization(challenge, 0)?;
+
+    // Later config changes don't move the goalposts for this challenge
+    challenge.results_proposed = true;
+    challenge.dispute_window = config.window;
+    challenge.dispute_bond = config.bond;
+    challenge.dispute_end = now
+        .checked_add(config.window)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(ResultsProposed {
+        challenge: challenge.key(),
+        winning_submission: plan.places[0].submission,
+        runner_up: plan.places.get(1).map(|place| place.submission),
+        dispute_end: challenge.dispute_end,
+        proposed_by: ctx.accounts.authority.key(),
+    });
+
+    msg!(
+        "Results proposed, submission {} leads; disputes open until {}",
+        plan.places[0].submission,
+        challenge.dispute_end
+    );
+
+    Ok(())
+}
+
+// Re-rank proposed results after a submission is disqualified and give the
+// new leaders a full window to be disputed. If nothing is left to rank, the
+// challenge has missed its quorum and can be cancelled instead
+pub fn repropose(challenge: &mut Challenge, address: Pubkey, proposed_by: Pubkey) -> Result<()> {
+    if !challenge.results_proposed {
+        return Ok(());
+    }
+    let Ok(plan) = payout::plan_finalization(challenge, 0) else {
+        return Ok(());
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    let reopened = now
+        .checked_add(challenge.dispute_window)
+        .ok_or(ErrorCode::MathOverflow)?;
+    challenge.dispute_end = challenge.dispute_end.max(reopened);
+
+    emit!(ResultsProposed {
+        challenge: address,
+        winning_submission: plan.places[0].submission,
+        runner_up: plan.places.get(1).map(|place| place.submission),
+        dispute_end: challenge.dispute_end,
+        proposed_by,
+    });
+
+    Ok(())
+}
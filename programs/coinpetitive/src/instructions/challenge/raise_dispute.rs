@@ -0,0 +1,127 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::DisputeRaised;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::treasury::transfer_from_signer;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RaiseDispute<'info> {
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+        constraint = challenge.results_proposed @ ErrorCode::ResultsNotProposed,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    // One open dispute per submission
+    #[account(
+        init,
+        payer = disputer,
+        space = Dispute::SPACE,
+        seeds = [b"dispute", challenge.key().as_ref(), submission_id.key().as_ref()],
+        bump,
+    )]
+    pub dispute: Box<Account<'info, Dispute>>,
+
+    /// CHECK: Just storing submission ID for reference
+    pub submission_id: AccountInfo<'info>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Disputer's token account
+    #[account(mut)]
+    pub disputer_token_account: AccountInfo<'info>,
+
+    /// CHECK: Main treasury PDA - verified in handler
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account - verified in handler
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<RaiseDispute>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let disputer = ctx.accounts.disputer.key();
+    let submission_id = ctx.accounts.submission_id.key();
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        challenge.accepts_disputes(now),
+        ErrorCode::DisputeWindowClosed
+    );
+    require!(
+        challenge.has_participant(&disputer),
+        ErrorCode::NotAParticipant
+    );
+    require!(
+        challenge.submitter_of(&submission_id).is_some(),
+        ErrorCode::SubmissionNotFound
+    );
+    require!(
+        !challenge.is_disqualified(&submission_id),
+        ErrorCode::SubmissionDisqualified
+    );
+
+    // The bond is held in the challenge treasury alongside the fees
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+    token_account_balance(
+        &ctx.accounts.treasury_token_account,
+        &challenge.treasury,
+        &challenge.reward_token_mint,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidTreasury))?;
+
+    let bond = challenge.dispute_bond;
+    if bond > 0 {
+        transfer_from_signer(
+            &ctx.accounts.token_program,
+            &ctx.accounts.disputer_token_account,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.disputer.to_account_info(),
+            bond,
+        )?;
+    }
+    challenge.locked_bonds = challenge
+        .locked_bonds
+        .checked_add(bond)
+        .ok_or(ErrorCode::MathOverflow)?;
+    challenge.open_disputes = challenge
+        .open_disputes
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.challenge = challenge.key();
+    dispute.submission = submission_id;
+    dispute.disputer = disputer;
+    dispute.bond = bond;
+
+    emit!(DisputeRaised {
+        challenge: challenge.key(),
+        submission: submission_id,
+        disputer,
+        bond,
+    });
+
+    msg!(
+        "Dispute against submission {} raised with a {} token bond",
+        submission_id,
+        bond
+    );
+
+    Ok(())
+}
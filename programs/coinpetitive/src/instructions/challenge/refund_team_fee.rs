@@ -0,0 +1,108 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::TeamFeeRefunded;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::treasury::transfer_signed;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RefundTeamFee<'info> {
+    // Anyone can crank a refund; it only ever goes to the member's own ATA
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !team.entered @ ErrorCode::AlreadyParticipated,
+    )]
+    pub team: Box<Account<'info, Team>>,
+
+    #[account(
+        mut,
+        address = team.challenge @ ErrorCode::InvalidTeam,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Main treasury PDA - verified in handler
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account - verified in handler
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Member's token account - owner verified in handler
+    #[account(mut)]
+    pub member_token_account: AccountInfo<'info>,
+}
+
+// A team that can no longer enter hands each member back the part of the
+// fee they paid in
+pub fn handle(ctx: Context<RefundTeamFee>, member: Pubkey) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let team = &mut ctx.accounts.team;
+
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+    token_account_balance(
+        &ctx.accounts.treasury_token_account,
+        &challenge.treasury,
+        &challenge.reward_token_mint,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidTreasury))?;
+    token_account_balance(
+        &ctx.accounts.member_token_account,
+        &member,
+        &challenge.reward_token_mint,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        challenge.team_fees_refundable(now),
+        ErrorCode::TeamCanStillEnter
+    );
+
+    let amount = team
+        .take_part(&member, challenge.participation_fee)
+        .ok_or(ErrorCode::NothingToRefund)?;
+    challenge.team_fees_held = challenge
+        .team_fees_held
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+
+    let challenge_pubkey = challenge.key();
+    if amount > 0 {
+        let (_, bump) =
+            Pubkey::find_program_address(&[b"treasury", challenge_pubkey.as_ref()], ctx.program_id);
+        transfer_signed(
+            &ctx.accounts.token_program,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.member_token_account,
+            &ctx.accounts.treasury,
+            &[b"treasury", challenge_pubkey.as_ref(), &[bump]],
+            amount,
+        )?;
+    }
+
+    emit!(TeamFeeRefunded {
+        team: team.key(),
+        challenge: challenge_pubkey,
+        member,
+        amount,
+    });
+
+    msg!(
+        "Refunded {} of team {}'s fee to {}",
+        amount,
+        team.key(),
+        member
+    );
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::DisputeResolved;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::profile::lower_reputation;
+use crate::instructions::challenge::propose_results::repropose;
+use crate::instructions::challenge::treasury::transfer_signed;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    // The platform admin or one of the challenge's judges
+    pub resolver: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    #[account(
+        mut,
+        close = disputer,
+        seeds = [b"dispute", challenge.key().as_ref(), dispute.submission.as_ref()],
+        bump,
+    )]
+    pub dispute: Box<Account<'info, Dispute>>,
+
+    /// CHECK: The wallet that raised the dispute, receives the account's rent
+    #[account(mut, address = dispute.disputer)]
+    pub disputer: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"challenge_tracker"],
+        bump,
+    )]
+    pub challenge_tracker: Box<Account<'info, ChallengeTracker>>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Main treasury PDA - verified in handler
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Disputer's token account - owner verified in handler when the bond is returned
+    #[account(mut)]
+    pub disputer_token_account: AccountInfo<'info>,
+
+    // Profile of whoever loses: the submitter if upheld, otherwise the
+    // disputer - wallet verified in handler
+    #[account(mut)]
+    pub loser_profile: Box<Account<'info, UserProfile>>,
+}
+
+pub fn handle(ctx: Context<ResolveDispute>, upheld: bool) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let resolver = ctx.accounts.resolver.key();
+    let submission = ctx.accounts.dispute.submission;
+    let disputer = ctx.accounts.dispute.disputer;
+    let bond = ctx.accounts.dispute.bond;
+
+    require!(
+        resolver == ctx.accounts.challenge_tracker.authority
+            || challenge.judge_index(&resolver).is_some(),
+        ErrorCode::Unauthorized
+    );
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+
+    challenge.release_bond(bond)?;
+
+    let challenge_pubkey = challenge.key();
+    if upheld {
+        // The disputer was right: their bond comes back and the submission
+        // is struck from the ranking
+        if bond > 0 {
+            token_account_balance(
+                &ctx.accounts.disputer_token_account,
+                &disputer,
+                &challenge.reward_token_mint,
+            )?;
+            let (_, bump) = Pubkey::find_program_address(
+                &[b"treasury", challenge_pubkey.as_ref()],
+                ctx.program_id,
+            );
+            transfer_signed(
+                &ctx.accounts.token_program,
+                &ctx.accounts.treasury_token_account,
+                &ctx.accounts.disputer_token_account,
+                &ctx.accounts.treasury,
+                &[b"treasury", challenge_pubkey.as_ref(), &[bump]],
+                bond,
+            )?;
+        }
+        challenge.disqualify(&submission)?;
+
+        let submitter = challenge
+            .submitter_of(&submission)
+            .ok_or(ErrorCode::SubmissionNotFound)?;
+        require_keys_eq!(
+            ctx.accounts.loser_profile.wallet,
+            submitter,
+            ErrorCode::InvalidProfile
+        );
+        lower_reputation(
+            &mut ctx.accounts.loser_profile,
+            REPUTATION_FOR_DISQUALIFICATION,
+        );
+    } else {
+        // The bond stays in the treasury and joins the fees
+        challenge.credit_treasury(bond)?;
+
+        require_keys_eq!(
+            ctx.accounts.loser_profile.wallet,
+            disputer,
+            ErrorCode::InvalidProfile
+        );
+        lower_reputation(&mut ctx.accounts.loser_profile, REPUTATION_FOR_LOST_DISPUTE);
+    }
+
+    emit!(DisputeResolved {
+        challenge: challenge_pubkey,
+        submission,
+        disputer,
+        upheld,
+        resolved_by: resolver,
+    });
+
+    msg!(
+        "Dispute against submission {} {}",
+        submission,
+        if upheld { "upheld" } else { "rejected" }
+    );
+
+    if upheld {
+        repropose(challenge, challenge_pubkey, resolver)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::errors::ErrorCode;
+use crate::events::VoteCast;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RevealVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    // Cancelled challenges accept reveals so the voting fee can be refunded
+    #[account(
+        mut,
+        constraint = challenge.is_active || challenge.is_cancelled @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    // Revealing returns the commitment's rent to the voter
+    #[account(
+        mut,
+        close = voter,
+        seeds = [b"vote_commitment", challenge.key().as_ref(), voter.key().as_ref()],
+        bump,
+    )]
+    pub vote_commitment: Box<Account<'info, VoteCommitment>>,
+
+    /// CHECK: Just storing submission ID for reference
+    pub submission_id: AccountInfo<'info>,
+}
+
+pub fn handle(ctx: Context<RevealVote>, salt: [u8; 32]) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let voter = ctx.accounts.voter.key();
+    let submission_id = ctx.accounts.submission_id.key();
+
+    require!(
+        VoteCommitment::hash(&challenge.key(), &voter, &submission_id, &salt)
+            == ctx.accounts.vote_commitment.commitment,
+        ErrorCode::InvalidReveal
+    );
+
+    // The vote no longer counts, it only makes the voter refundable
+    if challenge.is_cancelled {
+        challenge.voters.push((voter, submission_id));
+        challenge.vote_weights.push(1);
+        msg!("Vote revealed on a cancelled challenge, fee refundable");
+        return Ok(());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= challenge.voting_end, ErrorCode::VotingPeriodActive);
+    require!(now < challenge.reveal_end, ErrorCode::RevealClosed);
+
+    require!(
+        challenge.submitter_of(&submission_id).is_some(),
+        ErrorCode::SubmissionNotFound
+    );
+    require!(
+        !challenge.is_disqualified(&submission_id),
+        ErrorCode::SubmissionDisqualified
+    );
+
+    // Only revealed votes count towards the result and voter rewards
+    challenge.total_votes = challenge
+        .total_votes
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    challenge.add_vote(voter, submission_id)?;
+
+    emit!(VoteCast {
+        challenge: challenge.key(),
+        voter,
+        submission: submission_id,
+        fee: challenge.voting_fee,
+        weight: 1,
+        stake: 0,
+    });
+
+    msg!("Vote revealed for submission {}", submission_id);
+
+    Ok(())
+}
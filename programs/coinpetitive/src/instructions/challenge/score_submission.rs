@@ -0,0 +1,62 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::SubmissionScored;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ScoreSubmission<'info> {
+    pub judge: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+        constraint = challenge.judging_mode != JudgingMode::PublicVote @ ErrorCode::JudgingDisabled,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    /// CHECK: Just storing submission ID for reference
+    pub submission_id: AccountInfo<'info>,
+}
+
+pub fn handle(ctx: Context<ScoreSubmission>, score: u8) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let judge = ctx.accounts.judge.key();
+    let submission_id = ctx.accounts.submission_id.key();
+
+    let judge_index = challenge.judge_index(&judge).ok_or(ErrorCode::NotAJudge)?;
+    require!(score <= MAX_SCORE, ErrorCode::InvalidScore);
+
+    // Judges can score and rescore until the results are in, like voters
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < challenge.results_at(), ErrorCode::VotingClosed);
+
+    let submission_index = challenge
+        .submitters
+        .iter()
+        .position(|(s, _)| *s == submission_id)
+        .ok_or(ErrorCode::SubmissionNotFound)?;
+    require!(
+        !challenge.is_disqualified(&submission_id),
+        ErrorCode::SubmissionDisqualified
+    );
+
+    challenge.set_score(judge_index as u8, submission_index as u8, score);
+
+    emit!(SubmissionScored {
+        challenge: challenge.key(),
+        judge,
+        submission: submission_id,
+        score,
+    });
+
+    msg!(
+        "Judge {} scored submission {}: {}",
+        judge,
+        submission_id,
+        score
+    );
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::errors::ErrorCode;
+use crate::events::ChallengeFrozenChanged;
+use crate::instructions::challenge::audit_challenge::build_report;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetChallengeFrozen<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    // The tracker authority is the platform admin
+    #[account(
+        seeds = [b"challenge_tracker"],
+        bump,
+        constraint = challenge_tracker.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub challenge_tracker: Box<Account<'info, ChallengeTracker>>,
+
+    /// CHECK: Treasury's token account - verified in handler
+    pub treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Voting Treasury's token account - verified in handler
+    pub voting_treasury_token_account: AccountInfo<'info>,
+}
+
+pub fn handle(ctx: Context<SetChallengeFrozen>, frozen: bool) -> Result<()> {
+    // A challenge can only be frozen while its balances fall short of its ledger
+    if frozen {
+        let report = build_report(
+            &ctx.accounts.challenge,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.voting_treasury_token_account,
+        )?;
+        require!(!report.is_clean(), ErrorCode::LedgerMatchesBalances);
+    }
+
+    let challenge = &mut ctx.accounts.challenge;
+    challenge.is_frozen = frozen;
+
+    emit!(ChallengeFrozenChanged {
+        challenge: challenge.key(),
+        authority: ctx.accounts.authority.key(),
+        is_frozen: frozen,
+    });
+
+    msg!("Challenge {} frozen: {}", challenge.key(), frozen);
+
+    Ok(())
+}
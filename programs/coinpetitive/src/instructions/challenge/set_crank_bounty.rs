@@ -0,0 +1,53 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::CrankBountyChanged;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetCrankBounty<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // The tracker authority is the platform admin
+    #[account(
+        seeds = [b"challenge_tracker"],
+        bump,
+        constraint = challenge_tracker.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub challenge_tracker: Box<Account<'info, ChallengeTracker>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = CrankConfig::SPACE,
+        seeds = [b"crank_config"],
+        bump,
+    )]
+    pub crank_config: Box<Account<'info, CrankConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<SetCrankBounty>, finalize_bounty_bps: u16) -> Result<()> {
+    require!(
+        finalize_bounty_bps <= BASIS_POINTS,
+        ErrorCode::InvalidCrankBounty
+    );
+
+    let config = &mut ctx.accounts.crank_config;
+    config.authority = ctx.accounts.authority.key();
+    config.finalize_bounty_bps = finalize_bounty_bps;
+
+    emit!(CrankBountyChanged {
+        authority: config.authority,
+        finalize_bounty_bps,
+    });
+
+    msg!(
+        "Finalization bounty set to {} bps of the platform fee",
+        finalize_bounty_bps
+    );
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::errors::ErrorCode;
+use crate::events::DisputeConfigChanged;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetDisputeConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // The tracker authority is the platform admin
+    #[account(
+        seeds = [b"challenge_tracker"],
+        bump,
+        constraint = challenge_tracker.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub challenge_tracker: Box<Account<'info, ChallengeTracker>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = DisputeConfig::SPACE,
+        seeds = [b"dispute_config"],
+        bump,
+    )]
+    pub dispute_config: Box<Account<'info, DisputeConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<SetDisputeConfig>, bond: u64, window: i64) -> Result<()> {
+    require!(window >= 0, ErrorCode::InvalidDisputeConfig);
+
+    let config = &mut ctx.accounts.dispute_config;
+    config.authority = ctx.accounts.authority.key();
+    config.bond = bond;
+    config.window = window;
+
+    emit!(DisputeConfigChanged {
+        authority: config.authority,
+        bond,
+        window,
+    });
+
+    msg!(
+        "Disputes need a {} token bond within {} seconds of proposed results",
+        bond,
+        window
+    );
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::errors::ErrorCode;
+use crate::events::ReputationConfigChanged;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetReputationConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // The tracker authority is the platform admin
+    #[account(
+        seeds = [b"challenge_tracker"],
+        bump,
+        constraint = challenge_tracker.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub challenge_tracker: Box<Account<'info, ChallengeTracker>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ReputationConfig::SPACE,
+        seeds = [b"reputation_config"],
+        bump,
+    )]
+    pub reputation_config: Box<Account<'info, ReputationConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(
+    ctx: Context<SetReputationConfig>,
+    large_reward: u64,
+    min_creator_reputation: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.reputation_config;
+    config.authority = ctx.accounts.authority.key();
+    config.large_reward = large_reward;
+    config.min_creator_reputation = min_creator_reputation;
+
+    emit!(ReputationConfigChanged {
+        authority: config.authority,
+        large_reward,
+        min_creator_reputation,
+    });
+
+    msg!(
+        "Challenges rewarding {} or more need a creator reputation of {}",
+        large_reward,
+        min_creator_reputation
+    );
+
+    Ok(())
+}
@@ -0,0 +1,628 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::{
+    ChallengeFinalized, CrankBountyPaid, PayoutIssued, PayoutKind, SeasonPoolFunded,
+};
+use crate::instructions::challenge::audit_challenge::{
+    token_account_balance, token_account_balance_in,
+};
+use crate::instructions::challenge::profile::{award_season_points, raise_reputation};
+use crate::instructions::challenge::treasury::pay_prize;
+use crate::payout;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program;
+
+#[derive(Accounts)]
+pub struct SettleResults<'info> {
+    // Anyone may settle once the dispute window has passed, and is paid the crank bounty
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    // Token accounts
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Winner's account for the prize - verified in handler
+    #[account(mut)]
+    pub winner_token_account: AccountInfo<'info>,
+
+    /// CHECK: Runner-up's account for the prize (for 2nd place)
+    #[account(mut)]
+    pub runnerup_token_account: AccountInfo<'info>,
+
+    /// CHECK: Main treasury PDA - verified in handler
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Platform treasury's account for the prize - owner verified in handler
+    #[account(mut)]
+    pub platform_treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Where the prize is held: the treasury token account for CPT, the
+    /// treasury's account for a token prize, or the treasury PDA for SOL
+    #[account(mut)]
+    pub prize_escrow: AccountInfo<'info>,
+
+    /// CHECK: Program moving the prize - verified in handler
+    pub prize_token_program: AccountInfo<'info>,
+
+    /// CHECK: Mint of a CPT or Token prize, unused for SOL - verified in handler
+    pub prize_mint: AccountInfo<'info>,
+
+    /// CHECK: Token account for the leftover fees - the creator's, or the
+    /// tournament's escrow when a round rolls its fees into the final prize
+    #[account(mut)]
+    pub creator_token_account: AccountInfo<'info>,
+
+    /// CHECK: The actual creator of the challenge
+    pub creator: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"challenge_tracker"],
+        bump,
+    )]
+    pub challenge_tracker: Box<Account<'info, ChallengeTracker>>,
+
+    #[account(
+        seeds = [b"crank_config"],
+        bump,
+    )]
+    pub crank_config: Box<Account<'info, CrankConfig>>,
+
+    /// CHECK: Caller's account for the bounty, in the prize - owner verified in handler
+    #[account(mut)]
+    pub cranker_token_account: AccountInfo<'info>,
+
+    // Winner's profile, left out for a wallet without one - wallet verified
+    // in handler
+    #[account(mut)]
+    pub winner_profile: Option<Box<Account<'info, UserProfile>>>,
+
+    // Runner-up's profile when a different wallet than the winner is paid
+    // second, left out for a wallet without one - wallet verified in handler
+    #[account(mut)]
+    pub runnerup_profile: Option<Box<Account<'info, UserProfile>>>,
+
+    /// CHECK: Season config PDA, only read once the admin has created it
+    #[account(seeds = [b"season_config"], bump)]
+    pub season_config: UncheckedAccount<'info>,
+
+    // The season running at settlement, needed while one is - verified in handler
+    #[account(mut)]
+    pub season: Option<Box<Account<'info, Season>>>,
+
+    /// CHECK: Season's CPT account for its prize pool - verified in handler
+    #[account(mut)]
+    pub season_token_account: Option<UncheckedAccount<'info>>,
+
+    // System program
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<SettleResults>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+
+    // Verify creator key matches challenge creator
+    require!(
+        ctx.accounts.creator.key() == challenge.creator,
+        ErrorCode::InvalidCreator
+    );
+
+    // Verify treasury matches the one stored in the challenge
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+
+    // Anyone may settle, but only once proposed results have stood through the
+    // dispute window with every dispute resolved
+    let now = Clock::get()?.unix_timestamp;
+    require!(challenge.results_proposed, ErrorCode::ResultsNotProposed);
+    require!(now >= challenge.dispute_end, ErrorCode::DisputeWindowOpen);
+    require!(challenge.open_disputes == 0, ErrorCode::DisputesOpen);
+
+    // Rank submissions and work out every payout up front; preview_finalization
+    // runs exactly the same plan, minus any submission a dispute disqualified
+    let plan = payout::plan_finalization(challenge, ctx.accounts.crank_config.finalize_bounty_bps)?;
+
+    // Get the winning submission and runner-up
+    let winner = &plan.places[0];
+    let (winning_submission, winning_votes) = (winner.submission, winner.votes);
+    let runner_up = plan.places.get(1);
+
+    msg!(
+        "Found winner submission: {} with {} votes",
+        winning_submission,
+        winning_votes
+    );
+
+    // Find the winner's pubkey by checking which participant submitted this
+    let winner_pubkey = winning_submission;
+
+    // The caller picks every account, so each payee is checked against the
+    // wallet that is actually owed, in the prize's mint
+    require_prize_escrow(
+        challenge,
+        &ctx.accounts.prize_escrow,
+        &ctx.accounts.prize_mint,
+        &ctx.accounts.prize_token_program,
+    )?;
+    let winner_wallet = challenge
+        .submitter_of(&winning_submission)
+        .ok_or(ErrorCode::InvalidWinner)?;
+    require_prize_recipient(
+        challenge,
+        &ctx.accounts.winner_token_account,
+        &winner_wallet,
+        ErrorCode::InvalidWinner,
+    )?;
+    if let Some(winner_profile) = &ctx.accounts.winner_profile {
+        require_keys_eq!(
+            winner_profile.wallet,
+            winner_wallet,
+            ErrorCode::InvalidWinner
+        );
+    }
+    // The same profile twice would have its second copy overwrite the first
+    if let (Some(winner_profile), Some(runnerup_profile)) =
+        (&ctx.accounts.winner_profile, &ctx.accounts.runnerup_profile)
+    {
+        require_keys_neq!(
+            runnerup_profile.key(),
+            winner_profile.key(),
+            ErrorCode::InvalidRunnerUp
+        );
+    }
+    if let Some(runner_up) = runner_up.filter(|p| p.amount > 0) {
+        let runner_up_wallet = challenge
+            .submitter_of(&runner_up.submission)
+            .ok_or(ErrorCode::InvalidRunnerUp)?;
+        require_prize_recipient(
+            challenge,
+            &ctx.accounts.runnerup_token_account,
+            &runner_up_wallet,
+            ErrorCode::InvalidRunnerUp,
+        )?;
+        if let Some(runnerup_profile) = ctx
+            .accounts
+            .runnerup_profile
+            .as_ref()
+            .filter(|_| runner_up_wallet != winner_wallet)
+        {
+            require_keys_eq!(
+                runnerup_profile.wallet,
+                runner_up_wallet,
+                ErrorCode::InvalidRunnerUp
+            );
+        }
+    }
+    require_prize_recipient(
+        challenge,
+        &ctx.accounts.platform_treasury_token_account,
+        &ctx.accounts.challenge_tracker.authority,
+        ErrorCode::InvalidPlatformTreasury,
+    )?;
+    // Leftover fees are always CPT
+    token_account_balance(
+        &ctx.accounts.creator_token_account,
+        &challenge.remainder_owner(),
+        &challenge.reward_token_mint,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidCreator))?;
+    if plan.crank_bounty > 0 {
+        require_prize_recipient(
+            challenge,
+            &ctx.accounts.cranker_token_account,
+            &ctx.accounts.authority.key(),
+            ErrorCode::InvalidTokenAccount,
+        )?;
+    }
+
+    // Mark challenge as inactive
+    challenge.is_active = false;
+
+    // Set winner info
+    challenge.winner = Some(winner_pubkey);
+    challenge.winning_votes = winning_votes;
+    challenge.winning_weight = challenge.weight_for(&winner_pubkey);
+    challenge.judge_fee_pool = plan.judge_fee;
+
+    // Platform fee (2.1% of reward pool), then 75% / 25% of the rest to winner
+    // and runner-up, and whatever fees remain to the creator
+    let total_reward = plan.total_reward;
+    let crank_bounty = plan.crank_bounty;
+    let mut platform_fee = plan
+        .platform_fee
+        .checked_sub(crank_bounty)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let winner_reward = winner.amount;
+    let treasury_balance = plan.creator_remainder;
+
+    msg!(
+        "Total reward: {}, Platform fee (2.1%): {}",
+        total_reward,
+        platform_fee
+    );
+
+    // A season running now scores the winner and, for CPT prizes, takes its
+    // share of the platform fee into the prize pool
+    let season_config = &ctx.accounts.season_config;
+    let running_season = if season_config.owner == ctx.program_id {
+        SeasonConfig::try_deserialize(&mut &season_config.try_borrow_data()?[..])?.running_at(now)
+    } else {
+        None
+    };
+    let mut season_share = 0;
+    if let Some(season_id) = running_season {
+        let season = ctx
+            .accounts
+            .season
+            .as_ref()
+            .ok_or(ErrorCode::InvalidSeason)?;
+        require!(season.id == season_id, ErrorCode::InvalidSeason);
+        challenge.season = season_id;
+
+        if challenge.prize_kind == PrizeKind::Cpt {
+            season_share =
+                coinpetitive_math::season_pool_share(platform_fee, season.pool_share_bps);
+        }
+        if season_share > 0 {
+            let season_token_account = ctx
+                .accounts
+                .season_token_account
+                .as_ref()
+                .ok_or(ErrorCode::InvalidSeason)?;
+            require_prize_recipient(
+                challenge,
+                season_token_account,
+                &season.key(),
+                ErrorCode::InvalidSeason,
+            )?;
+            platform_fee -= season_share;
+        }
+    }
+
+    // Get bump seeds for treasury PDA to sign transaction
+    let challenge_pubkey = challenge.key();
+    let (_, bump) =
+        Pubkey::find_program_address(&[b"treasury", challenge_pubkey.as_ref()], ctx.program_id);
+
+    let treasury_seeds = &[b"treasury", challenge_pubkey.as_ref(), &[bump]];
+
+    // Transfer platform fee first, in the prize's own mint
+    let prize_kind = challenge.prize_kind;
+    if platform_fee > 0 {
+        msg!(
+            "Transferring {} to platform treasury (2.1% fee)",
+            platform_fee
+        );
+
+        pay_prize(
+            prize_kind,
+            &ctx.accounts.prize_token_program,
+            &ctx.accounts.prize_escrow,
+            &ctx.accounts.prize_mint,
+            &ctx.accounts.platform_treasury_token_account,
+            &ctx.accounts.treasury,
+            treasury_seeds,
+            platform_fee,
+        )?;
+        challenge.debit_prize(platform_fee)?;
+
+        emit!(PayoutIssued {
+            challenge: challenge_pubkey,
+            kind: PayoutKind::Platform,
+            recipient: ctx.accounts.challenge_tracker.authority,
+            amount: platform_fee,
+        });
+
+        msg!("Platform fee transferred successfully");
+    }
+
+    // The rest of the platform fee rewards whoever finalized
+    if crank_bounty > 0 {
+        pay_prize(
+            prize_kind,
+            &ctx.accounts.prize_token_program,
+            &ctx.accounts.prize_escrow,
+            &ctx.accounts.prize_mint,
+            &ctx.accounts.cranker_token_account,
+            &ctx.accounts.treasury,
+            treasury_seeds,
+            crank_bounty,
+        )?;
+        challenge.debit_prize(crank_bounty)?;
+
+        emit!(CrankBountyPaid {
+            challenge: challenge_pubkey,
+            cranker: ctx.accounts.authority.key(),
+            amount: crank_bounty,
+        });
+
+        msg!("Paid {} crank bounty", crank_bounty);
+    }
+
+    // The season's share of the platform fee joins its prize pool
+    if let (Some(season), Some(season_token_account)) = (
+        ctx.accounts.season.as_mut().filter(|_| season_share > 0),
+        ctx.accounts.season_token_account.as_ref(),
+    ) {
+        pay_prize(
+            prize_kind,
+            &ctx.accounts.prize_token_program,
+            &ctx.accounts.prize_escrow,
+            &ctx.accounts.prize_mint,
+            season_token_account,
+            &ctx.accounts.treasury,
+            treasury_seeds,
+            season_share,
+        )?;
+        challenge.debit_prize(season_share)?;
+        season.prize_pool = season
+            .prize_pool
+            .checked_add(season_share)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(SeasonPoolFunded {
+            season: season.id,
+            challenge: challenge_pubkey,
+            amount: season_share,
+            prize_pool: season.prize_pool,
+        });
+
+        msg!(
+            "Paid {} into season {}'s prize pool",
+            season_share,
+            season.id
+        );
+    }
+
+    // Pay the winner out of the prize escrow
+    if winner_reward > 0 {
+        msg!(
+            "Transferring {} to winner (75% of reward after fees)",
+            winner_reward
+        );
+
+        pay_prize(
+            prize_kind,
+            &ctx.accounts.prize_token_program,
+            &ctx.accounts.prize_escrow,
+            &ctx.accounts.prize_mint,
+            &ctx.accounts.winner_token_account,
+            &ctx.accounts.treasury,
+            treasury_seeds,
+            winner_reward,
+        )?;
+        challenge.debit_prize(winner_reward)?;
+
+        emit!(PayoutIssued {
+            challenge: challenge_pubkey,
+            kind: PayoutKind::Winner,
+            recipient: winner_wallet,
+            amount: winner_reward,
+        });
+
+        msg!("Transferred {} to winner: {}", winner_reward, winner_pubkey);
+    }
+
+    // Transfer to runner-up if there is one and there's a reward
+    if let Some(runner_up) = runner_up.filter(|p| p.amount > 0) {
+        let runnerup_reward = runner_up.amount;
+        let runnerup_pubkey = runner_up.submission;
+
+        msg!(
+            "Transferring {} to runner-up (25% of reward after fees)",
+            runnerup_reward
+        );
+
+        pay_prize(
+            prize_kind,
+            &ctx.accounts.prize_token_program,
+            &ctx.accounts.prize_escrow,
+            &ctx.accounts.prize_mint,
+            &ctx.accounts.runnerup_token_account,
+            &ctx.accounts.treasury,
+            treasury_seeds,
+            runnerup_reward,
+        )?;
+        challenge.debit_prize(runnerup_reward)?;
+
+        emit!(PayoutIssued {
+            challenge: challenge_pubkey,
+            kind: PayoutKind::RunnerUp,
+            recipient: challenge
+                .submitter_of(&runnerup_pubkey)
+                .ok_or(ErrorCode::InvalidRunnerUp)?,
+            amount: runnerup_reward,
+        });
+
+        msg!(
+            "Transferred {} to runner-up: {}",
+            runnerup_reward,
+            runnerup_pubkey
+        );
+    }
+
+    // After winners are paid, transfer any remaining balance to the creator
+    if treasury_balance > 0 {
+        msg!(
+            "Transferring remaining {} tokens from treasury to creator",
+            treasury_balance
+        );
+
+        let creator_transfer_ix = solana_program::instruction::Instruction {
+            program_id: ctx.accounts.token_program.key(),
+            accounts: vec![
+                solana_program::instruction::AccountMeta::new(
+                    ctx.accounts.treasury_token_account.key(),
+                    false,
+                ),
+                solana_program::instruction::AccountMeta::new(
+                    ctx.accounts.creator_token_account.key(),
+                    false,
+                ),
+                solana_program::instruction::AccountMeta::new_readonly(
+                    ctx.accounts.treasury.key(),
+                    true,
+                ),
+            ],
+            data: [3]
+                .into_iter() // Token instruction 3 = Transfer
+                .chain(treasury_balance.to_le_bytes())
+                .collect(),
+        };
+
+        // Execute the transfer
+        solana_program::program::invoke_signed(
+            &creator_transfer_ix,
+            &[
+                ctx.accounts.treasury_token_account.to_account_info(),
+                ctx.accounts.creator_token_account.to_account_info(),
+                ctx.accounts.treasury.to_account_info(),
+            ],
+            &[treasury_seeds],
+        )?;
+
+        challenge.debit_treasury(treasury_balance)?;
+
+        emit!(PayoutIssued {
+            challenge: challenge_pubkey,
+            kind: PayoutKind::Creator,
+            recipient: challenge.remainder_owner(),
+            amount: treasury_balance,
+        });
+
+        msg!(
+            "Transferred remaining {} tokens to creator",
+            treasury_balance
+        );
+    }
+
+    // Lifetime statistics, where only CPT prizes count towards CPT earned.
+    // A wallet without a profile has none to credit
+    let cpt_prize = prize_kind == PrizeKind::Cpt;
+    if let Some(winner_profile) = ctx.accounts.winner_profile.as_mut() {
+        winner_profile.challenges_won = winner_profile
+            .challenges_won
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        raise_reputation(winner_profile, REPUTATION_FOR_WIN);
+        if let Some(season) = ctx
+            .accounts
+            .season
+            .as_mut()
+            .filter(|_| running_season.is_some())
+        {
+            award_season_points(winner_profile, season, SEASON_POINTS_FOR_WIN);
+        }
+        if cpt_prize {
+            winner_profile.cpt_earned = winner_profile
+                .cpt_earned
+                .checked_add(winner_reward)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+    }
+    if let Some(runner_up) = runner_up.filter(|p| cpt_prize && p.amount > 0) {
+        // A wallet with two placed submissions is credited both places
+        let profile = if challenge.submitter_of(&runner_up.submission) == Some(winner_wallet) {
+            ctx.accounts.winner_profile.as_mut()
+        } else {
+            ctx.accounts.runnerup_profile.as_mut()
+        };
+        if let Some(profile) = profile {
+            profile.cpt_earned = profile
+                .cpt_earned
+                .checked_add(runner_up.amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+    }
+
+    // Freeze the voting pool so every winning voter gets the same share
+    challenge.voting_reward_pool = challenge.voting_treasury;
+
+    // Update challenge tracker with this finalized challenge
+    let tracker = &mut ctx.accounts.challenge_tracker;
+    tracker.total_challenges = tracker
+        .total_challenges
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    emit!(ChallengeFinalized {
+        challenge: challenge_pubkey,
+        winning_submission,
+        winning_votes,
+        finalized_by: ctx.accounts.authority.key(),
+    });
+
+    msg!(
+        "Challenge completed and tracked! Total challenges finalized: {}",
+        tracker.total_challenges
+    );
+
+    msg!("Challenge finalized successfully with 2.1% platform fee collected!");
+    Ok(())
+}
+
+// Prizes are paid into token accounts of the prize's mint, or straight to the
+// wallet for SOL prizes
+fn require_prize_recipient(
+    challenge: &Challenge,
+    account: &AccountInfo,
+    wallet: &Pubkey,
+    error: ErrorCode,
+) -> Result<()> {
+    match challenge.prize_kind {
+        PrizeKind::Sol => require_keys_eq!(account.key(), *wallet, error),
+        PrizeKind::Cpt | PrizeKind::Token => {
+            token_account_balance_in(
+                account,
+                &challenge.prize_token_program,
+                wallet,
+                &challenge.prize_mint,
+            )
+            .map_err(|_| error!(error))?;
+        }
+    }
+    Ok(())
+}
+
+pub fn require_prize_escrow(
+    challenge: &Challenge,
+    escrow: &AccountInfo,
+    mint: &AccountInfo,
+    token_program: &AccountInfo,
+) -> Result<()> {
+    require_keys_eq!(
+        token_program.key(),
+        challenge.prize_token_program,
+        ErrorCode::InvalidPrizeAccount
+    );
+    if challenge.prize_kind != PrizeKind::Sol {
+        require_keys_eq!(
+            mint.key(),
+            challenge.prize_mint,
+            ErrorCode::InvalidPrizeAccount
+        );
+    }
+    require_prize_recipient(
+        challenge,
+        escrow,
+        &challenge.treasury,
+        ErrorCode::InvalidPrizeAccount,
+    )
+}
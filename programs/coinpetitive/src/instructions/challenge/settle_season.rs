@@ -0,0 +1,128 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::{SeasonPrizePaid, SeasonSettled};
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::treasury::transfer_signed;
+use crate::payout;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+// The placed wallets' CPT token accounts follow as remaining accounts, in
+// leaderboard order
+#[derive(Accounts)]
+pub struct SettleSeason<'info> {
+    // Anyone may settle a season once it has ended
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !season.settled @ ErrorCode::SeasonAlreadySettled,
+    )]
+    pub season: Box<Account<'info, Season>>,
+
+    /// CHECK: Season's CPT token account - verified in handler
+    #[account(mut)]
+    pub season_token_account: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"challenge_tracker"],
+        bump,
+    )]
+    pub challenge_tracker: Box<Account<'info, ChallengeTracker>>,
+
+    /// CHECK: Platform treasury's CPT account, paid the pool when nobody
+    /// scored - owner verified in handler
+    #[account(mut)]
+    pub platform_treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, 'info, 'info, SettleSeason<'info>>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    require!(
+        Clock::get()?.unix_timestamp >= season.end_time,
+        ErrorCode::SeasonNotOver
+    );
+
+    let cpt_mint = CPT_TOKEN_MINT.parse::<Pubkey>().unwrap();
+    let season_pubkey = season.key();
+    token_account_balance(
+        &ctx.accounts.season_token_account,
+        &season_pubkey,
+        &cpt_mint,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidSeason))?;
+
+    let id_bytes = season.id.to_le_bytes();
+    let (_, bump) = Pubkey::find_program_address(&[b"season", id_bytes.as_ref()], ctx.program_id);
+    let season_seeds: &[&[u8]] = &[b"season", id_bytes.as_ref(), &[bump]];
+    let season_info = season.to_account_info();
+
+    let prizes = payout::plan_season_prizes(season);
+    require!(
+        ctx.remaining_accounts.len() == prizes.len(),
+        ErrorCode::InvalidTokenAccount
+    );
+
+    for (rank, (prize, account)) in prizes.iter().zip(ctx.remaining_accounts).enumerate() {
+        token_account_balance(account, &prize.wallet, &cpt_mint)?;
+        if prize.amount > 0 {
+            transfer_signed(
+                &ctx.accounts.token_program,
+                &ctx.accounts.season_token_account,
+                account,
+                &season_info,
+                season_seeds,
+                prize.amount,
+            )?;
+        }
+
+        emit!(SeasonPrizePaid {
+            season: season.id,
+            wallet: prize.wallet,
+            rank: rank as u8 + 1,
+            points: prize.points,
+            amount: prize.amount,
+        });
+    }
+
+    // Nobody scored, so nobody can be paid; the pool goes to the platform
+    if prizes.is_empty() && season.prize_pool > 0 {
+        token_account_balance(
+            &ctx.accounts.platform_treasury_token_account,
+            &ctx.accounts.challenge_tracker.authority,
+            &cpt_mint,
+        )
+        .map_err(|_| error!(ErrorCode::InvalidPlatformTreasury))?;
+        transfer_signed(
+            &ctx.accounts.token_program,
+            &ctx.accounts.season_token_account,
+            &ctx.accounts.platform_treasury_token_account,
+            &season_info,
+            season_seeds,
+            season.prize_pool,
+        )?;
+    }
+
+    let prize_pool = season.prize_pool;
+    season.prize_pool = 0;
+    season.settled = true;
+
+    emit!(SeasonSettled {
+        season: season.id,
+        prize_pool,
+        places_paid: prizes.len() as u8,
+    });
+
+    msg!(
+        "Season {} settled: {} paid to {} places",
+        season.id,
+        prize_pool,
+        prizes.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::TeamSharePaid;
+use crate::instructions::challenge::audit_challenge::token_account_balance_in;
+use crate::instructions::challenge::treasury::{move_lamports, transfer_checked_signed};
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+// The members' accounts in the split currency follow as remaining accounts,
+// in member order: their token accounts, or the wallets themselves for SOL
+#[derive(Accounts)]
+pub struct SplitTeamReward<'info> {
+    // Anyone may split a team's balance once its challenge is over
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub team: Box<Account<'info, Team>>,
+
+    #[account(
+        address = team.challenge @ ErrorCode::InvalidTeam,
+        constraint = !challenge.is_active @ ErrorCode::ChallengeStillActive,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    /// CHECK: Team's account being split: its CPT account for refunds and CPT
+    /// prizes, its account in the prize's mint, or the team PDA for SOL
+    #[account(mut)]
+    pub team_token_account: AccountInfo<'info>,
+
+    /// CHECK: Mint of the split balance, unused for SOL - verified in handler
+    pub mint: AccountInfo<'info>,
+
+    /// CHECK: Program holding the split balance - verified in handler
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, 'info, 'info, SplitTeamReward<'info>>) -> Result<()> {
+    let team = &ctx.accounts.team;
+    let challenge = &ctx.accounts.challenge;
+    let team_info = team.to_account_info();
+    let team_key = team.key();
+
+    // Only CPT, which fees and refunds are paid in, or the challenge's prize
+    let cpt_mint = CPT_TOKEN_MINT.parse::<Pubkey>().unwrap();
+    let token_program = ctx.accounts.token_program.key();
+    let mint = ctx.accounts.mint.key();
+    let is_sol = token_program == system_program::ID;
+    require!(
+        (token_program == spl_token_2022::ID && mint == cpt_mint)
+            || (token_program == challenge.prize_token_program
+                && (is_sol || mint == challenge.prize_mint)),
+        ErrorCode::InvalidPrizeAccount
+    );
+
+    let amount = if is_sol {
+        require_keys_eq!(
+            ctx.accounts.team_token_account.key(),
+            team_key,
+            ErrorCode::InvalidTokenAccount
+        );
+        // Everything above the team account's rent
+        let rent = Rent::get()?.minimum_balance(team_info.data_len());
+        team_info.lamports().saturating_sub(rent)
+    } else {
+        token_account_balance_in(
+            &ctx.accounts.team_token_account,
+            &token_program,
+            &team_key,
+            &mint,
+        )?
+    };
+    require!(amount > 0, ErrorCode::NothingToSplit);
+
+    require!(
+        ctx.remaining_accounts.len() == team.members.len(),
+        ErrorCode::InvalidTokenAccount
+    );
+    let parts = team.split(amount);
+
+    let (_, bump) = Pubkey::find_program_address(
+        &[b"team", team.challenge.as_ref(), team.captain.as_ref()],
+        ctx.program_id,
+    );
+    let team_seeds: &[&[u8]] = &[
+        b"team",
+        team.challenge.as_ref(),
+        team.captain.as_ref(),
+        &[bump],
+    ];
+
+    for ((member, part), account) in team.members.iter().zip(parts).zip(ctx.remaining_accounts) {
+        if is_sol {
+            require_keys_eq!(account.key(), *member, ErrorCode::InvalidTokenAccount);
+        } else {
+            token_account_balance_in(account, &token_program, member, &mint)?;
+        }
+        if part > 0 {
+            if is_sol {
+                move_lamports(&team_info, account, part)?;
+            } else {
+                transfer_checked_signed(
+                    &ctx.accounts.token_program,
+                    &ctx.accounts.team_token_account,
+                    &ctx.accounts.mint,
+                    account,
+                    &team_info,
+                    team_seeds,
+                    part,
+                )?;
+            }
+        }
+
+        emit!(TeamSharePaid {
+            team: team_key,
+            challenge: challenge.key(),
+            member: *member,
+            mint: if is_sol { Pubkey::default() } else { mint },
+            amount: part,
+        });
+    }
+
+    msg!(
+        "Split {} between team {}'s {} members",
+        amount,
+        team_key,
+        team.members.len()
+    );
+
+    Ok(())
+}
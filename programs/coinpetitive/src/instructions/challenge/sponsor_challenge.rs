@@ -0,0 +1,141 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::ChallengeSponsored;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::treasury::transfer_from_signer;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SponsorChallenge<'info> {
+    // Any wallet can sponsor, not just the creator
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = challenge.is_active @ ErrorCode::ChallengeNotActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    // One record per sponsor, topped up by later deposits
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        space = SponsorRecord::SPACE,
+        seeds = [b"sponsor", challenge.key().as_ref(), sponsor.key().as_ref()],
+        bump,
+    )]
+    pub sponsor_record: Box<Account<'info, SponsorRecord>>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Sponsor's token account
+    #[account(mut)]
+    pub sponsor_token_account: AccountInfo<'info>,
+
+    /// CHECK: Main treasury PDA - verified in handler
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account - verified in handler
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(
+    ctx: Context<SponsorChallenge>,
+    amount: u64,
+    metadata_uri: Option<String>,
+) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let sponsor = ctx.accounts.sponsor.key();
+
+    // Sponsors can only grow the reward while it is still open to entries
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        challenge.accepts_submissions(now),
+        ErrorCode::SponsorshipClosed
+    );
+    require!(
+        challenge.prize_kind == PrizeKind::Cpt,
+        ErrorCode::CptPrizeOnly
+    );
+    require!(!challenge.is_duel, ErrorCode::DuelStakesOnly);
+    require!(amount > 0, ErrorCode::InvalidSponsorship);
+    require!(
+        metadata_uri.as_ref().map_or(0, String::len) <= MAX_SPONSOR_URI_LEN,
+        ErrorCode::SponsorUriTooLong
+    );
+
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+    token_account_balance(
+        &ctx.accounts.treasury_token_account,
+        &challenge.treasury,
+        &challenge.reward_token_mint,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidTreasury))?;
+
+    if !challenge.sponsors.contains(&sponsor) {
+        require!(
+            challenge.sponsors.len() < MAX_SPONSORS,
+            ErrorCode::MaxSponsorsReached
+        );
+        challenge.sponsors.push(sponsor);
+    }
+
+    transfer_from_signer(
+        &ctx.accounts.token_program,
+        &ctx.accounts.sponsor_token_account,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.sponsor.to_account_info(),
+        amount,
+    )?;
+    challenge.credit_treasury(amount)?;
+
+    // The deposit goes straight into the reward the winners split
+    challenge.reward = challenge
+        .reward
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    challenge.sponsored = challenge
+        .sponsored
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let record = &mut ctx.accounts.sponsor_record;
+    record.challenge = challenge.key();
+    record.sponsor = sponsor;
+    record.amount = record
+        .amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if metadata_uri.is_some() {
+        record.metadata_uri = metadata_uri;
+    }
+
+    emit!(ChallengeSponsored {
+        challenge: challenge.key(),
+        sponsor,
+        amount,
+        total_sponsored: record.amount,
+        reward: challenge.reward,
+        metadata_uri: record.metadata_uri.clone(),
+    });
+
+    msg!(
+        "{} sponsored {} tokens, reward is now {}",
+        sponsor,
+        amount,
+        challenge.reward
+    );
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::VoteCast;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::profile::{record_vote, require_reputation};
+use crate::instructions::challenge::treasury::transfer_from_signer;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct StakeVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+        constraint = challenge.voting_mode == VotingMode::StakeWeighted @ ErrorCode::WrongVotingMode,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    // Every stake the voter locks in this challenge, withdrawn in one go
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VoteStake::SPACE,
+        seeds = [b"vote_stake", challenge.key().as_ref(), voter.key().as_ref()],
+        bump,
+    )]
+    pub vote_stake: Box<Account<'info, VoteStake>>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Voter's token account
+    #[account(mut)]
+    pub voter_token_account: AccountInfo<'info>,
+
+    /// CHECK: Just storing submission ID for reference
+    pub submission_id: AccountInfo<'info>,
+
+    /// CHECK: Voting Treasury account (PDA) - verified in handler
+    pub voting_treasury: AccountInfo<'info>,
+
+    /// CHECK: Voting Treasury's token account
+    #[account(mut)]
+    pub voting_treasury_token_account: AccountInfo<'info>,
+
+    // Created by the wallet's first challenge action
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = UserProfile::SPACE,
+        seeds = [b"user_profile", voter.key().as_ref()],
+        bump,
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    /// CHECK: Token state PDA, only counted in once the admin has created it
+    #[account(mut, seeds = [b"token_state"], bump)]
+    pub token_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<StakeVote>, stake: u64) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let voter = ctx.accounts.voter.key();
+    let submission_id = ctx.accounts.submission_id.key();
+
+    // The stake is the vote's weight
+    require!(stake > 0, ErrorCode::InvalidVoteWeight);
+
+    let now = Clock::get()?.unix_timestamp;
+    challenge.check_new_vote(&voter, &submission_id, now)?;
+
+    require!(
+        ctx.accounts.voting_treasury.key() == challenge.voting_treasury_pda,
+        ErrorCode::InvalidVotingTreasury
+    );
+    token_account_balance(
+        &ctx.accounts.voting_treasury_token_account,
+        &challenge.voting_treasury_pda,
+        &challenge.reward_token_mint,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidVotingTreasury))?;
+
+    // The fee joins the reward pool, the stake is only held until settlement
+    let voting_fee = challenge.voting_fee;
+    let amount = voting_fee
+        .checked_add(stake)
+        .ok_or(ErrorCode::MathOverflow)?;
+    transfer_from_signer(
+        &ctx.accounts.token_program,
+        &ctx.accounts.voter_token_account,
+        &ctx.accounts.voting_treasury_token_account,
+        &ctx.accounts.voter.to_account_info(),
+        amount,
+    )?;
+    challenge.credit_voting_treasury(voting_fee)?;
+    challenge.locked_stake = challenge
+        .locked_stake
+        .checked_add(stake)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    record_vote(
+        &mut ctx.accounts.user_profile,
+        voter,
+        &ctx.accounts.token_state,
+        voting_fee,
+    )?;
+    require_reputation(&ctx.accounts.user_profile, challenge.min_voter_reputation)?;
+
+    let vote_stake = &mut ctx.accounts.vote_stake;
+    vote_stake.challenge = challenge.key();
+    vote_stake.voter = voter;
+    vote_stake.amount = vote_stake
+        .amount
+        .checked_add(stake)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    challenge.total_votes = challenge
+        .total_votes
+        .checked_add(stake)
+        .ok_or(ErrorCode::MathOverflow)?;
+    challenge.add_weighted_vote(voter, submission_id, stake)?;
+
+    emit!(VoteCast {
+        challenge: challenge.key(),
+        voter,
+        submission: submission_id,
+        fee: voting_fee,
+        weight: stake,
+        stake,
+    });
+
+    msg!(
+        "Vote with {} tokens staked recorded for submission {}",
+        stake,
+        submission_id
+    );
+
+    Ok(())
+}
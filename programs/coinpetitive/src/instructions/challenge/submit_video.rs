@@ -1,105 +1,190 @@
-use crate::constraints::*;
-use crate::errors::ErrorCode;
-use crate::state::*;
-use anchor_lang::prelude::*;
-use anchor_lang::solana_program;
-
-// Token instruction enum
-#[derive(Clone, Debug)]
-pub enum TokenInstruction {
-    Transfer = 3,
-}
-
-#[derive(Accounts)]
-pub struct SubmitVideo<'info> {
-    #[account(mut)]
-    pub participant: Signer<'info>,
-
-    pub challenge: Box<Account<'info, Challenge>>,
-
-    /// CHECK: Treasury account (PDA) - verified in the handler
-    #[account(mut)]
-    pub treasury: AccountInfo<'info>,
-
-    /// CHECK: Token-2022 program
-    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
-    pub token_program: AccountInfo<'info>,
-
-    /// CHECK: Participant's token account
-    #[account(mut)]
-    pub participant_token_account: AccountInfo<'info>,
-
-    /// CHECK: Treasury's token account
-    #[account(mut)]
-    pub treasury_token_account: AccountInfo<'info>,
-
-    /// CHECK: This is a unique reference for the video
-    pub video_reference: AccountInfo<'info>,
-}
-
-pub fn handle(ctx: Context<SubmitVideo>, video_url: String) -> Result<()> {
-    let challenge = &mut ctx.accounts.challenge;
-
-    // Verify treasury account matches the one stored in the challenge
-    require!(
-        ctx.accounts.treasury.key() == challenge.treasury,
-        ErrorCode::InvalidTreasury
-    );
-
-    // Use fixed submission fee instead of challenge.participation_fee
-    msg!(
-        "Submitting video and paying fixed fee: {} tokens",
-        FIXED_SUBMISSION_FEE
-    );
-    msg!("From participant: {}", ctx.accounts.participant.key());
-    msg!("To treasury: {}", ctx.accounts.treasury.key());
-
-    // Create a simplified Transfer instruction manually with FIXED_SUBMISSION_FEE
-    let ix = solana_program::instruction::Instruction {
-        program_id: ctx.accounts.token_program.key(),
-        accounts: vec![
-            solana_program::instruction::AccountMeta::new(
-                ctx.accounts.participant_token_account.key(),
-                false,
-            ),
-            solana_program::instruction::AccountMeta::new(
-                ctx.accounts.treasury_token_account.key(),
-                false,
-            ),
-            solana_program::instruction::AccountMeta::new_readonly(
-                ctx.accounts.participant.key(),
-                true,
-            ),
-        ],
-        // Token instruction 3 = Transfer, followed by amount as little-endian bytes
-        data: [3]
-            .into_iter()
-            .chain(FIXED_SUBMISSION_FEE.to_le_bytes().into_iter())
-            .collect(),
-    };
-
-    // Execute the transfer
-    solana_program::program::invoke(
-        &ix,
-        &[
-            ctx.accounts.participant_token_account.to_account_info(),
-            ctx.accounts.treasury_token_account.to_account_info(),
-            ctx.accounts.participant.to_account_info(),
-        ],
-    )?;
-
-    // Update challenge treasury with fixed fee instead of challenge.participation_fee
-    challenge.challenge_treasury += FIXED_SUBMISSION_FEE;
-
-    msg!(
-        "Video submitted and fixed submission fee of {} paid successfully",
-        FIXED_SUBMISSION_FEE
-    );
-
-    // Add video reference and initial votes
-    challenge
-        .submission_votes
-        .push((ctx.accounts.video_reference.key(), 0));
-
-    Ok(())
-}
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::VideoSubmitted;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program;
+
+// Token instruction enum
+#[derive(Clone, Debug)]
+pub enum TokenInstruction {
+    Transfer = 3,
+}
+
+#[derive(Accounts)]
+pub struct SubmitVideo<'info> {
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    // The team the captain submits for, which is then the entry's submitter
+    #[account(mut)]
+    pub team: Option<Box<Account<'info, Team>>>,
+
+    /// CHECK: Treasury account (PDA) - verified in the handler
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Participant's token account
+    #[account(mut)]
+    pub participant_token_account: AccountInfo<'info>,
+
+    /// CHECK: Treasury's token account
+    #[account(mut)]
+    pub treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: This is a unique reference for the video
+    pub video_reference: AccountInfo<'info>,
+
+    // Created when the participant joined
+    #[account(
+        mut,
+        seeds = [b"user_profile", participant.key().as_ref()],
+        bump,
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+}
+
+pub fn handle(ctx: Context<SubmitVideo>, _video_url: String) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+
+    // Verify treasury account matches the one stored in the challenge
+    require!(
+        ctx.accounts.treasury.key() == challenge.treasury,
+        ErrorCode::InvalidTreasury
+    );
+    token_account_balance(
+        &ctx.accounts.treasury_token_account,
+        &challenge.treasury,
+        &challenge.reward_token_mint,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidTreasury))?;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        challenge.is_active && challenge.accepts_submissions(now),
+        ErrorCode::SubmissionsClosed
+    );
+    require!(
+        challenge.submission_votes.len() < MAX_SUBMISSIONS,
+        ErrorCode::MaxSubmissionsReached
+    );
+    require!(
+        challenge
+            .submitter_of(&ctx.accounts.video_reference.key())
+            .is_none(),
+        ErrorCode::InvalidSubmissionId
+    );
+
+    // Each duelist enters one video, and only once both have staked
+    if challenge.is_duel {
+        let participant = ctx.accounts.participant.key();
+        require!(
+            challenge.duel_matched()
+                && challenge.has_participant(&participant)
+                && !challenge.submitters.iter().any(|(_, s)| *s == participant),
+            ErrorCode::DuelVideoLimit
+        );
+    }
+
+    // A team enters one video, sent by its captain once the team has entered
+    if let Some(team) = &ctx.accounts.team {
+        require_keys_eq!(team.challenge, challenge.key(), ErrorCode::InvalidTeam);
+        require_keys_eq!(
+            team.captain,
+            ctx.accounts.participant.key(),
+            ErrorCode::Unauthorized
+        );
+        require!(team.entered, ErrorCode::TeamNotEntered);
+        require!(team.submission.is_none(), ErrorCode::TeamAlreadySubmitted);
+    }
+
+    // Use fixed submission fee instead of challenge.participation_fee
+    let fee = challenge.submission_fee();
+    msg!("Submitting video and paying fixed fee: {} tokens", fee);
+    msg!("From participant: {}", ctx.accounts.participant.key());
+    msg!("To treasury: {}", ctx.accounts.treasury.key());
+
+    // Duelists enter for free, everyone else pays the fixed fee
+    if fee > 0 {
+        // Create a simplified Transfer instruction manually with the fixed fee
+        let ix = solana_program::instruction::Instruction {
+            program_id: ctx.accounts.token_program.key(),
+            accounts: vec![
+                solana_program::instruction::AccountMeta::new(
+                    ctx.accounts.participant_token_account.key(),
+                    false,
+                ),
+                solana_program::instruction::AccountMeta::new(
+                    ctx.accounts.treasury_token_account.key(),
+                    false,
+                ),
+                solana_program::instruction::AccountMeta::new_readonly(
+                    ctx.accounts.participant.key(),
+                    true,
+                ),
+            ],
+            // Token instruction 3 = Transfer, followed by amount as little-endian bytes
+            data: [3]
+                .into_iter()
+                .chain(fee.to_le_bytes())
+                .collect(),
+        };
+
+        // Execute the transfer
+        solana_program::program::invoke(
+            &ix,
+            &[
+                ctx.accounts.participant_token_account.to_account_info(),
+                ctx.accounts.treasury_token_account.to_account_info(),
+                ctx.accounts.participant.to_account_info(),
+            ],
+        )?;
+    }
+
+    // Update challenge treasury with fixed fee instead of challenge.participation_fee
+    challenge.credit_treasury(fee)?;
+
+    let profile = &mut ctx.accounts.user_profile;
+    profile.fees_paid = profile
+        .fees_paid
+        .checked_add(fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    msg!(
+        "Video submitted and fixed submission fee of {} paid successfully",
+        fee
+    );
+
+    // Add video reference and initial votes, remembering who gets paid if it
+    // wins: the team, for a video its captain enters on its behalf
+    let video_reference = ctx.accounts.video_reference.key();
+    let submitter = match ctx.accounts.team.as_mut() {
+        Some(team) => {
+            team.submission = Some(video_reference);
+            team.key()
+        }
+        None => ctx.accounts.participant.key(),
+    };
+    challenge.submission_votes.push((video_reference, 0));
+    challenge.submitters.push((video_reference, submitter));
+
+    emit!(VideoSubmitted {
+        challenge: challenge.key(),
+        submission: video_reference,
+        participant: submitter,
+        fee,
+    });
+
+    Ok(())
+}
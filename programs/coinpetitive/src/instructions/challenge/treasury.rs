@@ -0,0 +1,255 @@
+use crate::errors::ErrorCode;
+use crate::state::PrizeKind;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::state::Mint as Token2022Mint;
+
+// Token-2022 transfer out of an account owned by a transaction signer
+pub fn transfer_from_signer<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let transfer_ix = solana_program::instruction::Instruction {
+        program_id: token_program.key(),
+        accounts: vec![
+            solana_program::instruction::AccountMeta::new(from.key(), false),
+            solana_program::instruction::AccountMeta::new(to.key(), false),
+            solana_program::instruction::AccountMeta::new_readonly(authority.key(), true),
+        ],
+        data: [3]
+            .into_iter() // Token instruction 3 = Transfer
+            .chain(amount.to_le_bytes())
+            .collect(),
+    };
+
+    solana_program::program::invoke(&transfer_ix, &[from.clone(), to.clone(), authority.clone()])?;
+
+    Ok(())
+}
+
+// Token-2022 transfer out of a PDA-owned token account
+pub fn transfer_signed<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    let transfer_ix = solana_program::instruction::Instruction {
+        program_id: token_program.key(),
+        accounts: vec![
+            solana_program::instruction::AccountMeta::new(from.key(), false),
+            solana_program::instruction::AccountMeta::new(to.key(), false),
+            solana_program::instruction::AccountMeta::new_readonly(authority.key(), true),
+        ],
+        data: [3]
+            .into_iter() // Token instruction 3 = Transfer
+            .chain(amount.to_le_bytes())
+            .collect(),
+    };
+
+    solana_program::program::invoke_signed(
+        &transfer_ix,
+        &[from.clone(), to.clone(), authority.clone()],
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
+
+// Decimals of an SPL Token or Token-2022 mint; both share the base layout
+pub fn mint_decimals(mint: &AccountInfo, token_program: &Pubkey) -> Result<u8> {
+    require!(mint.owner == token_program, ErrorCode::InvalidTokenProgram);
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+    Ok(state.base.decimals)
+}
+
+// TransferChecked for mints the program doesn't know: the token program
+// verifies the mint and decimals, and Token-2022 mints with extensions such
+// as transfer fees refuse the plain Transfer
+fn transfer_checked_ix(
+    token_program: &AccountInfo,
+    from: &AccountInfo,
+    mint: &AccountInfo,
+    to: &AccountInfo,
+    authority: &AccountInfo,
+    amount: u64,
+) -> Result<solana_program::instruction::Instruction> {
+    let decimals = mint_decimals(mint, &token_program.key())?;
+    Ok(solana_program::instruction::Instruction {
+        program_id: token_program.key(),
+        accounts: vec![
+            solana_program::instruction::AccountMeta::new(from.key(), false),
+            solana_program::instruction::AccountMeta::new_readonly(mint.key(), false),
+            solana_program::instruction::AccountMeta::new(to.key(), false),
+            solana_program::instruction::AccountMeta::new_readonly(authority.key(), true),
+        ],
+        data: [12] // Token instruction 12 = TransferChecked
+            .into_iter()
+            .chain(amount.to_le_bytes())
+            .chain([decimals])
+            .collect(),
+    })
+}
+
+// Checked transfer out of an account owned by a transaction signer
+pub fn transfer_checked_from_signer<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let transfer_ix = transfer_checked_ix(token_program, from, mint, to, authority, amount)?;
+
+    solana_program::program::invoke(
+        &transfer_ix,
+        &[from.clone(), mint.clone(), to.clone(), authority.clone()],
+    )?;
+
+    Ok(())
+}
+
+// Checked transfer out of a PDA-owned token account
+pub fn transfer_checked_signed<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    let transfer_ix = transfer_checked_ix(token_program, from, mint, to, authority, amount)?;
+
+    solana_program::program::invoke_signed(
+        &transfer_ix,
+        &[from.clone(), mint.clone(), to.clone(), authority.clone()],
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
+
+// Close an empty PDA-owned token account, returning its rent to `destination`
+pub fn close_token_account_signed<'info>(
+    token_program: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let close_ix = solana_program::instruction::Instruction {
+        program_id: token_program.key(),
+        accounts: vec![
+            solana_program::instruction::AccountMeta::new(account.key(), false),
+            solana_program::instruction::AccountMeta::new(destination.key(), false),
+            solana_program::instruction::AccountMeta::new_readonly(authority.key(), true),
+        ],
+        data: vec![9], // Token instruction 9 = CloseAccount
+    };
+
+    solana_program::program::invoke_signed(
+        &close_ix,
+        &[account.clone(), destination.clone(), authority.clone()],
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
+
+// Move every lamport out of a program-owned treasury PDA
+pub fn drain_lamports(from: &AccountInfo, to: &AccountInfo) -> Result<()> {
+    let lamports = from.lamports();
+    **from.try_borrow_mut_lamports()? = 0;
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+// Move lamports out of a program-owned treasury PDA, keeping the rest
+pub fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+// Pay part of a challenge's prize out of its escrow: a transfer signed by the
+// treasury PDA, or for SOL prizes lamports straight out of the PDA
+#[allow(clippy::too_many_arguments)]
+pub fn pay_prize<'info>(
+    kind: PrizeKind,
+    token_program: &AccountInfo<'info>,
+    escrow: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    treasury_seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    match kind {
+        PrizeKind::Sol => move_lamports(treasury, to, amount),
+        PrizeKind::Cpt | PrizeKind::Token => transfer_checked_signed(
+            token_program,
+            escrow,
+            mint,
+            to,
+            treasury,
+            treasury_seeds,
+            amount,
+        ),
+    }
+}
+
+// Create `owner`'s associated token account for `mint` unless it exists
+pub fn create_associated_token_account<'info>(
+    associated_token_program: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let create_ata_ix = solana_program::instruction::Instruction {
+        program_id: associated_token_program.key(),
+        accounts: vec![
+            solana_program::instruction::AccountMeta::new(payer.key(), true),
+            solana_program::instruction::AccountMeta::new(account.key(), false),
+            solana_program::instruction::AccountMeta::new_readonly(owner.key(), false),
+            solana_program::instruction::AccountMeta::new_readonly(mint.key(), false),
+            solana_program::instruction::AccountMeta::new_readonly(system_program.key(), false),
+            solana_program::instruction::AccountMeta::new_readonly(token_program.key(), false),
+        ],
+        data: vec![1], // Associated token instruction 1 = CreateIdempotent
+    };
+
+    solana_program::program::invoke(
+        &create_ata_ix,
+        &[
+            payer.clone(),
+            account.clone(),
+            owner.clone(),
+            mint.clone(),
+            system_program.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    Ok(())
+}
@@ -1,5 +1,8 @@
 use crate::constraints::*;
 use crate::errors::ErrorCode;
+use crate::events::VoteCast;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::profile::{record_vote, require_reputation};
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
@@ -12,6 +15,7 @@ pub struct VoteForSubmission<'info> {
     #[account(
         mut,
         // other constraints remain, but remove the is_active constraint
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
     )]
     pub challenge: Box<Account<'info, Challenge>>,
 
@@ -42,18 +46,44 @@ pub struct VoteForSubmission<'info> {
     /// CHECK: Voting Treasury's token account
     #[account(mut)]
     pub voting_treasury_token_account: AccountInfo<'info>,
+
+    // Created by the wallet's first challenge action
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = UserProfile::SPACE,
+        seeds = [b"user_profile", voter.key().as_ref()],
+        bump,
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+
+    /// CHECK: Token state PDA, only counted in once the admin has created it
+    #[account(mut, seeds = [b"token_state"], bump)]
+    pub token_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handle(ctx: Context<VoteForSubmission>) -> Result<()> {
+pub fn handle(ctx: Context<VoteForSubmission>, weight: u64) -> Result<()> {
     let challenge = &mut ctx.accounts.challenge;
     let voter = ctx.accounts.voter.key();
     let submission_id = ctx.accounts.submission_id.key();
 
-    // Add a check for maximum voters
+    require!(!challenge.commit_reveal, ErrorCode::CommitRevealRequired);
+
+    // Stake-weighted votes lock their weight through stake_vote, and only
+    // quadratic votes can buy more than one vote
     require!(
-        challenge.voters.len() < 150, // Set a reasonable limit based on your space allocation
-        ErrorCode::MaxVotersReached   // Add this new error
+        challenge.voting_mode != VotingMode::StakeWeighted,
+        ErrorCode::WrongVotingMode
     );
+    require!(
+        weight == 1 || (weight > 1 && challenge.voting_mode == VotingMode::Quadratic),
+        ErrorCode::InvalidVoteWeight
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    challenge.check_new_vote(&voter, &submission_id, now)?;
 
     // Verify treasury account matches the one stored in the challenge
     require!(
@@ -66,11 +96,15 @@ pub fn handle(ctx: Context<VoteForSubmission>) -> Result<()> {
         ctx.accounts.voting_treasury.key() == challenge.voting_treasury_pda,
         ErrorCode::InvalidVotingTreasury
     );
+    token_account_balance(
+        &ctx.accounts.voting_treasury_token_account,
+        &challenge.voting_treasury_pda,
+        &challenge.reward_token_mint,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidVotingTreasury))?;
 
-    // Check if voter has already voted for this submission
-    if challenge.has_voted_for(&voter, &submission_id) {
-        return Err(ErrorCode::AlreadyVoted.into());
-    }
+    // Quadratic votes cost voting_fee * weight²
+    let voting_fee = challenge.vote_cost(weight).ok_or(ErrorCode::MathOverflow)?;
 
     // Create a Token-2022 Transfer instruction - CORRECT IMPLEMENTATION
     let transfer_ix = solana_program::instruction::Instruction {
@@ -87,10 +121,7 @@ pub fn handle(ctx: Context<VoteForSubmission>) -> Result<()> {
             solana_program::instruction::AccountMeta::new_readonly(ctx.accounts.voter.key(), true),
         ],
         // Token instruction 3 = Transfer, followed by amount as little-endian bytes
-        data: [3]
-            .into_iter()
-            .chain(challenge.voting_fee.to_le_bytes().into_iter())
-            .collect(),
+        data: [3].into_iter().chain(voting_fee.to_le_bytes()).collect(),
     };
 
     // Execute the transfer with the correct accounts
@@ -104,11 +135,31 @@ pub fn handle(ctx: Context<VoteForSubmission>) -> Result<()> {
     )?;
 
     // Update voting treasury and total votes
-    challenge.voting_treasury += challenge.voting_fee;
-    challenge.total_votes += 1;
+    challenge.credit_voting_treasury(voting_fee)?;
+    challenge.total_votes = challenge
+        .total_votes
+        .checked_add(weight)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     // This correctly adds/updates the submission vote
-    challenge.add_vote(voter, submission_id)?;
+    challenge.add_weighted_vote(voter, submission_id, weight)?;
+
+    record_vote(
+        &mut ctx.accounts.user_profile,
+        voter,
+        &ctx.accounts.token_state,
+        voting_fee,
+    )?;
+    require_reputation(&ctx.accounts.user_profile, challenge.min_voter_reputation)?;
+
+    emit!(VoteCast {
+        challenge: challenge.key(),
+        voter,
+        submission: submission_id,
+        fee: voting_fee,
+        weight,
+        stake: 0,
+    });
 
     msg!("Vote recorded for submission {}", submission_id);
 
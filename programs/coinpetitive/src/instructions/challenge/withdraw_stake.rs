@@ -0,0 +1,91 @@
+use crate::constraints::*;
+use crate::errors::ErrorCode;
+use crate::events::StakeWithdrawn;
+use crate::instructions::challenge::audit_challenge::token_account_balance;
+use crate::instructions::challenge::treasury::transfer_signed;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    // Anyone can crank a withdrawal; the stake only ever goes to the voter's own ATA
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !challenge.is_active @ ErrorCode::ChallengeStillActive,
+        constraint = !challenge.is_frozen @ ErrorCode::ChallengeFrozen,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+
+    #[account(
+        mut,
+        close = voter,
+        seeds = [b"vote_stake", challenge.key().as_ref(), vote_stake.voter.as_ref()],
+        bump,
+    )]
+    pub vote_stake: Box<Account<'info, VoteStake>>,
+
+    /// CHECK: The voter who locked the stake, receives the account's rent
+    #[account(mut, address = vote_stake.voter)]
+    pub voter: AccountInfo<'info>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Voting Treasury PDA - verified in handler
+    pub voting_treasury: AccountInfo<'info>,
+
+    /// CHECK: Voting Treasury's token account
+    #[account(mut)]
+    pub voting_treasury_token_account: AccountInfo<'info>,
+
+    /// CHECK: Voter's token account - owner verified in handler
+    #[account(mut)]
+    pub voter_token_account: AccountInfo<'info>,
+}
+
+pub fn handle(ctx: Context<WithdrawStake>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let voter = ctx.accounts.vote_stake.voter;
+    let amount = ctx.accounts.vote_stake.amount;
+
+    require!(
+        ctx.accounts.voting_treasury.key() == challenge.voting_treasury_pda,
+        ErrorCode::InvalidVotingTreasury
+    );
+    token_account_balance(
+        &ctx.accounts.voter_token_account,
+        &voter,
+        &challenge.reward_token_mint,
+    )?;
+
+    let challenge_pubkey = challenge.key();
+    let (_, bump) = Pubkey::find_program_address(
+        &[b"voting_treasury", challenge_pubkey.as_ref()],
+        ctx.program_id,
+    );
+    transfer_signed(
+        &ctx.accounts.token_program,
+        &ctx.accounts.voting_treasury_token_account,
+        &ctx.accounts.voter_token_account,
+        &ctx.accounts.voting_treasury,
+        &[b"voting_treasury", challenge_pubkey.as_ref(), &[bump]],
+        amount,
+    )?;
+    challenge.locked_stake = challenge
+        .locked_stake
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+
+    emit!(StakeWithdrawn {
+        challenge: challenge_pubkey,
+        voter,
+        amount,
+    });
+
+    msg!("Returned {} staked tokens to {}", amount, voter);
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 pub mod distribute_token;
 pub mod fee_tracking;
+#[allow(clippy::module_inception)]
 pub mod token;
 
 pub use distribute_token::*;
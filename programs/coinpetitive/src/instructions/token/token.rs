@@ -204,7 +204,15 @@ pub fn mint_tokens(ctx: Context<MintTokens>, quantity: u64) -> Result<()> {
         TokenError::ExceedsMaxSupplyCap
     );
 
-    // Perform the mint - no conditions checked
+    // Anything beyond the initial supply uses up a met mint condition
+    if token_state.current_supply + quantity > INITIAL_SUPPLY {
+        let condition = token_state
+            .use_pending_milestone()
+            .ok_or(TokenError::NoMintConditionsMet)?;
+        msg!("Minting under condition {}", condition);
+    }
+
+    // Perform the mint
     let seeds = &["mint".as_bytes(), &[ctx.bumps.mint]];
     let signer = [&seeds[..]];
     mint_to(
@@ -278,8 +286,8 @@ pub fn transfer_with_limit(ctx: Context<TransferWithLimit>, amount: u64) -> Resu
     );
 
     // Calculate burn amount (1% for liquidity pool transactions)
-    let burn_amount = amount * BURN_RATE as u64 / 100;
-    let transfer_amount = amount - burn_amount;
+    let split = coinpetitive_math::burn_split(amount);
+    let (burn_amount, transfer_amount) = (split.burn, split.transfer);
 
     // Transfer reduced amount to recipient
     transfer(
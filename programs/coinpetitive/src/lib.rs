@@ -1,14 +1,18 @@
 use anchor_lang::prelude::*;
 pub mod constraints;
 pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod payout;
 pub mod state;
 
+#[allow(ambiguous_glob_reexports)]
+pub use instructions::*;
+use state::{DisqualificationReason, PrizeKind};
+
 declare_id!("5NLxDYs6Br5H8D3F3eq4JjGa8wX292onmvwKbLC3wEbU");
 #[program]
 pub mod coinpetitive {
-    use crate::instructions::finalize_challenge::FinalizeChallenge;
-
     use super::*;
 
     // pub fn init_token(ctx: Context<InitToken>, metadata: InitTokenParams) -> Result<()> {
@@ -34,29 +38,54 @@ pub mod coinpetitive {
     // Updated Challenge Functions
     pub fn create_challenge(
         ctx: Context<CreateChallenge>,
-        reward: u64,
-        participation_fee: u64,
-        voting_fee: u64,
-        max_participants: u8,
-        challenge_id: u64, // Add this new parameter
+        params: CreateChallengeParams,
     ) -> Result<()> {
-        instructions::challenge::create_challenge::handle(
-            ctx,
-            reward,
-            participation_fee,
-            voting_fee,
-            max_participants,
-            challenge_id,
-        )
+        instructions::challenge::create_challenge::handle(ctx, params)
+    }
+
+    pub fn fund_prize(ctx: Context<FundPrize>, kind: PrizeKind) -> Result<()> {
+        instructions::challenge::fund_prize::handle(ctx, kind)
     }
 
     pub fn pay_participation_fee(ctx: Context<PayParticipationFee>) -> Result<()> {
         instructions::challenge::pay_participation_fee::handle(ctx)
     }
 
-    // pub fn vote_for_submission(ctx: Context<VoteForSubmission>) -> Result<()> {
-    //     instructions::challenge::vote_for_submission::handle(ctx)
-    // }
+    pub fn sponsor_challenge(
+        ctx: Context<SponsorChallenge>,
+        amount: u64,
+        metadata_uri: Option<String>,
+    ) -> Result<()> {
+        instructions::challenge::sponsor_challenge::handle(ctx, amount, metadata_uri)
+    }
+
+    pub fn vote_for_submission(ctx: Context<VoteForSubmission>, weight: u64) -> Result<()> {
+        instructions::challenge::vote_for_submission::handle(ctx, weight)
+    }
+
+    pub fn stake_vote(ctx: Context<StakeVote>, stake: u64) -> Result<()> {
+        instructions::challenge::stake_vote::handle(ctx, stake)
+    }
+
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        instructions::challenge::withdraw_stake::handle(ctx)
+    }
+
+    pub fn score_submission(ctx: Context<ScoreSubmission>, score: u8) -> Result<()> {
+        instructions::challenge::score_submission::handle(ctx, score)
+    }
+
+    pub fn claim_judge_fee(ctx: Context<ClaimJudgeFee>, judge: Pubkey) -> Result<()> {
+        instructions::challenge::claim_judge_fee::handle(ctx, judge)
+    }
+
+    pub fn commit_vote(ctx: Context<CommitVote>, commitment: [u8; 32]) -> Result<()> {
+        instructions::challenge::commit_vote::handle(ctx, commitment)
+    }
+
+    pub fn reveal_vote(ctx: Context<RevealVote>, salt: [u8; 32]) -> Result<()> {
+        instructions::challenge::reveal_vote::handle(ctx, salt)
+    }
 
     // pub fn finalize_challenge(
     //     ctx: Context<FinalizeChallenge>,
@@ -66,8 +95,31 @@ pub mod coinpetitive {
     //     instructions::finalize_challenge(ctx, &winner_pubkey, winning_votes)
     // }
 
-    pub fn finalize_challenge(ctx: Context<FinalizeChallenge>) -> Result<()> {
-        instructions::challenge::finalize_challenge::handle(ctx)
+    pub fn propose_results(ctx: Context<ProposeResults>) -> Result<()> {
+        instructions::challenge::propose_results::handle(ctx)
+    }
+
+    pub fn raise_dispute(ctx: Context<RaiseDispute>) -> Result<()> {
+        instructions::challenge::raise_dispute::handle(ctx)
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, upheld: bool) -> Result<()> {
+        instructions::challenge::resolve_dispute::handle(ctx, upheld)
+    }
+
+    pub fn expire_dispute(ctx: Context<ExpireDispute>) -> Result<()> {
+        instructions::challenge::expire_dispute::handle(ctx)
+    }
+
+    pub fn settle_results(ctx: Context<SettleResults>) -> Result<()> {
+        instructions::challenge::settle_results::handle(ctx)
+    }
+
+    pub fn disqualify_submission(
+        ctx: Context<DisqualifySubmission>,
+        reason: DisqualificationReason,
+    ) -> Result<()> {
+        instructions::challenge::disqualify_submission::handle(ctx, reason)
     }
 
     pub fn submit_video(ctx: Context<SubmitVideo>, video_url: String) -> Result<()> {
@@ -86,6 +138,175 @@ pub mod coinpetitive {
         instructions::challenge::claim_creator_reward::handle(ctx)
     }
 
+    pub fn close_submissions(ctx: Context<CloseSubmissions>) -> Result<()> {
+        instructions::challenge::close_submissions::handle(ctx)
+    }
+
+    pub fn cancel_challenge(ctx: Context<CancelChallenge>) -> Result<()> {
+        instructions::challenge::cancel_challenge::handle(ctx)
+    }
+
+    pub fn claim_refund(ctx: Context<ClaimRefund>, wallet: Pubkey) -> Result<()> {
+        instructions::challenge::claim_refund::handle(ctx, wallet)
+    }
+
+    pub fn claim_sponsor_refund(ctx: Context<ClaimSponsorRefund>) -> Result<()> {
+        instructions::challenge::claim_sponsor_refund::handle(ctx)
+    }
+
+    pub fn claim_badge(ctx: Context<ClaimBadge>) -> Result<()> {
+        instructions::challenge::claim_badge::handle(ctx)
+    }
+
+    pub fn mint_trophy(ctx: Context<MintTrophy>, rank: u8) -> Result<()> {
+        instructions::challenge::mint_trophy::handle(ctx, rank)
+    }
+
+    pub fn close_challenge(ctx: Context<CloseChallenge>) -> Result<()> {
+        instructions::challenge::close_challenge::handle(ctx)
+    }
+
+    pub fn audit_challenge(ctx: Context<AuditChallenge>) -> Result<AuditReport> {
+        instructions::challenge::audit_challenge::handle(ctx)
+    }
+
+    pub fn set_challenge_frozen(ctx: Context<SetChallengeFrozen>, frozen: bool) -> Result<()> {
+        instructions::challenge::set_challenge_frozen::handle(ctx, frozen)
+    }
+
+    pub fn set_crank_bounty(ctx: Context<SetCrankBounty>, finalize_bounty_bps: u16) -> Result<()> {
+        instructions::challenge::set_crank_bounty::handle(ctx, finalize_bounty_bps)
+    }
+
+    pub fn set_dispute_config(
+        ctx: Context<SetDisputeConfig>,
+        bond: u64,
+        window: i64,
+    ) -> Result<()> {
+        instructions::challenge::set_dispute_config::handle(ctx, bond, window)
+    }
+
+    pub fn set_reputation_config(
+        ctx: Context<SetReputationConfig>,
+        large_reward: u64,
+        min_creator_reputation: u64,
+    ) -> Result<()> {
+        instructions::challenge::set_reputation_config::handle(
+            ctx,
+            large_reward,
+            min_creator_reputation,
+        )
+    }
+
+    pub fn create_season(
+        ctx: Context<CreateSeason>,
+        season_id: u64,
+        start_time: i64,
+        end_time: i64,
+        pool_share_bps: u16,
+        payout_places: u8,
+    ) -> Result<()> {
+        instructions::challenge::create_season::handle(
+            ctx,
+            season_id,
+            start_time,
+            end_time,
+            pool_share_bps,
+            payout_places,
+        )
+    }
+
+    pub fn settle_season<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleSeason<'info>>,
+    ) -> Result<()> {
+        instructions::challenge::settle_season::handle(ctx)
+    }
+
+    pub fn create_team(
+        ctx: Context<CreateTeam>,
+        members: Vec<Pubkey>,
+        shares_bps: Vec<u16>,
+    ) -> Result<()> {
+        instructions::challenge::create_team::handle(ctx, members, shares_bps)
+    }
+
+    pub fn pay_team_fee(ctx: Context<PayTeamFee>, all: bool) -> Result<()> {
+        instructions::challenge::pay_team_fee::handle(ctx, all)
+    }
+
+    pub fn refund_team_fee(ctx: Context<RefundTeamFee>, member: Pubkey) -> Result<()> {
+        instructions::challenge::refund_team_fee::handle(ctx, member)
+    }
+
+    pub fn split_team_reward<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SplitTeamReward<'info>>,
+    ) -> Result<()> {
+        instructions::challenge::split_team_reward::handle(ctx)
+    }
+
+    pub fn open_duel(
+        ctx: Context<OpenDuel>,
+        stake: u64,
+        opponent: Option<Pubkey>,
+        accept_by: i64,
+    ) -> Result<()> {
+        instructions::challenge::open_duel::handle(ctx, stake, opponent, accept_by)
+    }
+
+    pub fn accept_duel(ctx: Context<AcceptDuel>) -> Result<()> {
+        instructions::challenge::accept_duel::handle(ctx)
+    }
+
+    pub fn create_tournament(
+        ctx: Context<CreateTournament>,
+        tournament_id: u64,
+        round_count: u8,
+        advance_count: u8,
+        prize: u64,
+        roll_fees: bool,
+    ) -> Result<()> {
+        instructions::challenge::create_tournament::handle(
+            ctx,
+            tournament_id,
+            round_count,
+            advance_count,
+            prize,
+            roll_fees,
+        )
+    }
+
+    pub fn add_tournament_round(ctx: Context<AddTournamentRound>) -> Result<()> {
+        instructions::challenge::add_tournament_round::handle(ctx)
+    }
+
+    pub fn advance_tournament(ctx: Context<AdvanceTournament>) -> Result<()> {
+        instructions::challenge::advance_tournament::handle(ctx)
+    }
+
+    pub fn finish_tournament(ctx: Context<FinishTournament>) -> Result<()> {
+        instructions::challenge::finish_tournament::handle(ctx)
+    }
+
+    pub fn initialize_trophy_collection(
+        ctx: Context<InitializeTrophyCollection>,
+        symbol: String,
+        base_uri: String,
+    ) -> Result<()> {
+        instructions::challenge::initialize_trophy_collection::handle(ctx, symbol, base_uri)
+    }
+
+    pub fn preview_finalization(
+        ctx: Context<PreviewFinalization>,
+    ) -> Result<payout::FinalizationPreview> {
+        instructions::challenge::preview_finalization::handle(ctx)
+    }
+
+    pub fn preview_voter_reward(
+        ctx: Context<PreviewVoterReward>,
+    ) -> Result<payout::VoterRewardPreview> {
+        instructions::challenge::preview_voter_reward::handle(ctx)
+    }
+
     pub fn initialize_fee_tracker(ctx: Context<InitializeFeeTracker>) -> Result<()> {
         instructions::initialize_fee_tracker(ctx)
     }
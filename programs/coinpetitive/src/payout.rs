@@ -0,0 +1,245 @@
+use crate::constraints::MAX_TROPHY_RANK;
+use crate::errors::ErrorCode;
+use crate::state::{Challenge, JudgingMode, PrizeKind, Season, SeasonEntry, VotingMode};
+use anchor_lang::prelude::*;
+
+// The arithmetic itself lives in coinpetitive-math so clients share it
+pub use coinpetitive_math::{voter_share, weighted_share};
+
+// A ranked submission and what it is paid at finalization
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PlacePayout {
+    pub submission: Pubkey,
+    pub votes: u64,
+    pub points: u64, // What it was ranked by, see ranking_points
+    pub amount: u64,
+}
+
+// Everything settle_results will pay out, in payout order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FinalizationPreview {
+    pub total_reward: u64,
+    pub platform_fee: u64,
+    pub crank_bounty: u64, // Part of the platform fee paid to whoever finalizes
+    pub places: Vec<PlacePayout>, // Winner first, then runner-up if there is one
+    pub judge_fee: u64,    // Left in the treasury for the judges to claim
+    pub creator_remainder: u64,
+    pub voter_reward: VoterRewardPreview,
+}
+
+// What each voter who backed the winner receives from the voting treasury
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VoterRewardPreview {
+    pub winning_submission: Pubkey,
+    pub voting_pool: u64,
+    pub winning_voters: u64,
+    pub winning_weight: u64,
+    pub reward_per_voter: u64, // Per unit of weight in stake-weighted and quadratic challenges
+}
+
+// A leaderboard place and what settle_season pays it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SeasonPrize {
+    pub wallet: Pubkey,
+    pub points: u64,
+    pub amount: u64,
+}
+
+// What a submission is ranked by: its votes in public-vote challenges,
+// otherwise its judges' score blended with its share of the vote, both in
+// basis points
+pub fn ranking_points(challenge: &Challenge, submission: &Pubkey, votes: u64) -> u64 {
+    let mut scores = challenge.scores_for(submission);
+    let score = match challenge.judging_mode {
+        JudgingMode::PublicVote => return votes,
+        JudgingMode::Mean => coinpetitive_math::mean_score(&scores),
+        JudgingMode::Median => coinpetitive_math::median_score(&mut scores),
+    };
+    let total_votes = challenge
+        .submission_votes
+        .iter()
+        .fold(0u64, |total, (_, v)| total.saturating_add(*v));
+
+    coinpetitive_math::blend_points(
+        coinpetitive_math::score_points(score),
+        coinpetitive_math::vote_points(votes, total_votes),
+        challenge.judge_weight_bps,
+    )
+}
+
+// Submissions sorted by ranking points (descending), ties keep submission
+// order. Disqualified submissions are left out
+pub fn rank_submissions(challenge: &Challenge) -> Result<Vec<(Pubkey, u64)>> {
+    let mut sorted_submissions: Vec<(Pubkey, u64)> = challenge
+        .submission_votes
+        .iter()
+        .filter(|(submission, _)| !challenge.is_disqualified(submission))
+        .copied()
+        .collect();
+    require!(!sorted_submissions.is_empty(), ErrorCode::NoSubmissions);
+
+    sorted_submissions.sort_by_key(|(submission, votes)| {
+        std::cmp::Reverse(ranking_points(challenge, submission, *votes))
+    });
+
+    // Make sure the winner has at least one vote, or the judges have scored
+    match challenge.judging_mode {
+        JudgingMode::PublicVote => require!(sorted_submissions[0].1 > 0, ErrorCode::NoVotes),
+        JudgingMode::Mean | JudgingMode::Median => {
+            require!(challenge.has_results(), ErrorCode::NoScores)
+        }
+    }
+
+    Ok(sorted_submissions)
+}
+
+// The ranked places that earn a trophy: the winner and runner-up, or just
+// the winner of a duel, who takes the whole pot
+pub fn trophy_places(challenge: &Challenge) -> Result<Vec<(Pubkey, u64)>> {
+    let places = if challenge.is_duel {
+        1
+    } else {
+        MAX_TROPHY_RANK
+    };
+    let mut ranked = rank_submissions(challenge)?;
+    ranked.truncate(usize::from(places));
+    Ok(ranked)
+}
+
+pub fn plan_finalization(
+    challenge: &Challenge,
+    finalize_bounty_bps: u16,
+) -> Result<FinalizationPreview> {
+    let ranked = rank_submissions(challenge)?;
+    require!(challenge.has_quorum(), ErrorCode::QuorumNotMet);
+
+    let total_reward = challenge.reward;
+    // A duel's winner takes the whole pot of both stakes
+    let rewards = if challenge.is_duel {
+        coinpetitive_math::duel_rewards(total_reward)
+    } else {
+        coinpetitive_math::place_rewards(total_reward)
+    };
+
+    // CPT rewards are paid out of the fees collected in the treasury, so the
+    // creator only receives what is left once the full reward has been covered.
+    // SOL and token prizes come out of their own escrow instead
+    let fees_left = coinpetitive_math::creator_remainder(
+        challenge.challenge_treasury,
+        challenge.reward_from_fees(),
+    )
+    .ok_or(ErrorCode::InsufficientFunds)?;
+    require!(
+        challenge.prize_kind == PrizeKind::Cpt || challenge.prize_pool >= total_reward,
+        ErrorCode::InsufficientFunds
+    );
+
+    // Judges share the creator's leftover fees, if any of them scored
+    let judge_fee = if challenge.scoring_judges() == 0 {
+        0
+    } else {
+        coinpetitive_math::judge_fee(fees_left, challenge.judge_fee_bps)
+    };
+
+    let place = |(submission, votes): (Pubkey, u64), amount: u64| PlacePayout {
+        submission,
+        votes,
+        points: ranking_points(challenge, &submission, votes),
+        amount,
+    };
+    let mut places = vec![place(ranked[0], rewards.winner)];
+    if let Some(&runner_up) = ranked.get(1) {
+        places.push(place(runner_up, rewards.runner_up));
+    }
+
+    Ok(FinalizationPreview {
+        total_reward,
+        platform_fee: rewards.platform_fee,
+        crank_bounty: coinpetitive_math::crank_bounty(rewards.platform_fee, finalize_bounty_bps),
+        places,
+        judge_fee,
+        creator_remainder: fees_left - judge_fee,
+        voter_reward: plan_voter_reward(challenge)?,
+    })
+}
+
+pub fn plan_voter_reward(challenge: &Challenge) -> Result<VoterRewardPreview> {
+    // Before finalization the pool is still open and the leader stands in for the winner
+    let (winning_submission, voting_pool) = match challenge.winner {
+        Some(winner) => (winner, challenge.voting_reward_pool),
+        None => (rank_submissions(challenge)?[0].0, challenge.voting_treasury),
+    };
+
+    let winning_voters = challenge.voters_for(&winning_submission);
+    let winning_weight = match challenge.winner {
+        Some(_) => challenge.winning_weight,
+        None => challenge.weight_for(&winning_submission),
+    };
+    let reward_per_voter = match challenge.voting_mode {
+        VotingMode::Flat => voter_share(voting_pool, winning_voters),
+        VotingMode::StakeWeighted | VotingMode::Quadratic => {
+            weighted_share(voting_pool, 1, winning_weight)
+        }
+    };
+
+    Ok(VoterRewardPreview {
+        winning_submission,
+        voting_pool,
+        winning_voters,
+        winning_weight,
+        reward_per_voter,
+    })
+}
+
+// A winning voter's share of the pool frozen at finalization. Flat challenges
+// split it evenly using the count provided by the backend, the other modes in
+// proportion to the weight each voter put behind the winner
+pub fn voter_reward(challenge: &Challenge, voter: &Pubkey, winning_voters_count: u64) -> u64 {
+    match (challenge.voting_mode, challenge.winner) {
+        (VotingMode::Flat, _) => voter_share(challenge.voting_reward_pool, winning_voters_count),
+        (_, Some(winner)) => weighted_share(
+            challenge.voting_reward_pool,
+            challenge.weight_of(voter, &winner),
+            challenge.winning_weight,
+        ),
+        (_, None) => 0,
+    }
+}
+
+// Whether a winning voter still has a share to claim. Rounding dust left in
+// the voting treasury doesn't count, so it can be swept to the creator
+pub fn voter_rewards_pending(challenge: &Challenge) -> bool {
+    let Some(winner) = challenge.winner else {
+        return false;
+    };
+    let claimable = |share: u64| share > 0 && share <= challenge.voting_treasury;
+
+    let winners = challenge.voters_for(&winner);
+    challenge
+        .voters
+        .iter()
+        .enumerate()
+        .filter(|(i, (_, s))| *s == winner && !challenge.voter_rewarded(*i))
+        .any(|(_, (voter, _))| claimable(voter_reward(challenge, voter, winners)))
+}
+
+// Split a season's prize pool between its placed wallets in proportion to
+// their points, with the rounding dust going to first place
+pub fn plan_season_prizes(season: &Season) -> Vec<SeasonPrize> {
+    let placed: Vec<&SeasonEntry> = season.placed().collect();
+    let total_points: u64 = placed.iter().map(|e| e.points).sum();
+
+    let mut prizes: Vec<SeasonPrize> = placed
+        .iter()
+        .map(|e| SeasonPrize {
+            wallet: e.wallet,
+            points: e.points,
+            amount: weighted_share(season.prize_pool, e.points, total_points),
+        })
+        .collect();
+    let paid: u64 = prizes.iter().map(|p| p.amount).sum();
+    if let Some(first) = prizes.first_mut() {
+        first.amount += season.prize_pool - paid;
+    }
+    prizes
+}
@@ -1,9 +1,41 @@
+pub mod badge;
 pub mod challenge_tracker;
 pub mod challenge;
+pub mod crank_config;
+pub mod dispute;
+pub mod dispute_config;
 pub mod fee_tracker;
+pub mod moderation_record;
+pub mod reputation_config;
+pub mod season;
+pub mod season_config;
+pub mod sponsor_record;
+pub mod team;
 pub mod token;
+pub mod tournament;
+pub mod trophy;
+pub mod trophy_collection;
+pub mod user_profile;
+pub mod vote_commitment;
+pub mod vote_stake;
 
+pub use badge::*;
 pub use challenge_tracker::*;
 pub use challenge::*;
+pub use crank_config::*;
+pub use dispute::*;
+pub use dispute_config::*;
 pub use fee_tracker::*;
-pub use token::*;
\ No newline at end of file
+pub use moderation_record::*;
+pub use reputation_config::*;
+pub use season::*;
+pub use season_config::*;
+pub use sponsor_record::*;
+pub use team::*;
+pub use token::*;
+pub use tournament::*;
+pub use trophy::*;
+pub use trophy_collection::*;
+pub use user_profile::*;
+pub use vote_commitment::*;
+pub use vote_stake::*;
\ No newline at end of file
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+// Proof a wallet took part in a settled challenge, alongside the soulbound
+// badge token it was minted. One per challenge and wallet
+#[account]
+#[derive(Default, Debug)]
+pub struct Badge {
+    pub challenge: Pubkey,
+    pub wallet: Pubkey,
+    pub participant: bool,
+    pub voter: bool,
+    pub minted_at: i64,
+}
+
+impl Badge {
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 1 + 8;
+}
+
+impl anchor_lang::Id for Badge {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
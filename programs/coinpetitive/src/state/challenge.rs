@@ -2,6 +2,38 @@ use anchor_lang::prelude::*;
 
 use crate::errors;
 
+// How a challenge prices and weighs votes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VotingMode {
+    #[default]
+    Flat, // One vote per submission at voting_fee
+    StakeWeighted, // Weight is the CPT locked alongside the voting fee
+    Quadratic,     // n votes on one submission cost voting_fee * n²
+}
+
+// How submissions are ranked at finalization
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JudgingMode {
+    #[default]
+    PublicVote, // Votes alone
+    Mean,   // Mean judge score, blended with the vote by judge_weight_bps
+    Median, // Median judge score, blended with the vote by judge_weight_bps
+}
+
+// What the winners are paid in. Fees are always CPT
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PrizeKind {
+    #[default]
+    Cpt, // Paid out of the fees in the treasury ATA
+    Token, // Any other SPL or Token-2022 mint, escrowed in the treasury PDA's ATA for it
+    Sol,   // Lamports escrowed in the treasury PDA itself
+}
+
+/// Ledger invariant: `challenge_treasury` and `voting_treasury` never exceed the
+/// token balances of the treasury and voting treasury ATAs. Every transfer into
+/// or out of those ATAs goes through the `credit_*` / `debit_*` helpers below,
+/// so the ledger can lag behind the real balance (e.g. tokens sent directly to
+/// the ATA) but can never promise more than the ATA holds.
 #[account]
 #[derive(Default, Debug)]
 pub struct Challenge {
@@ -22,6 +54,55 @@ pub struct Challenge {
     pub voters: Vec<(Pubkey, Pubkey)>,        // (voter, submission_id)
     pub treasury: Pubkey,                     // Treasury PDA address
     pub voting_treasury_pda: Pubkey,          // Add this new field for voting treasury PDA
+    pub voting_reward_pool: u64,              // Voting treasury snapshot taken at finalization
+    pub is_frozen: bool,                      // Set by the admin when an audit finds a mismatch
+    pub is_cancelled: bool,                   // Cancelled challenges refund instead of paying out
+    pub submission_end: i64,                  // Unix time submissions and entries close
+    pub voting_end: i64,                      // Unix time voting closes and finalization opens
+    pub submissions_closed: bool,             // Set by close_submissions once submission_end passes
+    pub submitters: Vec<(Pubkey, Pubkey)>,    // (submission_id, participant) for paying winners
+    pub min_participants: u8,                 // Entries needed by submission_end
+    pub min_voters: u8,                       // Distinct voters needed by voting_end
+    pub max_voters: u8,                       // Maximum distinct voters
+    pub commit_reveal: bool, // Votes are committed sealed and revealed after voting_end
+    pub reveal_end: i64,     // Unix time the reveal window closes
+    pub vote_commitments: u16, // Sealed votes committed so far
+    pub voting_mode: VotingMode,
+    pub vote_weights: Vec<u64>, // Weight of each entry in voters, zeroed once rewarded
+    pub locked_stake: u64,      // Stake-weighted voting stakes held in the voting treasury ATA
+    pub winning_weight: u64,    // Weight behind the winner, snapshot taken at finalization
+    pub judging_mode: JudgingMode,
+    pub judges: Vec<Pubkey>,             // Judge panel, fixed at creation
+    pub judge_scores: Vec<(u8, u8, u8)>, // (judge index, submitters index, score)
+    pub judge_weight_bps: u16,           // Share of the ranking decided by the judges
+    pub judge_fee_bps: u16,              // Share of the creator's leftover fees paid to judges
+    pub judge_fee_pool: u64,             // Judges' fees left in the treasury at finalization
+    pub judges_paid: u8,                 // Bit per judge index, set once nothing more is owed
+    pub results_proposed: bool,          // Set by propose_results, opens the dispute window
+    pub dispute_window: i64,             // Seconds the window runs, snapshot at propose_results
+    pub dispute_end: i64,                // Unix time the results can be settled
+    pub dispute_bond: u64,               // Bond a dispute needs, snapshot at propose_results
+    pub open_disputes: u8,               // Disputes raised and not yet resolved
+    pub locked_bonds: u64,               // Dispute bonds held in the treasury ATA
+    pub disqualified: u32,               // Bit per submitters index struck from the ranking
+    pub sponsors: Vec<Pubkey>,           // Sponsors whose deposits are still held
+    pub sponsored: u64,                  // Their deposits, already counted in reward
+    pub prize_kind: PrizeKind,           // Switched from CPT by fund_prize
+    pub prize_mint: Pubkey,              // CPT, the Token prize's mint, or unset for SOL
+    pub prize_token_program: Pubkey,     // System program for SOL prizes
+    pub prize_pool: u64,                 // Escrowed Token or SOL prize still held
+    pub voter_badges: bool,              // Voters earn a badge too, not just participants
+    pub min_participant_reputation: u64, // Reputation needed to join, zero for anyone
+    pub min_voter_reputation: u64,       // Reputation needed to vote, zero for anyone
+    pub season: u64,                     // Season running when it settled, zero for none
+    pub tournament: Pubkey,              // Tournament this is a round of, unset otherwise
+    pub tournament_round: u8,            // Its round, counting from zero
+    pub fees_to_tournament: bool,        // Settlement pays leftover fees to the tournament prize
+    pub is_duel: bool,                   // Head-to-head wager, participation_fee is the stake
+    pub duel_opponent: Pubkey,           // Named opponent, unset while open to anyone
+    pub duel_accept_by: i64,             // Unix time the opponent must match the stake by
+    pub voters_rewarded: u64,            // Bit per voters index whose voter reward has been paid
+    pub team_fees_held: u64,             // Parts of team fees in the treasury ATA, team not entered yet
 }
 
 impl anchor_lang::Id for Challenge {
@@ -30,6 +111,14 @@ impl anchor_lang::Id for Challenge {
     }
 }
 
+// What claim_refund owes a wallet on a cancelled challenge
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Refund {
+    pub participation: u64,
+    pub submission: u64,
+    pub voting: u64,
+}
+
 impl Challenge {
     // Check if a participant is already in the challenge
     pub fn has_participant(&self, participant: &Pubkey) -> bool {
@@ -43,8 +132,49 @@ impl Challenge {
             .any(|(v, s)| v == voter && s == submission_id)
     }
 
+    // Checks shared by every way of casting a vote
+    pub fn check_new_vote(&self, voter: &Pubkey, submission_id: &Pubkey, now: i64) -> Result<()> {
+        require!(
+            self.voters.len() < crate::constraints::MAX_VOTERS,
+            errors::ErrorCode::MaxVotersReached
+        );
+        let is_new_voter = !self.voters.iter().any(|(v, _)| v == voter);
+        require!(
+            !is_new_voter || self.unique_voters() < self.max_voters as usize,
+            errors::ErrorCode::MaxVotersReached
+        );
+
+        require!(self.accepts_votes(now), errors::ErrorCode::VotingClosed);
+
+        // Only submissions that were actually entered can be voted for
+        require!(
+            self.submitter_of(submission_id).is_some(),
+            errors::ErrorCode::SubmissionNotFound
+        );
+        require!(
+            !self.is_disqualified(submission_id),
+            errors::ErrorCode::SubmissionDisqualified
+        );
+
+        require!(
+            !self.has_voted_for(voter, submission_id),
+            errors::ErrorCode::AlreadyVoted
+        );
+        Ok(())
+    }
+
     // Add a vote for a submission
     pub fn add_vote(&mut self, voter: Pubkey, submission_id: Pubkey) -> Result<()> {
+        self.add_weighted_vote(voter, submission_id, 1)
+    }
+
+    // Add a vote carrying `weight` votes for a submission
+    pub fn add_weighted_vote(
+        &mut self,
+        voter: Pubkey,
+        submission_id: Pubkey,
+        weight: u64,
+    ) -> Result<()> {
         // Check if voter has already voted for this submission
         if self.has_voted_for(&voter, &submission_id) {
             return Err(errors::ErrorCode::AlreadyVoted.into());
@@ -52,6 +182,7 @@ impl Challenge {
 
         // Record the vote
         self.voters.push((voter, submission_id));
+        self.vote_weights.push(weight);
 
         // Find or add submission to the votes tracking
         let submission_idx = self
@@ -61,12 +192,463 @@ impl Challenge {
 
         if let Some(idx) = submission_idx {
             // Update existing submission vote count
-            self.submission_votes[idx].1 += 1;
+            self.submission_votes[idx].1 = self.submission_votes[idx]
+                .1
+                .checked_add(weight)
+                .ok_or(errors::ErrorCode::MathOverflow)?;
+        } else {
+            // Add new submission with its first vote
+            self.submission_votes.push((submission_id, weight));
+        }
+
+        Ok(())
+    }
+
+    // Index in voters of `voter`'s vote for `submission_id`
+    pub fn vote_index(&self, voter: &Pubkey, submission_id: &Pubkey) -> Option<usize> {
+        self.voters
+            .iter()
+            .position(|(v, s)| v == voter && s == submission_id)
+    }
+
+    // Number of votes cast for `submission_id`, one per voter
+    pub fn voters_for(&self, submission_id: &Pubkey) -> u64 {
+        self.voters
+            .iter()
+            .filter(|(_, s)| s == submission_id)
+            .count() as u64
+    }
+
+    // Whether the vote at `index` in voters has already been paid its reward
+    pub fn voter_rewarded(&self, index: usize) -> bool {
+        self.voters_rewarded & (1 << index) != 0
+    }
+
+    // Record the reward for the vote at `index` as claimed, failing if it
+    // already was. Everything a claim credits hangs off this record
+    pub fn record_voter_reward(&mut self, index: usize) -> Result<()> {
+        require!(
+            !self.voter_rewarded(index),
+            errors::ErrorCode::VoterAlreadyRewarded
+        );
+        self.voters_rewarded |= 1 << index;
+        Ok(())
+    }
+
+    // Weight of the vote at `index` in voters
+    pub fn vote_weight(&self, index: usize) -> u64 {
+        self.vote_weights.get(index).copied().unwrap_or(1)
+    }
+
+    // Weight a voter put behind a submission, 0 if they didn't vote for it
+    pub fn weight_of(&self, voter: &Pubkey, submission_id: &Pubkey) -> u64 {
+        self.voters
+            .iter()
+            .position(|(v, s)| v == voter && s == submission_id)
+            .map_or(0, |i| self.vote_weight(i))
+    }
+
+    // Total weight of the votes cast for a submission, as vote_weights holds it
+    pub fn weight_for(&self, submission_id: &Pubkey) -> u64 {
+        self.voters
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, s))| s == submission_id)
+            .map(|(i, _)| self.vote_weight(i))
+            .fold(0u64, u64::saturating_add)
+    }
+
+    // What a vote of `weight` costs in voting fees; stakes are paid on top
+    pub fn vote_cost(&self, weight: u64) -> Option<u64> {
+        match self.voting_mode {
+            VotingMode::Quadratic => coinpetitive_math::quadratic_cost(self.voting_fee, weight),
+            VotingMode::Flat | VotingMode::StakeWeighted => Some(self.voting_fee),
+        }
+    }
+
+    // Remove every vote a wallet cast, returning their weights
+    pub fn remove_votes_of(&mut self, wallet: &Pubkey) -> Vec<u64> {
+        let mut removed = Vec::new();
+        let mut i = 0;
+        while i < self.voters.len() {
+            if self.voters[i].0 == *wallet {
+                removed.push(self.vote_weight(i));
+                self.voters.remove(i);
+                if i < self.vote_weights.len() {
+                    self.vote_weights.remove(i);
+                }
+            } else {
+                i += 1;
+            }
+        }
+        removed
+    }
+
+    pub fn submitter_of(&self, submission_id: &Pubkey) -> Option<Pubkey> {
+        self.submitters
+            .iter()
+            .find(|(s, _)| s == submission_id)
+            .map(|(_, participant)| *participant)
+    }
+
+    // Remove every submission a wallet entered, returning how many of them
+    // still have their fee held. Disqualified entries were already refunded
+    // or slashed when they were struck
+    pub fn remove_submissions_of(&mut self, wallet: &Pubkey) -> u64 {
+        let mut refundable = 0;
+        let mut i = 0;
+        while i < self.submitters.len() {
+            if self.submitters[i].1 != *wallet {
+                i += 1;
+                continue;
+            }
+            let bit = 1u32 << i;
+            if self.disqualified & bit == 0 {
+                refundable += 1;
+            }
+            // Later entries move down one index, and so do their bits
+            let below = self.disqualified & (bit - 1);
+            self.disqualified = below | ((self.disqualified >> 1) & !(bit - 1));
+            self.submitters.remove(i);
+        }
+        refundable
+    }
+
+    // Wallets a cancelled challenge still owes a refund, each once
+    pub fn refundable_wallets(&self) -> Vec<Pubkey> {
+        let mut wallets: Vec<Pubkey> = Vec::new();
+        let owed = self
+            .participants
+            .iter()
+            .chain(
+                self.submitters
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| self.disqualified & (1 << i) == 0)
+                    .map(|(_, (_, participant))| participant),
+            )
+            .chain(self.voters.iter().map(|(voter, _)| voter));
+        for wallet in owed {
+            if !wallets.contains(wallet) {
+                wallets.push(*wallet);
+            }
+        }
+        wallets
+    }
+
+    // What each video costs to enter: the fixed fee, waived for duelists who
+    // already staked and whose fees would only end up with the creator
+    pub fn submission_fee(&self) -> u64 {
+        if self.is_duel {
+            0
+        } else {
+            crate::constraints::FIXED_SUBMISSION_FEE
+        }
+    }
+
+    // Work out `wallet`'s refund: its participation fee, the fixed fee for
+    // every video it entered and what each of its votes cost. Its entries are
+    // removed so nothing is refunded twice. Stakes are not part of it, they
+    // come back through withdraw_stake
+    pub fn take_refund(&mut self, wallet: &Pubkey) -> Result<Refund> {
+        let participation = if self.has_participant(wallet) {
+            self.participants.retain(|p| p != wallet);
+            self.participation_fee
         } else {
-            // Add new submission with 1 vote
-            self.submission_votes.push((submission_id, 1));
+            0
+        };
+
+        let submission = self
+            .submission_fee()
+            .checked_mul(self.remove_submissions_of(wallet))
+            .ok_or(errors::ErrorCode::MathOverflow)?;
+
+        let mut voting: u64 = 0;
+        for weight in self.remove_votes_of(wallet) {
+            let cost = self
+                .vote_cost(weight)
+                .ok_or(errors::ErrorCode::MathOverflow)?;
+            voting = voting
+                .checked_add(cost)
+                .ok_or(errors::ErrorCode::MathOverflow)?;
         }
 
+        Ok(Refund {
+            participation,
+            submission,
+            voting,
+        })
+    }
+
+    // Set by settle_results, the only way a challenge gets a winner; a
+    // cancelled challenge's treasury only pays refunds
+    pub fn is_settled(&self) -> bool {
+        !self.is_active && !self.is_cancelled && self.winner.is_some()
+    }
+
+    pub fn accepts_submissions(&self, now: i64) -> bool {
+        !self.submissions_closed && now < self.submission_end
+    }
+
+    // A team short of its fee can no longer enter once the challenge is over
+    // or its entries have closed, so its members' parts can be refunded
+    pub fn team_fees_refundable(&self, now: i64) -> bool {
+        !self.is_active || !self.accepts_submissions(now)
+    }
+
+    // In commit-reveal mode this is the commit phase
+    pub fn accepts_votes(&self, now: i64) -> bool {
+        now < self.voting_end
+    }
+
+    // Unix time the vote count is final and the challenge can be settled
+    pub fn results_at(&self) -> i64 {
+        if self.commit_reveal {
+            self.reveal_end
+        } else {
+            self.voting_end
+        }
+    }
+
+    // Number of wallets that cast at least one vote
+    pub fn unique_voters(&self) -> usize {
+        let mut seen: Vec<&Pubkey> = Vec::new();
+        for (voter, _) in &self.voters {
+            if !seen.contains(&voter) {
+                seen.push(voter);
+            }
+        }
+        seen.len()
+    }
+
+    // A challenge can only be finalized once it has enough participants and
+    // voters and at least one submission has a vote, or a score when judged
+    pub fn has_quorum(&self) -> bool {
+        self.participants.len() >= self.min_participants as usize
+            && self.unique_voters() >= self.min_voters as usize
+            && self.has_results()
+    }
+
+    // Nobody can join after submission_end or vote once results are in, so a
+    // challenge that is short by then can never reach its quorum
+    pub fn quorum_missed(&self, now: i64) -> bool {
+        let short_of_participants =
+            now >= self.submission_end && self.participants.len() < self.min_participants as usize;
+        short_of_participants
+            || self.duel_unmatched(now)
+            || (now >= self.results_at() && !self.has_quorum())
+    }
+
+    // Both sides of a duel have staked once its second participant is in
+    pub fn duel_matched(&self) -> bool {
+        self.is_duel && self.participants.len() >= 2
+    }
+
+    // A duel nobody matched by duel_accept_by refunds its creator
+    pub fn duel_unmatched(&self, now: i64) -> bool {
+        self.is_duel && !self.duel_matched() && now >= self.duel_accept_by
+    }
+
+    // Whether `wallet` may take up the duel: the named opponent, or anyone
+    // but the creator while it is open
+    pub fn can_accept_duel(&self, wallet: &Pubkey) -> bool {
+        *wallet != self.creator
+            && (self.duel_opponent == Pubkey::default() || self.duel_opponent == *wallet)
+    }
+
+    pub fn judge_index(&self, judge: &Pubkey) -> Option<usize> {
+        self.judges.iter().position(|j| j == judge)
+    }
+
+    // Record or replace a judge's score for the submission at `submission_index`
+    // in submitters
+    pub fn set_score(&mut self, judge_index: u8, submission_index: u8, score: u8) {
+        match self
+            .judge_scores
+            .iter_mut()
+            .find(|(j, s, _)| *j == judge_index && *s == submission_index)
+        {
+            Some(entry) => entry.2 = score,
+            None => self
+                .judge_scores
+                .push((judge_index, submission_index, score)),
+        }
+    }
+
+    // Every score a submission has received
+    pub fn scores_for(&self, submission_id: &Pubkey) -> Vec<u8> {
+        let Some(index) = self.submitters.iter().position(|(s, _)| s == submission_id) else {
+            return Vec::new();
+        };
+        self.judge_scores
+            .iter()
+            .filter(|(_, s, _)| *s as usize == index)
+            .map(|(_, _, score)| *score)
+            .collect()
+    }
+
+    // Bit per judge index that has scored at least one submission
+    pub fn scoring_judges(&self) -> u8 {
+        self.judge_scores
+            .iter()
+            .fold(0u8, |bits, (j, _, _)| bits | (1 << j))
+    }
+
+    // Each scoring judge's equal part of the judge fee pool
+    pub fn judge_fee_share(&self) -> u64 {
+        coinpetitive_math::voter_share(
+            self.judge_fee_pool,
+            self.scoring_judges().count_ones() as u64,
+        )
+    }
+
+    // Judge fees still held in the treasury for judges who haven't claimed
+    pub fn judge_fees_owed(&self) -> u64 {
+        let unpaid = self.scoring_judges() & !self.judges_paid;
+        self.judge_fee_share()
+            .saturating_mul(unpaid.count_ones() as u64)
+    }
+
+    // Whether there is anything to rank submissions by
+    pub fn has_results(&self) -> bool {
+        match self.judging_mode {
+            JudgingMode::PublicVote => self
+                .submission_votes
+                .iter()
+                .any(|(submission, votes)| *votes > 0 && !self.is_disqualified(submission)),
+            JudgingMode::Mean | JudgingMode::Median => self
+                .judge_scores
+                .iter()
+                .any(|(_, s, _)| self.disqualified & (1 << s) == 0),
+        }
+    }
+
+    pub fn is_disqualified(&self, submission_id: &Pubkey) -> bool {
+        self.submitters
+            .iter()
+            .position(|(s, _)| s == submission_id)
+            .is_some_and(|i| self.disqualified & (1 << i) != 0)
+    }
+
+    // Strike a submission from the ranking; its votes and scores are kept
+    pub fn disqualify(&mut self, submission_id: &Pubkey) -> Result<()> {
+        let index = self
+            .submitters
+            .iter()
+            .position(|(s, _)| s == submission_id)
+            .ok_or(errors::ErrorCode::SubmissionNotFound)?;
+        self.disqualified |= 1 << index;
+        Ok(())
+    }
+
+    // Results can be disputed from propose_results until dispute_end
+    pub fn accepts_disputes(&self, now: i64) -> bool {
+        self.results_proposed && now < self.dispute_end
+    }
+
+    // Disputes still open this long after the window closed can be expired
+    pub fn disputes_expired(&self, now: i64) -> bool {
+        self.results_proposed
+            && now
+                >= self
+                    .dispute_end
+                    .saturating_add(crate::constraints::DISPUTE_RESOLUTION_PERIOD)
+    }
+
+    // Take a resolved or expired dispute's bond off the open ones
+    pub fn release_bond(&mut self, bond: u64) -> Result<()> {
+        self.locked_bonds = self
+            .locked_bonds
+            .checked_sub(bond)
+            .ok_or(errors::ErrorCode::InsufficientFunds)?;
+        self.open_disputes = self
+            .open_disputes
+            .checked_sub(1)
+            .ok_or(errors::ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // Payouts wait for the dispute window to pass and every dispute to be resolved
+    pub fn can_settle(&self, now: i64) -> bool {
+        self.results_proposed && now >= self.dispute_end && self.open_disputes == 0
+    }
+
+    // A sponsor's pro-rata share of what the treasury still holds for
+    // sponsors, which is all of `amount` unless the ledger has come up short
+    pub fn sponsor_refund(&self, amount: u64) -> u64 {
+        let held = self.sponsored.min(self.challenge_treasury);
+        coinpetitive_math::weighted_share(held, amount, self.sponsored)
+    }
+
+    pub fn is_tournament_round(&self) -> bool {
+        self.tournament != Pubkey::default()
+    }
+
+    // Who settlement pays the leftover fees to: the creator, or the
+    // tournament's prize escrow for rounds that roll them
+    pub fn remainder_owner(&self) -> Pubkey {
+        if self.fees_to_tournament {
+            self.tournament
+        } else {
+            self.creator
+        }
+    }
+
+    // What settlement takes out of the CPT fees; other prizes are escrowed
+    pub fn reward_from_fees(&self) -> u64 {
+        match self.prize_kind {
+            PrizeKind::Cpt => self.reward,
+            PrizeKind::Token | PrizeKind::Sol => 0,
+        }
+    }
+
+    // Record part of the prize paid out, from the fees or the escrow
+    pub fn debit_prize(&mut self, amount: u64) -> Result<()> {
+        match self.prize_kind {
+            PrizeKind::Cpt => self.debit_treasury(amount),
+            PrizeKind::Token | PrizeKind::Sol => {
+                self.prize_pool = self
+                    .prize_pool
+                    .checked_sub(amount)
+                    .ok_or(errors::ErrorCode::InsufficientFunds)?;
+                Ok(())
+            }
+        }
+    }
+
+    // Record tokens received by the treasury ATA
+    pub fn credit_treasury(&mut self, amount: u64) -> Result<()> {
+        self.challenge_treasury = self
+            .challenge_treasury
+            .checked_add(amount)
+            .ok_or(errors::ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // Record tokens paid out of the treasury ATA
+    pub fn debit_treasury(&mut self, amount: u64) -> Result<()> {
+        self.challenge_treasury = self
+            .challenge_treasury
+            .checked_sub(amount)
+            .ok_or(errors::ErrorCode::InsufficientFunds)?;
+        Ok(())
+    }
+
+    // Record tokens received by the voting treasury ATA
+    pub fn credit_voting_treasury(&mut self, amount: u64) -> Result<()> {
+        self.voting_treasury = self
+            .voting_treasury
+            .checked_add(amount)
+            .ok_or(errors::ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // Record tokens paid out of the voting treasury ATA
+    pub fn debit_voting_treasury(&mut self, amount: u64) -> Result<()> {
+        self.voting_treasury = self
+            .voting_treasury
+            .checked_sub(amount)
+            .ok_or(errors::ErrorCode::InsufficientFunds)?;
         Ok(())
     }
 }
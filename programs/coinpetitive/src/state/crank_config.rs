@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug)]
+pub struct CrankConfig {
+    pub authority: Pubkey,
+    pub finalize_bounty_bps: u16, // Share of the platform fee paid to whoever finalizes
+}
+
+impl CrankConfig {
+    pub const SPACE: usize = 8 + 32 + 2;
+}
+
+impl anchor_lang::Id for CrankConfig {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
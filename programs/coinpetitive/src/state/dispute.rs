@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+// A bonded objection to a submission, closed once it is resolved
+#[account]
+#[derive(Default, Debug)]
+pub struct Dispute {
+    pub challenge: Pubkey,
+    pub submission: Pubkey,
+    pub disputer: Pubkey,
+    pub bond: u64, // Held in the challenge treasury ATA until resolved
+}
+
+impl Dispute {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8;
+}
+
+impl anchor_lang::Id for Dispute {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug)]
+pub struct DisputeConfig {
+    pub authority: Pubkey,
+    pub bond: u64,   // CPT a participant posts to dispute a submission
+    pub window: i64, // Seconds between propose_results and settle_results
+}
+
+impl DisputeConfig {
+    pub const SPACE: usize = 8 + 32 + 8 + 8;
+}
+
+impl anchor_lang::Id for DisputeConfig {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
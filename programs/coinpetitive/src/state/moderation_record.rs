@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+// Why a submission was taken out of the ranking, which also decides what
+// happens to its submission fee
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DisqualificationReason {
+    #[default]
+    Spam, // Fee slashed
+    Abusive,    // Fee slashed
+    Plagiarism, // Fee slashed
+    Ineligible, // Fee refunded, entered in good faith
+    Duplicate,  // Fee refunded, entered twice by mistake
+}
+
+impl DisqualificationReason {
+    pub fn refunds_fee(&self) -> bool {
+        matches!(
+            self,
+            DisqualificationReason::Ineligible | DisqualificationReason::Duplicate
+        )
+    }
+}
+
+// A moderator's decision to disqualify a submission, kept for audits
+#[account]
+#[derive(Default, Debug)]
+pub struct ModerationRecord {
+    pub challenge: Pubkey,
+    pub submission: Pubkey,
+    pub submitter: Pubkey,
+    pub moderator: Pubkey,
+    pub reason: DisqualificationReason,
+    pub fee_refunded: u64, // 0 when the fee was slashed
+    pub fee_slashed: u64,  // Left in the challenge treasury
+    pub disqualified_at: i64,
+}
+
+impl ModerationRecord {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 32 + 1 + 8 + 8 + 8;
+}
+
+impl anchor_lang::Id for ModerationRecord {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
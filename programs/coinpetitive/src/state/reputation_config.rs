@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug)]
+pub struct ReputationConfig {
+    pub authority: Pubkey,
+    pub large_reward: u64, // Rewards from here on need min_creator_reputation, zero for none
+    pub min_creator_reputation: u64,
+}
+
+impl ReputationConfig {
+    pub const SPACE: usize = 8 + 32 + 8 + 8;
+
+    /// Reputation a creator needs for a challenge with `reward`.
+    pub fn required_for(&self, reward: u64) -> u64 {
+        if self.large_reward > 0 && reward >= self.large_reward {
+            self.min_creator_reputation
+        } else {
+            0
+        }
+    }
+}
+
+impl anchor_lang::Id for ReputationConfig {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
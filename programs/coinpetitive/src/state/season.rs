@@ -0,0 +1,71 @@
+use crate::constraints::SEASON_LEADERBOARD_SIZE;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct SeasonEntry {
+    pub wallet: Pubkey,
+    pub points: u64,
+}
+
+impl SeasonEntry {
+    pub fn is_empty(&self) -> bool {
+        self.wallet == Pubkey::default()
+    }
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct Season {
+    pub id: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub pool_share_bps: u16, // Share of each CPT platform fee paid into the prize pool
+    pub payout_places: u8,   // Leaderboard places settle_season pays
+    pub prize_pool: u64,     // CPT held in the season's token account
+    pub settled: bool,
+    pub leaderboard: [SeasonEntry; SEASON_LEADERBOARD_SIZE], // Most points first, empty slots last
+}
+
+impl Season {
+    pub const SPACE: usize = 8 + 8 + 8 + 8 + 2 + 1 + 8 + 1 + (32 + 8) * SEASON_LEADERBOARD_SIZE;
+
+    /// Rank `wallet` with its season total of `points`. A wallet off the
+    /// leaderboard only gets on by beating the lowest entry; ties keep
+    /// whoever got there first.
+    pub fn record(&mut self, wallet: Pubkey, points: u64) {
+        let board = &mut self.leaderboard;
+        let last = board.len() - 1;
+        let slot = board
+            .iter()
+            .position(|e| e.wallet == wallet)
+            .or_else(|| board.iter().position(SeasonEntry::is_empty))
+            .or_else(|| (board[last].points < points).then_some(last));
+        let Some(mut i) = slot else {
+            return;
+        };
+        if board[i].wallet == wallet && board[i].points >= points {
+            return;
+        }
+
+        // Totals only grow, so the entry can only move up
+        board[i] = SeasonEntry { wallet, points };
+        while i > 0 && board[i - 1].points < points {
+            board.swap(i - 1, i);
+            i -= 1;
+        }
+    }
+
+    /// The entries settle_season pays, best first.
+    pub fn placed(&self) -> impl Iterator<Item = &SeasonEntry> {
+        self.leaderboard
+            .iter()
+            .take(self.payout_places as usize)
+            .filter(|e| !e.is_empty())
+    }
+}
+
+impl anchor_lang::Id for Season {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug)]
+pub struct SeasonConfig {
+    pub authority: Pubkey,
+    pub current_season: u64, // Latest season created, zero before the first
+    pub current_start: i64,
+    pub current_end: i64, // The next season can't start before this
+}
+
+impl SeasonConfig {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 8;
+
+    /// The season running at `now`, if any.
+    pub fn running_at(&self, now: i64) -> Option<u64> {
+        (self.current_season > 0 && self.current_start <= now && now < self.current_end)
+            .then_some(self.current_season)
+    }
+}
+
+impl anchor_lang::Id for SeasonConfig {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+// A wallet's contribution to a challenge's reward, kept after settlement for
+// branding and closed once a cancelled challenge refunds it
+#[account]
+#[derive(Default, Debug)]
+pub struct SponsorRecord {
+    pub challenge: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64, // Every deposit the sponsor made, added to the reward
+    pub metadata_uri: Option<String>, // Branding shown alongside the challenge
+}
+
+impl SponsorRecord {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1 + 4 + crate::constraints::MAX_SPONSOR_URI_LEN;
+}
+
+impl anchor_lang::Id for SponsorRecord {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
@@ -0,0 +1,72 @@
+use crate::constraints::MAX_TEAM_MEMBERS;
+use anchor_lang::prelude::*;
+
+// A team entering a challenge as one participant. The team PDA stands in for
+// a wallet: it is what the challenge lists, submits as and pays out to
+#[account]
+#[derive(Default, Debug)]
+pub struct Team {
+    pub challenge: Pubkey,
+    pub captain: Pubkey,
+    pub members: Vec<Pubkey>,       // Captain first
+    pub shares_bps: Vec<u16>,       // Shares of the fee and payouts, adding up to 10,000
+    pub paid: Vec<bool>,            // Members whose part of the fee is in
+    pub entered: bool,              // Whole fee paid and the team listed as a participant
+    pub submission: Option<Pubkey>, // The team's one video
+    pub created_at: i64,
+}
+
+impl Team {
+    pub const SPACE: usize = 8
+        + 32
+        + 32
+        + (4 + 32 * MAX_TEAM_MEMBERS)
+        + (4 + 2 * MAX_TEAM_MEMBERS)
+        + (4 + MAX_TEAM_MEMBERS)
+        + 1
+        + 33
+        + 8;
+
+    pub fn member_index(&self, wallet: &Pubkey) -> Option<usize> {
+        self.members.iter().position(|m| m == wallet)
+    }
+
+    /// `amount` split by the members' shares, in member order. The captain
+    /// takes the rounding dust, so the parts always add up to `amount`.
+    pub fn split(&self, amount: u64) -> Vec<u64> {
+        let mut parts: Vec<u64> = self
+            .shares_bps
+            .iter()
+            .map(|&share| coinpetitive_math::team_share(amount, share))
+            .collect();
+        let dust = amount - parts.iter().sum::<u64>();
+        if let Some(captain) = parts.first_mut() {
+            *captain += dust;
+        }
+        parts
+    }
+
+    pub fn is_fully_paid(&self) -> bool {
+        self.paid.iter().all(|&paid| paid)
+    }
+
+    /// Take back `wallet`'s paid part of `fee` while the team has not entered,
+    /// marking it unpaid. Returns the part, or None if nothing is owed.
+    pub fn take_part(&mut self, wallet: &Pubkey, fee: u64) -> Option<u64> {
+        if self.entered {
+            return None;
+        }
+        let i = self.member_index(wallet)?;
+        if !self.paid[i] {
+            return None;
+        }
+        self.paid[i] = false;
+        Some(self.split(fee)[i])
+    }
+}
+
+impl anchor_lang::Id for Team {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
@@ -1,3 +1,4 @@
+use crate::constraints::WALLET_MILESTONES;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -23,4 +24,31 @@ impl anchor_lang::Id for TokenState {
 
 impl TokenState {
     pub const SPACE: usize = 8 + std::mem::size_of::<TokenState>();
-}
\ No newline at end of file
+
+    /// Count a wallet seen for the first time, meeting any unique-wallet mint
+    /// condition it reaches. Returns the condition it met, if any.
+    pub fn track_new_wallet(&mut self) -> Option<u8> {
+        self.unique_wallets = self.unique_wallets.saturating_add(1);
+
+        let (_, condition) = WALLET_MILESTONES
+            .iter()
+            .find(|(threshold, _)| *threshold == self.unique_wallets)?;
+        let index = *condition as usize;
+        self.mint_conditions_met[index] = true;
+        if self.pending_mint_milestone.is_none() && !self.mint_conditions_used[index] {
+            self.pending_mint_milestone = Some(*condition);
+        }
+        Some(*condition)
+    }
+
+    /// Use up the pending mint condition for a mint, moving on to the next
+    /// one met and not yet used. Returns the condition used, if any.
+    pub fn use_pending_milestone(&mut self) -> Option<u8> {
+        let condition = self.pending_mint_milestone.take()?;
+        self.mint_conditions_used[condition as usize] = true;
+        self.pending_mint_milestone = (0..8u8).find(|&index| {
+            self.mint_conditions_met[index as usize] && !self.mint_conditions_used[index as usize]
+        });
+        Some(condition)
+    }
+}
@@ -0,0 +1,56 @@
+use crate::constraints::{MAX_SUBMISSIONS, MAX_TOURNAMENT_ROUNDS};
+use anchor_lang::prelude::*;
+
+// A knockout event: each round is a challenge of its own, and only the top
+// submitters of one round may enter the next
+#[account]
+#[derive(Default, Debug)]
+pub struct Tournament {
+    pub creator: Pubkey,
+    pub id: u64,
+    pub round_count: u8,        // Rounds it runs, the last being the final
+    pub advance_count: u8,      // Top submitters of a round who go through
+    pub rounds: Vec<Pubkey>,    // Round challenges in order, as they were added
+    pub current_round: u8,      // Round being played, counting from zero
+    pub qualified: Vec<Pubkey>, // Wallets let into the current round, empty for the first
+    pub prize: u64,             // CPT the creator escrowed
+    pub roll_fees: bool,        // Rounds' leftover fees join the prize
+    pub finished: bool,
+    pub winner: Option<Pubkey>, // Final's winning wallet, once paid
+    pub created_at: i64,
+}
+
+impl Tournament {
+    pub const SPACE: usize = 8
+        + 32
+        + 8
+        + 1
+        + 1
+        + (4 + 32 * MAX_TOURNAMENT_ROUNDS)
+        + 1
+        + (4 + 32 * MAX_SUBMISSIONS)
+        + 8
+        + 1
+        + 1
+        + 33
+        + 8;
+
+    pub fn current_round_challenge(&self) -> Option<Pubkey> {
+        self.rounds.get(self.current_round as usize).copied()
+    }
+
+    pub fn is_final_round(&self) -> bool {
+        self.current_round as usize + 1 == self.round_count as usize
+    }
+
+    /// Whether `wallet` may join round `round`.
+    pub fn admits(&self, round: u8, wallet: &Pubkey) -> bool {
+        round == self.current_round && (round == 0 || self.qualified.contains(wallet))
+    }
+}
+
+impl anchor_lang::Id for Tournament {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
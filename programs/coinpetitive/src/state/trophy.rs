@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+// A placed winner's 1-of-1 trophy NFT, one per challenge and place. Kept after
+// the challenge is closed as the on-chain record of the win
+#[account]
+#[derive(Default, Debug)]
+pub struct Trophy {
+    pub challenge: Pubkey,
+    pub submission: Pubkey,
+    pub winner: Pubkey,
+    pub mint: Pubkey,
+    pub rank: u8,   // 1 for the winner, 2 for the runner-up
+    pub votes: u64, // Votes the submission finished with
+    pub minted_at: i64,
+}
+
+impl Trophy {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 32 + 1 + 8 + 8;
+}
+
+impl anchor_lang::Id for Trophy {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+// The Metaplex collection every trophy belongs to. The PDA is the collection's
+// update authority and mints and verifies each trophy itself
+#[account]
+#[derive(Default, Debug)]
+pub struct TrophyCollection {
+    pub authority: Pubkey,
+    pub mint: Pubkey,     // Collection NFT mint
+    pub symbol: String,   // Symbol of the collection and every trophy
+    pub base_uri: String, // Trophy metadata lives at <base_uri>/<challenge>/<rank>.json
+    pub minted: u64,      // Trophies minted so far
+}
+
+impl TrophyCollection {
+    pub const SPACE: usize = 8
+        + 32
+        + 32
+        + 4
+        + crate::constraints::MAX_TROPHY_SYMBOL_LEN
+        + 4
+        + crate::constraints::MAX_TROPHY_URI_LEN
+        + 8;
+}
+
+impl anchor_lang::Id for TrophyCollection {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+// A wallet's lifetime statistics, created the first time it creates, joins
+// or votes in a challenge. One per wallet
+#[account]
+#[derive(Default, Debug)]
+pub struct UserProfile {
+    pub wallet: Pubkey,
+    pub challenges_created: u64,
+    pub challenges_joined: u64,
+    pub challenges_won: u64,
+    pub votes_cast: u64,
+    pub cpt_earned: u64, // Prizes and voter rewards paid in CPT
+    pub fees_paid: u64,  // Participation, submission and voting fees, stakes excluded
+    pub created_at: i64,
+    pub reputation: u64,    // Starts at BASE_REPUTATION, never below zero
+    pub season: u64,        // Last season the wallet scored in
+    pub season_points: u64, // Points in that season
+}
+
+impl UserProfile {
+    pub const SPACE: usize = 8 + 32 + 8 * 6 + 8 + 8 + 8 + 8;
+
+    /// Whether the account was just created and not yet claimed for a wallet.
+    pub fn is_new(&self) -> bool {
+        self.wallet == Pubkey::default()
+    }
+}
+
+impl anchor_lang::Id for UserProfile {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+// A sealed vote in a commit-reveal challenge, closed once it is revealed
+#[account]
+#[derive(Default, Debug)]
+pub struct VoteCommitment {
+    pub challenge: Pubkey,
+    pub voter: Pubkey,
+    pub commitment: [u8; 32], // hash(challenge, voter, submission_id, salt)
+}
+
+impl VoteCommitment {
+    pub const SPACE: usize = 8 + 32 + 32 + 32;
+
+    // What a voter commits to; the salt keeps the choice secret until reveal,
+    // and binding the challenge and voter stops anyone replaying another
+    // voter's commitment as their own
+    pub fn hash(
+        challenge: &Pubkey,
+        voter: &Pubkey,
+        submission_id: &Pubkey,
+        salt: &[u8; 32],
+    ) -> [u8; 32] {
+        hashv(&[
+            challenge.as_ref(),
+            voter.as_ref(),
+            submission_id.as_ref(),
+            salt,
+        ])
+        .to_bytes()
+    }
+}
+
+impl anchor_lang::Id for VoteCommitment {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
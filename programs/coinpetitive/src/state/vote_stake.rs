@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+// CPT a voter locked in a stake-weighted challenge, closed once it is withdrawn
+#[account]
+#[derive(Default, Debug)]
+pub struct VoteStake {
+    pub challenge: Pubkey,
+    pub voter: Pubkey,
+    pub amount: u64, // Sum of the stakes behind every vote the voter cast
+}
+
+impl VoteStake {
+    pub const SPACE: usize = 8 + 32 + 32 + 8;
+}
+
+impl anchor_lang::Id for VoteStake {
+    fn id() -> Pubkey {
+        crate::ID
+    }
+}
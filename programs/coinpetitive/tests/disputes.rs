@@ -0,0 +1,88 @@
+use anchor_lang::prelude::Pubkey;
+use coinpetitive::constraints::DISPUTE_RESOLUTION_PERIOD;
+use coinpetitive::payout::plan_finalization;
+use coinpetitive::state::Challenge;
+
+// A CPT challenge with proposed results open to disputes until `dispute_end`
+fn proposed_challenge(dispute_end: i64) -> (Challenge, Pubkey, Pubkey) {
+    let (top, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut challenge = Challenge {
+        reward: 1_000,
+        challenge_treasury: 1_000,
+        submitters: vec![(top, Pubkey::new_unique()), (second, Pubkey::new_unique())],
+        results_proposed: true,
+        dispute_end,
+        ..Default::default()
+    };
+    for _ in 0..3 {
+        challenge.add_vote(Pubkey::new_unique(), top).unwrap();
+    }
+    challenge.add_vote(Pubkey::new_unique(), second).unwrap();
+    (challenge, top, second)
+}
+
+#[test]
+fn results_settle_only_once_the_window_closes_without_open_disputes() {
+    let (mut challenge, _, _) = proposed_challenge(100);
+
+    assert!(challenge.accepts_disputes(99));
+    assert!(!challenge.can_settle(99));
+    assert!(!challenge.accepts_disputes(100));
+    assert!(challenge.can_settle(100));
+
+    // A dispute raised in time holds the payouts until it is resolved
+    challenge.open_disputes = 1;
+    assert!(!challenge.can_settle(1_000));
+    challenge.open_disputes = 0;
+    assert!(challenge.can_settle(1_000));
+}
+
+#[test]
+fn an_upheld_dispute_passes_the_winner_over() {
+    let (mut challenge, top, second) = proposed_challenge(100);
+    challenge.disqualify(&top).unwrap();
+
+    let plan = plan_finalization(&challenge, 0).unwrap();
+    assert_eq!(plan.places.len(), 1);
+    assert_eq!(plan.places[0].submission, second);
+}
+
+#[test]
+fn striking_the_only_voted_entry_misses_the_quorum() {
+    let (top, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut challenge = Challenge {
+        submitters: vec![(top, Pubkey::new_unique()), (other, Pubkey::new_unique())],
+        voting_end: 200,
+        ..Default::default()
+    };
+    challenge.add_vote(Pubkey::new_unique(), top).unwrap();
+    assert!(!challenge.quorum_missed(200));
+
+    challenge.disqualify(&top).unwrap();
+    assert!(!challenge.quorum_missed(199));
+    assert!(challenge.quorum_missed(200));
+    assert!(plan_finalization(&challenge, 0).is_err());
+}
+
+#[test]
+fn an_unresolved_dispute_expires_and_frees_the_challenge() {
+    let (mut challenge, top, _) = proposed_challenge(100);
+    challenge.open_disputes = 1;
+    challenge.locked_bonds = 50;
+
+    let deadline = 100 + DISPUTE_RESOLUTION_PERIOD;
+    assert!(!challenge.disputes_expired(deadline - 1));
+    assert!(challenge.disputes_expired(deadline));
+
+    challenge.release_bond(50).unwrap();
+    assert_eq!(challenge.open_disputes, 0);
+    assert_eq!(challenge.locked_bonds, 0);
+    assert!(challenge.can_settle(deadline));
+
+    // The disputed entry keeps its place
+    let plan = plan_finalization(&challenge, 0).unwrap();
+    assert_eq!(plan.places[0].submission, top);
+
+    // A bond can't be released twice
+    assert!(challenge.release_bond(50).is_err());
+}
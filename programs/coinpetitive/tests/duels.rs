@@ -0,0 +1,87 @@
+use anchor_lang::prelude::Pubkey;
+use coinpetitive::payout::plan_finalization;
+use coinpetitive::state::Challenge;
+
+fn open_duel(creator: Pubkey, opponent: Pubkey, accept_by: i64) -> Challenge {
+    Challenge {
+        creator,
+        is_duel: true,
+        participants: vec![creator],
+        duel_opponent: opponent,
+        duel_accept_by: accept_by,
+        submission_end: 500,
+        voting_end: 1_000,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn an_unmatched_duel_expires_at_accept_by() {
+    let creator = Pubkey::new_unique();
+    let challenge = open_duel(creator, Pubkey::default(), 100);
+
+    assert!(!challenge.duel_unmatched(99));
+    assert!(!challenge.quorum_missed(99));
+    assert!(challenge.duel_unmatched(100));
+    assert!(challenge.quorum_missed(100));
+    assert_eq!(challenge.refundable_wallets(), vec![creator]);
+}
+
+#[test]
+fn a_matched_duel_no_longer_expires() {
+    let creator = Pubkey::new_unique();
+    let mut challenge = open_duel(creator, Pubkey::default(), 100);
+    challenge.participants.push(Pubkey::new_unique());
+
+    assert!(challenge.duel_matched());
+    assert!(!challenge.duel_unmatched(100));
+    assert!(!challenge.quorum_missed(100));
+}
+
+#[test]
+fn only_the_named_opponent_can_take_up_a_duel() {
+    let (creator, opponent) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let named = open_duel(creator, opponent, 100);
+    assert!(named.can_accept_duel(&opponent));
+    assert!(!named.can_accept_duel(&Pubkey::new_unique()));
+
+    let open = open_duel(creator, Pubkey::default(), 100);
+    assert!(open.can_accept_duel(&Pubkey::new_unique()));
+    assert!(!open.can_accept_duel(&creator));
+}
+
+#[test]
+fn the_duel_winner_takes_both_stakes_less_the_fee() {
+    let (creator, opponent) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (theirs, ours) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut challenge = open_duel(creator, opponent, 100);
+    challenge.participants.push(opponent);
+    challenge.submitters = vec![(theirs, creator), (ours, opponent)];
+    challenge.reward = 2_000;
+    challenge.challenge_treasury = 2_000;
+    challenge.add_vote(Pubkey::new_unique(), ours).unwrap();
+
+    let plan = plan_finalization(&challenge, 0).unwrap();
+    assert_eq!(plan.places.len(), 1);
+    assert_eq!(plan.places[0].submission, ours);
+    assert_eq!(plan.platform_fee + plan.places[0].amount, 2_000);
+    assert_eq!(plan.creator_remainder, 0);
+}
+
+#[test]
+fn a_cancelled_duel_refunds_the_stake_and_no_submission_fee() {
+    let (creator, opponent) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let video = Pubkey::new_unique();
+    let mut challenge = Challenge {
+        participation_fee: 1_000,
+        participants: vec![creator, opponent],
+        submitters: vec![(video, creator)],
+        submission_votes: vec![(video, 0)],
+        ..open_duel(creator, opponent, 100)
+    };
+
+    assert_eq!(challenge.submission_fee(), 0);
+    let refund = challenge.take_refund(&creator).unwrap();
+    assert_eq!(refund.participation, 1_000);
+    assert_eq!(refund.submission, 0);
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::Pubkey;
+use coinpetitive::payout::plan_finalization;
+use coinpetitive::state::Challenge;
+
+// A CPT challenge whose treasury holds `fees`, with `votes[i]` votes on the
+// i-th submission
+fn voted_challenge(reward: u64, fees: u64, votes: &[u64]) -> (Challenge, Vec<Pubkey>) {
+    let mut challenge = Challenge {
+        reward,
+        challenge_treasury: fees,
+        ..Default::default()
+    };
+    let submissions: Vec<Pubkey> = votes.iter().map(|_| Pubkey::new_unique()).collect();
+    for (submission, count) in submissions.iter().zip(votes) {
+        challenge
+            .submitters
+            .push((*submission, Pubkey::new_unique()));
+        for _ in 0..*count {
+            challenge
+                .add_vote(Pubkey::new_unique(), *submission)
+                .unwrap();
+        }
+    }
+    (challenge, submissions)
+}
+
+#[test]
+fn odd_rewards_are_split_without_losing_a_token() {
+    let (challenge, submissions) = voted_challenge(1_001, 1_500, &[1, 3]);
+    let plan = plan_finalization(&challenge, 1_000).unwrap();
+
+    // 2.1% of 1,001 rounds down to 21, leaving 980 split 735 / 245
+    assert_eq!(plan.platform_fee, 21);
+    assert_eq!(plan.crank_bounty, 2);
+    assert_eq!(plan.places.len(), 2);
+    assert_eq!(plan.places[0].submission, submissions[1]);
+    assert_eq!(plan.places[0].amount, 735);
+    assert_eq!(plan.places[1].submission, submissions[0]);
+    assert_eq!(plan.places[1].amount, 245);
+    assert_eq!(
+        plan.platform_fee + plan.places[0].amount + plan.places[1].amount,
+        1_001
+    );
+
+    // The creator keeps whatever fees the reward didn't use
+    assert_eq!(plan.judge_fee, 0);
+    assert_eq!(plan.creator_remainder, 499);
+}
+
+#[test]
+fn a_lone_submission_is_paid_the_winners_share_only() {
+    let (challenge, submissions) = voted_challenge(999, 999, &[2]);
+    let plan = plan_finalization(&challenge, 0).unwrap();
+
+    assert_eq!(plan.platform_fee, 20);
+    assert_eq!(plan.crank_bounty, 0);
+    assert_eq!(plan.places.len(), 1);
+    assert_eq!(plan.places[0].submission, submissions[0]);
+    assert_eq!(plan.places[0].amount, 734);
+    assert_eq!(plan.creator_remainder, 0);
+}
+
+#[test]
+fn fees_short_of_the_reward_cannot_be_finalized() {
+    let (challenge, _) = voted_challenge(1_001, 1_000, &[1, 1]);
+    assert!(plan_finalization(&challenge, 0).is_err());
+
+    let (challenge, _) = voted_challenge(1_001, 1_001, &[0, 0]);
+    assert!(plan_finalization(&challenge, 0).is_err());
+}
@@ -0,0 +1,152 @@
+use anchor_lang::prelude::Pubkey;
+use coinpetitive::constraints::FIXED_SUBMISSION_FEE;
+use coinpetitive::state::{Challenge, Refund, Team};
+
+#[test]
+fn a_challenge_short_of_participants_refunds_everyone_once() {
+    let (a, b, voter) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut challenge = Challenge {
+        participants: vec![a, b],
+        submitters: vec![(first, a), (second, b)],
+        min_participants: 3,
+        submission_end: 100,
+        voting_end: 200,
+        ..Default::default()
+    };
+    challenge.add_vote(voter, first).unwrap();
+    challenge.add_vote(voter, second).unwrap();
+    challenge.add_vote(b, first).unwrap();
+
+    // Entries can still come in until submission_end
+    assert!(!challenge.quorum_missed(99));
+    assert!(challenge.quorum_missed(100));
+    assert_eq!(challenge.refundable_wallets(), vec![a, b, voter]);
+}
+
+#[test]
+fn withdrawn_votes_are_refunded_at_their_weight() {
+    let (voter, submission) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut challenge = Challenge::default();
+    challenge.add_weighted_vote(voter, submission, 4).unwrap();
+    challenge
+        .add_weighted_vote(Pubkey::new_unique(), submission, 2)
+        .unwrap();
+    challenge
+        .add_weighted_vote(voter, Pubkey::new_unique(), 1)
+        .unwrap();
+
+    assert_eq!(challenge.remove_votes_of(&voter), vec![4, 1]);
+    assert_eq!(challenge.voters.len(), 1);
+    assert_eq!(challenge.vote_weight(0), 2);
+}
+
+#[test]
+fn struck_entries_are_not_refunded_twice() {
+    let wallet = Pubkey::new_unique();
+    let (struck, kept, other) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let mut challenge = Challenge {
+        submitters: vec![
+            (struck, wallet),
+            (other, Pubkey::new_unique()),
+            (kept, wallet),
+        ],
+        ..Default::default()
+    };
+    challenge.disqualify(&struck).unwrap();
+
+    assert_eq!(challenge.remove_submissions_of(&wallet), 1);
+    assert_eq!(challenge.submitters.len(), 1);
+    // The entry left behind moved down and isn't marked disqualified
+    assert!(!challenge.is_disqualified(&other));
+}
+
+#[test]
+fn a_cancelled_challenge_refunds_everything_and_never_pays_its_creator() {
+    let (a, b, voter) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut challenge = Challenge {
+        is_active: true,
+        participation_fee: 100,
+        voting_fee: 10,
+        participants: vec![a, b],
+        submitters: vec![(first, a), (second, b)],
+        challenge_treasury: 200 + 2 * FIXED_SUBMISSION_FEE,
+        voting_treasury: 30,
+        ..Default::default()
+    };
+    challenge.add_vote(voter, first).unwrap();
+    challenge.add_vote(voter, second).unwrap();
+    challenge.add_vote(a, second).unwrap();
+
+    // What cancel_challenge leaves behind
+    challenge.is_active = false;
+    challenge.is_cancelled = true;
+    assert!(!challenge.is_settled());
+
+    for wallet in challenge.refundable_wallets() {
+        let refund = challenge.take_refund(&wallet).unwrap();
+        challenge
+            .debit_treasury(refund.participation + refund.submission)
+            .unwrap();
+        challenge.debit_voting_treasury(refund.voting).unwrap();
+        assert_eq!(challenge.take_refund(&wallet).unwrap(), Refund::default());
+    }
+    assert_eq!(challenge.challenge_treasury, 0);
+    assert_eq!(challenge.voting_treasury, 0);
+}
+
+#[test]
+fn a_team_that_never_entered_hands_back_each_paid_share() {
+    let (captain, member, late) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let mut team = Team {
+        members: vec![captain, member, late],
+        shares_bps: vec![5_000, 3_000, 2_000],
+        paid: vec![true, true, false],
+        ..Default::default()
+    };
+    let challenge = Challenge {
+        is_active: true,
+        participation_fee: 1_001,
+        submission_end: 100,
+        ..Default::default()
+    };
+
+    // The team could still complete its fee while entries are open
+    assert!(!challenge.team_fees_refundable(99));
+    assert!(challenge.team_fees_refundable(100));
+
+    // The captain's part carries the rounding dust, as it did when paid
+    assert_eq!(
+        team.take_part(&captain, challenge.participation_fee),
+        Some(501)
+    );
+    assert_eq!(
+        team.take_part(&member, challenge.participation_fee),
+        Some(300)
+    );
+    assert_eq!(team.take_part(&captain, challenge.participation_fee), None);
+    assert_eq!(team.take_part(&late, challenge.participation_fee), None);
+    assert!(team.paid.iter().all(|&paid| !paid));
+
+    // A team that entered is refunded as a participant instead
+    team.paid = vec![true; 3];
+    team.entered = true;
+    assert_eq!(team.take_part(&captain, challenge.participation_fee), None);
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::Pubkey;
+use coinpetitive::constraints::{SEASON_POINTS_FOR_ACCURATE_VOTE, SEASON_POINTS_FOR_WIN};
+use coinpetitive::instructions::challenge::profile::{award_season_points, claim_accurate_vote};
+use coinpetitive::state::{Challenge, Season, UserProfile};
+
+// A challenge settled in `season` whose winner `voter` backed
+fn won_vote(season: u64, voter: Pubkey) -> (Challenge, usize) {
+    let winner = Pubkey::new_unique();
+    let mut challenge = Challenge {
+        season,
+        submitters: vec![(winner, Pubkey::new_unique())],
+        ..Default::default()
+    };
+    challenge.add_vote(voter, winner).unwrap();
+    challenge.winner = Some(winner);
+    let index = challenge.vote_index(&voter, &winner).unwrap();
+    (challenge, index)
+}
+
+#[test]
+fn repeated_claims_do_not_farm_season_points() {
+    let voter = Pubkey::new_unique();
+    let (mut challenge, index) = won_vote(1, voter);
+    let mut season = Season {
+        id: 1,
+        ..Default::default()
+    };
+    let mut profile = UserProfile {
+        wallet: voter,
+        ..Default::default()
+    };
+
+    claim_accurate_vote(&mut challenge, index, &mut profile, Some(&mut season)).unwrap();
+    assert!(claim_accurate_vote(&mut challenge, index, &mut profile, Some(&mut season)).is_err());
+    assert!(claim_accurate_vote(&mut challenge, index, &mut profile, Some(&mut season)).is_err());
+
+    assert_eq!(profile.season_points, SEASON_POINTS_FOR_ACCURATE_VOTE);
+    assert_eq!(season.leaderboard[0].wallet, voter);
+    assert_eq!(
+        season.leaderboard[0].points,
+        SEASON_POINTS_FOR_ACCURATE_VOTE
+    );
+    assert!(season.leaderboard[1].is_empty());
+}
+
+#[test]
+fn a_late_claim_leaves_both_seasons_totals_alone() {
+    let voter = Pubkey::new_unique();
+    let mut profile = UserProfile {
+        wallet: voter,
+        ..Default::default()
+    };
+    let mut first = Season {
+        id: 1,
+        ..Default::default()
+    };
+    let mut second = Season {
+        id: 2,
+        ..Default::default()
+    };
+
+    // A win in season 1, then the wallet moves on and wins in season 2
+    assert!(award_season_points(
+        &mut profile,
+        &mut first,
+        SEASON_POINTS_FOR_WIN
+    ));
+    assert!(award_season_points(
+        &mut profile,
+        &mut second,
+        SEASON_POINTS_FOR_WIN
+    ));
+
+    // A vote from season 1 claimed once season 2 is under way
+    let (mut challenge, index) = won_vote(1, voter);
+    claim_accurate_vote(&mut challenge, index, &mut profile, Some(&mut first)).unwrap();
+
+    assert_eq!(profile.season, 2);
+    assert_eq!(profile.season_points, SEASON_POINTS_FOR_WIN);
+    assert_eq!(first.leaderboard[0].points, SEASON_POINTS_FOR_WIN);
+    assert_eq!(second.leaderboard[0].points, SEASON_POINTS_FOR_WIN);
+
+    // The leaderboard never lowers a wallet's total either
+    first.record(voter, SEASON_POINTS_FOR_ACCURATE_VOTE);
+    assert_eq!(first.leaderboard[0].points, SEASON_POINTS_FOR_WIN);
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::Pubkey;
+use coinpetitive::payout;
+use coinpetitive::state::{Challenge, VotingMode};
+
+fn flat_challenge(winner: Pubkey, voters: &[Pubkey]) -> Challenge {
+    let mut challenge = Challenge {
+        voting_fee: 10,
+        submitters: vec![(winner, Pubkey::new_unique())],
+        ..Default::default()
+    };
+    for voter in voters {
+        challenge.add_vote(*voter, winner).unwrap();
+    }
+    challenge.winner = Some(winner);
+    challenge.voting_reward_pool = 1_000;
+    challenge.voting_treasury = 1_000;
+    challenge
+}
+
+#[test]
+fn flat_rewards_are_pending_until_every_winning_vote_is_paid() {
+    let winner = Pubkey::new_unique();
+    let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut challenge = flat_challenge(winner, &[alice, bob]);
+
+    assert_eq!(challenge.voters_for(&winner), 2);
+    assert_eq!(challenge.vote_index(&bob, &winner), Some(1));
+    assert_eq!(payout::voter_reward(&challenge, &alice, 2), 500);
+    assert!(payout::voter_rewards_pending(&challenge));
+
+    // distribute_voting_treasury records the vote's claim before paying it
+    challenge.record_voter_reward(0).unwrap();
+    assert!(challenge.voter_rewarded(0));
+    assert!(!challenge.voter_rewarded(1));
+    assert!(payout::voter_rewards_pending(&challenge));
+
+    challenge.record_voter_reward(1).unwrap();
+    assert!(!payout::voter_rewards_pending(&challenge));
+}
+
+#[test]
+fn a_winning_vote_can_only_be_claimed_once() {
+    let winner = Pubkey::new_unique();
+    let voter = Pubkey::new_unique();
+    let mut challenge = flat_challenge(winner, &[voter]);
+
+    // The reputation and season points for an accurate vote are credited
+    // after this record, so a repeated claim fails before reaching them
+    let index = challenge.vote_index(&voter, &winner).unwrap();
+    challenge.record_voter_reward(index).unwrap();
+    assert!(challenge.record_voter_reward(index).is_err());
+    assert!(challenge.voter_rewarded(index));
+}
+
+#[test]
+fn the_winning_voters_count_comes_from_the_votes_on_record() {
+    let winner = Pubkey::new_unique();
+    let voters: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let mut challenge = flat_challenge(winner, &voters);
+    assert!(challenge.add_vote(voters[0], winner).is_err());
+
+    // Votes on other submissions don't count towards the winner's voters
+    let other = Pubkey::new_unique();
+    challenge.submitters.push((other, Pubkey::new_unique()));
+    challenge.add_vote(Pubkey::new_unique(), other).unwrap();
+    assert_eq!(challenge.voters_for(&winner), 4);
+    assert_eq!(challenge.voting_mode, VotingMode::Flat);
+}